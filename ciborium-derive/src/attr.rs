@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr, Path, Result, Token, Type};
+
+/// The key under which a field is stored in a map
+#[derive(Clone, PartialEq, Eq)]
+pub enum Key {
+    Integer(i128),
+    Text(String),
+}
+
+impl ToTokens for Key {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Key::Integer(x) if *x >= 0 => Literal::u64_suffixed(*x as u64).to_tokens(tokens),
+            Key::Integer(x) => Literal::i64_suffixed(*x as i64).to_tokens(tokens),
+            Key::Text(x) => x.to_tokens(tokens),
+        }
+    }
+}
+
+/// A field of the struct, along with its attributes
+pub struct Field {
+    pub ident: Ident,
+    pub ty: Type,
+    pub key: Key,
+    pub with: Option<Path>,
+}
+
+/// A struct, along with its attributes
+pub struct Container {
+    pub as_array: bool,
+    pub tag: Option<u64>,
+    pub fields: Vec<Field>,
+}
+
+impl Container {
+    pub fn parse(input: &DeriveInput) -> Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "only structs with named fields are supported",
+                    ))
+                }
+            },
+
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "only structs with named fields are supported",
+                ))
+            }
+        };
+
+        let mut container = Self {
+            as_array: false,
+            tag: None,
+            fields: Vec::new(),
+        };
+
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("cbor")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("as_array") {
+                    container.as_array = true;
+                } else if meta.path.is_ident("tag") {
+                    container.tag = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else {
+                    return Err(meta.error("unknown container attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        for field in fields {
+            let ident = field.ident.clone().unwrap();
+            let mut key = None;
+            let mut with = None;
+
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("cbor")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("key") {
+                        let value = meta.value()?;
+
+                        key = Some(if value.peek(LitStr) {
+                            Key::Text(value.parse::<LitStr>()?.value())
+                        } else {
+                            let negative = value.parse::<Option<Token![-]>>()?.is_some();
+                            let lit = value.parse::<LitInt>()?;
+                            let x: i128 = lit.base10_parse()?;
+                            let x = if negative { -x } else { x };
+
+                            if x < i64::MIN.into() || x > u64::MAX.into() {
+                                return Err(Error::new(lit.span(), "integer key out of range"));
+                            }
+
+                            Key::Integer(x)
+                        });
+                    } else if meta.path.is_ident("with") {
+                        with = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                    } else {
+                        return Err(meta.error("unknown field attribute"));
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            if container.as_array && key.is_some() {
+                return Err(Error::new(
+                    ident.span(),
+                    "fields of structs encoded as arrays have no keys",
+                ));
+            }
+
+            let key = key.unwrap_or_else(|| Key::Text(ident.to_string()));
+            if container.fields.iter().any(|f| f.key == key) {
+                return Err(Error::new(ident.span(), "duplicate key"));
+            }
+
+            container.fields.push(Field {
+                ident,
+                ty: field.ty.clone(),
+                key,
+                with,
+            });
+        }
+
+        Ok(container)
+    }
+}
+
+/// The generated name of a per-field item
+pub fn generated(prefix: &str, index: usize) -> Ident {
+    Ident::new(&format!("{}{}", prefix, index), Span::call_site())
+}
+
+/// The `PhantomData` of the struct, used by generated wrappers
+pub fn phantom(input: &DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    quote!(::core::marker::PhantomData<#name #ty_generics>)
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::attr::{Container, Key};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Result};
+
+/// Quotes `x` as a CDDL text string, using JSON-style escapes
+fn text(x: &str) -> String {
+    let mut out = String::from('"');
+    for c in x.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let container = Container::parse(input)?;
+    let name = &input.ident;
+
+    let mut bounded = input.generics.clone();
+    for param in input.generics.type_params() {
+        let ident = &param.ident;
+        bounded
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident: ::ciborium::cddl::Cddl));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = bounded.split_for_impl();
+
+    // Fields using a `with` module have an unknown encoding.
+    let mut entries = Vec::new();
+    for field in &container.fields {
+        let ty = &field.ty;
+        let cddl = match field.with {
+            None => quote!(<#ty as ::ciborium::cddl::Cddl>::cddl(__schema)),
+            Some(..) => quote!(alloc::string::String::from("any")),
+        };
+
+        let key = match (&field.key, container.as_array) {
+            (_, true) => format!("{}: ", field.ident),
+            (Key::Integer(x), false) => format!("{}: ", x),
+            (Key::Text(x), false) => format!("{}: ", text(x)),
+        };
+
+        entries.push(quote!(alloc::format!("{}{}", #key, #cddl)));
+    }
+
+    let (open, close) = match container.as_array {
+        true => ("[", "]"),
+        false => ("{", "}"),
+    };
+
+    let mut definition = quote! {
+        {
+            let __entries: &[alloc::string::String] = &[#(#entries),*];
+            alloc::format!("{} {} {}", #open, __entries.join(", "), #close)
+        }
+    };
+
+    if let Some(tag) = container.tag {
+        let prefix = format!("#6.{}(", tag);
+        definition = quote!(alloc::format!("{}{})", #prefix, #definition));
+    }
+
+    // Generic structs are described inline, since their rules would depend
+    // on the type parameters.
+    let body = match input.generics.type_params().next() {
+        Some(..) => definition,
+        None => {
+            let name = name.to_string();
+            quote!(__schema.rule(#name, |__schema| #definition))
+        }
+    };
+
+    Ok(quote! {
+        #[doc(hidden)]
+        const _: () = {
+            extern crate alloc;
+
+            #[automatically_derived]
+            impl #impl_generics ::ciborium::cddl::Cddl for #name #ty_generics #where_clause {
+                fn cddl(__schema: &mut ::ciborium::cddl::Schema) -> alloc::string::String {
+                    #body
+                }
+            }
+        };
+    })
+}
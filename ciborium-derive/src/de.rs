@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::attr::{generated, phantom, Container, Key};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, LifetimeParam, Result};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let container = Container::parse(input)?;
+    let name = &input.ident;
+    let expecting = format!("struct {}", name);
+    let phantom = phantom(input);
+
+    let (decl_generics, ty_generics, decl_where) = input.generics.split_for_impl();
+
+    // The `'de` lifetime outlives all lifetimes of the struct.
+    let mut bounded = input.generics.clone();
+    let mut de: LifetimeParam = parse_quote!('de);
+    de.bounds
+        .extend(input.generics.lifetimes().map(|l| l.lifetime.clone()));
+    bounded.params.insert(0, de.into());
+    for param in input.generics.type_params() {
+        let ident = &param.ident;
+        bounded
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident: ::serde::Deserialize<'de>));
+    }
+
+    let (impl_generics, _, where_clause) = bounded.split_for_impl();
+
+    // Fields deserialized through a `with` module are wrapped in a type which
+    // forwards to the module's `deserialize` function.
+    let mut wrappers = Vec::new();
+    let mut types = Vec::new();
+    let mut unwraps = Vec::new();
+    for (index, field) in container.fields.iter().enumerate() {
+        let ty = &field.ty;
+
+        match &field.with {
+            None => {
+                types.push(quote!(#ty));
+                unwraps.push(quote!());
+            }
+
+            Some(path) => {
+                let wrap = generated("__DeserializeWith", index);
+                types.push(quote!(#wrap #ty_generics));
+                unwraps.push(quote!(.0));
+                wrappers.push(quote! {
+                    struct #wrap #decl_generics (#ty, #phantom) #decl_where;
+
+                    #[automatically_derived]
+                    impl #impl_generics ::serde::Deserialize<'de> for #wrap #ty_generics #where_clause {
+                        fn deserialize<__D: ::serde::Deserializer<'de>>(
+                            __deserializer: __D,
+                        ) -> ::core::result::Result<Self, __D::Error> {
+                            #path::deserialize(__deserializer)
+                                .map(|__value| Self(__value, ::core::marker::PhantomData))
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    let idents: Vec<_> = container.fields.iter().map(|f| &f.ident).collect();
+    let vars: Vec<_> = (0..idents.len()).map(|i| generated("__field", i)).collect();
+
+    let (visit, body) = match container.as_array {
+        true => {
+            let indices = 0..vars.len();
+            let visit = quote! {
+                fn visit_seq<__A: ::serde::de::SeqAccess<'de>>(
+                    self,
+                    mut __seq: __A,
+                ) -> ::core::result::Result<Self::Value, __A::Error> {
+                    #(
+                        let #vars = match ::serde::de::SeqAccess::next_element::<#types>(&mut __seq)? {
+                            ::core::option::Option::Some(__value) => __value #unwraps,
+                            ::core::option::Option::None => {
+                                return ::core::result::Result::Err(
+                                    ::serde::de::Error::invalid_length(#indices, &self),
+                                )
+                            }
+                        };
+                    )*
+
+                    ::core::result::Result::Ok(#name { #(#idents: #vars),* })
+                }
+            };
+
+            (
+                visit,
+                quote!(::serde::Deserializer::deserialize_seq(
+                    __deserializer,
+                    __Visitor(::core::marker::PhantomData)
+                )),
+            )
+        }
+
+        false => {
+            let names: Vec<_> = idents.iter().map(|i| i.to_string()).collect();
+            let visit = quote! {
+                fn visit_map<__A: ::serde::de::MapAccess<'de>>(
+                    self,
+                    mut __map: __A,
+                ) -> ::core::result::Result<Self::Value, __A::Error> {
+                    #(let mut #vars: ::core::option::Option<#types> = ::core::option::Option::None;)*
+
+                    while let ::core::option::Option::Some(__key) =
+                        ::serde::de::MapAccess::next_key::<__Field>(&mut __map)?
+                    {
+                        match __key {
+                            #(
+                                __Field::#vars => {
+                                    if #vars.is_some() {
+                                        return ::core::result::Result::Err(
+                                            ::serde::de::Error::duplicate_field(#names),
+                                        );
+                                    }
+
+                                    #vars = ::core::option::Option::Some(
+                                        ::serde::de::MapAccess::next_value(&mut __map)?,
+                                    );
+                                }
+                            )*
+
+                            __Field::__ignore => {
+                                ::serde::de::MapAccess::next_value::<::serde::de::IgnoredAny>(
+                                    &mut __map,
+                                )?;
+                            }
+                        }
+                    }
+
+                    // Missing fields are only acceptable for types which can
+                    // be deserialized from nothing, such as `Option`.
+                    #(
+                        let #vars = match #vars {
+                            ::core::option::Option::Some(__value) => __value #unwraps,
+                            ::core::option::Option::None => {
+                                <#types as ::serde::Deserialize>::deserialize(
+                                    ::serde::de::IntoDeserializer::<__A::Error>::into_deserializer(()),
+                                )
+                                .map_err(|_| ::serde::de::Error::missing_field(#names))?
+                                #unwraps
+                            }
+                        };
+                    )*
+
+                    ::core::result::Result::Ok(#name { #(#idents: #vars),* })
+                }
+            };
+
+            (
+                visit,
+                quote!(::serde::Deserializer::deserialize_map(
+                    __deserializer,
+                    __Visitor(::core::marker::PhantomData)
+                )),
+            )
+        }
+    };
+
+    let fields = field(&container, &vars);
+
+    // Tagged structs deserialize their body through `ciborium::tag::Required`.
+    let (body, tagged) = match container.tag {
+        None => (body, quote!()),
+        Some(tag) => (
+            quote! {
+                let ::ciborium::tag::Required(__Body(__value)) =
+                    <::ciborium::tag::Required<__Body #ty_generics, #tag> as ::serde::Deserialize>::deserialize(
+                        __deserializer,
+                    )?;
+                ::core::result::Result::Ok(__value)
+            },
+            quote! {
+                struct __Body #decl_generics (#name #ty_generics) #decl_where;
+
+                #[automatically_derived]
+                impl #impl_generics ::serde::Deserialize<'de> for __Body #ty_generics #where_clause {
+                    fn deserialize<__D: ::serde::Deserializer<'de>>(
+                        __deserializer: __D,
+                    ) -> ::core::result::Result<Self, __D::Error> {
+                        #body.map(__Body)
+                    }
+                }
+            },
+        ),
+    };
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, clippy::all)]
+        const _: () = {
+            #(#wrappers)*
+            #fields
+            #tagged
+
+            struct __Visitor #decl_generics (#phantom) #decl_where;
+
+            #[automatically_derived]
+            impl #impl_generics ::serde::de::Visitor<'de> for __Visitor #ty_generics #where_clause {
+                type Value = #name #ty_generics;
+
+                fn expecting(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    __f.write_str(#expecting)
+                }
+
+                #visit
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'de>>(
+                    __deserializer: __D,
+                ) -> ::core::result::Result<Self, __D::Error> {
+                    #body
+                }
+            }
+        };
+    })
+}
+
+/// Generates the identifier type matching map keys to fields
+fn field(container: &Container, vars: &[syn::Ident]) -> TokenStream {
+    if container.as_array {
+        return quote!();
+    }
+
+    let mut unsigned = Vec::new();
+    let mut signed = Vec::new();
+    let mut text = Vec::new();
+    for (field, var) in container.fields.iter().zip(vars) {
+        match &field.key {
+            Key::Integer(x) => {
+                if let Ok(x) = u64::try_from(*x) {
+                    unsigned.push(quote!(#x => __Field::#var,));
+                }
+
+                if let Ok(x) = i64::try_from(*x) {
+                    signed.push(quote!(#x => __Field::#var,));
+                }
+            }
+
+            Key::Text(x) => {
+                let bytes = syn::LitByteStr::new(x.as_bytes(), proc_macro2::Span::call_site());
+                text.push((
+                    quote!(#x => __Field::#var,),
+                    quote!(#bytes => __Field::#var,),
+                ));
+            }
+        }
+    }
+
+    let (strs, bytes): (Vec<_>, Vec<_>) = text.into_iter().unzip();
+
+    quote! {
+        enum __Field {
+            #(#vars,)*
+            __ignore,
+        }
+
+        struct __FieldVisitor;
+
+        #[automatically_derived]
+        impl<'de> ::serde::de::Visitor<'de> for __FieldVisitor {
+            type Value = __Field;
+
+            fn expecting(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                __f.write_str("a field key")
+            }
+
+            fn visit_u64<__E: ::serde::de::Error>(self, __v: u64) -> ::core::result::Result<__Field, __E> {
+                ::core::result::Result::Ok(match __v {
+                    #(#unsigned)*
+                    _ => __Field::__ignore,
+                })
+            }
+
+            fn visit_i64<__E: ::serde::de::Error>(self, __v: i64) -> ::core::result::Result<__Field, __E> {
+                ::core::result::Result::Ok(match __v {
+                    #(#signed)*
+                    _ => __Field::__ignore,
+                })
+            }
+
+            fn visit_str<__E: ::serde::de::Error>(self, __v: &str) -> ::core::result::Result<__Field, __E> {
+                ::core::result::Result::Ok(match __v {
+                    #(#strs)*
+                    _ => __Field::__ignore,
+                })
+            }
+
+            fn visit_bytes<__E: ::serde::de::Error>(self, __v: &[u8]) -> ::core::result::Result<__Field, __E> {
+                ::core::result::Result::Ok(match __v {
+                    #(#bytes)*
+                    _ => __Field::__ignore,
+                })
+            }
+        }
+
+        #[automatically_derived]
+        impl<'de> ::serde::Deserialize<'de> for __Field {
+            fn deserialize<__D: ::serde::Deserializer<'de>>(
+                __deserializer: __D,
+            ) -> ::core::result::Result<Self, __D::Error> {
+                ::serde::Deserializer::deserialize_any(__deserializer, __FieldVisitor)
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Derive macros for CBOR-specific serde implementations
+//!
+//! CBOR protocols commonly identify struct fields by small integers rather
+//! than by name, encode structs as arrays, or wrap them in a semantic tag.
+//! Expressing this with plain serde requires hand-written `Serialize` and
+//! `Deserialize` implementations. This crate provides the
+//! [`CborSerialize`] and [`CborDeserialize`] derive macros, which generate
+//! those implementations from attributes instead.
+//!
+//! ```
+//! use ciborium_derive::{CborDeserialize, CborSerialize};
+//!
+//! #[derive(Debug, PartialEq, CborSerialize, CborDeserialize)]
+//! #[cbor(tag = 1234)]
+//! struct Header {
+//!     #[cbor(key = 1)]
+//!     alg: i32,
+//!
+//!     #[cbor(key = 4, with = "serde_bytes")]
+//!     kid: Vec<u8>,
+//!
+//!     #[cbor(key = -1)]
+//!     extra: Option<u8>,
+//! }
+//!
+//! let header = Header { alg: -7, kid: vec![0xab], extra: None };
+//!
+//! let mut bytes = Vec::new();
+//! ciborium::into_writer(&header, &mut bytes).unwrap();
+//! assert_eq!(bytes, [0xd9, 0x04, 0xd2, 0xa3, 0x01, 0x26, 0x04, 0x41, 0xab, 0x20, 0xf6]);
+//!
+//! let decoded: Header = ciborium::from_reader(&bytes[..]).unwrap();
+//! assert_eq!(decoded, header);
+//! ```
+//!
+//! # Attributes
+//!
+//! On the struct:
+//!
+//!   * `#[cbor(as_array)]`: encode the fields as an array, in declaration
+//!     order, rather than as a map.
+//!   * `#[cbor(tag = N)]`: wrap the struct in tag `N`. The tag is required
+//!     during deserialization.
+//!
+//! On a field:
+//!
+//!   * `#[cbor(key = N)]` or `#[cbor(key = "name")]`: the map key of the
+//!     field. Integer keys may be negative. Defaults to the field name.
+//!   * `#[cbor(with = "module")]`: serialize and deserialize the field with
+//!     `module::serialize` and `module::deserialize`, as with serde's own
+//!     `with` attribute.
+//!
+//! Unknown map keys are ignored during deserialization. Missing fields are
+//! an error unless the field type can be deserialized from nothing, such as
+//! `Option`. Only structs with named fields are supported.
+//!
+//! The [`Cddl`] derive macro describes the same encoding as a CDDL schema
+//! using `ciborium::cddl`.
+//!
+//! The generated code refers to the `serde` crate and, for tagged structs,
+//! to the `ciborium` crate; both must be dependencies of the crate using the
+//! derive macros.
+
+#![deny(missing_docs)]
+#![deny(clippy::all)]
+#![deny(clippy::cargo)]
+
+mod attr;
+mod cddl;
+mod de;
+mod ser;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives `serde::Serialize` using the `#[cbor(...)]` attributes
+///
+/// See the [crate documentation](crate) for the supported attributes.
+#[proc_macro_derive(CborSerialize, attributes(cbor))]
+pub fn serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ser::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `serde::Deserialize` using the `#[cbor(...)]` attributes
+///
+/// See the [crate documentation](crate) for the supported attributes.
+#[proc_macro_derive(CborDeserialize, attributes(cbor))]
+pub fn deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    de::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ciborium::cddl::Cddl` using the `#[cbor(...)]` attributes
+///
+/// The derived schema describes the encoding generated by
+/// [`CborSerialize`]. Fields using a `with` module are described as `any`.
+#[proc_macro_derive(Cddl, attributes(cbor))]
+pub fn cddl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    cddl::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::attr::{generated, phantom, Container};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Result};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let container = Container::parse(input)?;
+    let name = &input.ident;
+    let phantom = phantom(input);
+
+    let mut bounded = input.generics.clone();
+    for param in input.generics.type_params() {
+        let ident = &param.ident;
+        bounded
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident: ::serde::Serialize));
+    }
+
+    let mut wrapper = bounded.clone();
+    wrapper.params.insert(0, parse_quote!('__a));
+
+    let (impl_generics, ty_generics, where_clause) = bounded.split_for_impl();
+    let (wrapper_impl, wrapper_ty, _) = wrapper.split_for_impl();
+
+    // Fields serialized through a `with` module are wrapped in a type which
+    // forwards to the module's `serialize` function.
+    let mut wrappers = Vec::new();
+    let mut values = Vec::new();
+    for (index, field) in container.fields.iter().enumerate() {
+        let ident = &field.ident;
+        let ty = &field.ty;
+
+        match &field.with {
+            None => values.push(quote!(&__value.#ident)),
+            Some(path) => {
+                let wrap = generated("__SerializeWith", index);
+                values.push(quote!(&#wrap(&__value.#ident, ::core::marker::PhantomData)));
+                wrappers.push(quote! {
+                    struct #wrap #wrapper_impl (&'__a #ty, #phantom) #where_clause;
+
+                    #[automatically_derived]
+                    impl #wrapper_impl ::serde::Serialize for #wrap #wrapper_ty #where_clause {
+                        fn serialize<__S: ::serde::Serializer>(
+                            &self,
+                            __serializer: __S,
+                        ) -> ::core::result::Result<__S::Ok, __S::Error> {
+                            #path::serialize(self.0, __serializer)
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    let len = container.fields.len();
+    let body = match container.as_array {
+        true => quote! {
+            let mut __seq = ::serde::Serializer::serialize_tuple(__serializer, #len)?;
+            #(::serde::ser::SerializeTuple::serialize_element(&mut __seq, #values)?;)*
+            ::serde::ser::SerializeTuple::end(__seq)
+        },
+
+        false => {
+            let keys = container.fields.iter().map(|f| &f.key);
+            quote! {
+                let mut __map = ::serde::Serializer::serialize_map(
+                    __serializer,
+                    ::core::option::Option::Some(#len),
+                )?;
+                #(::serde::ser::SerializeMap::serialize_entry(&mut __map, &#keys, #values)?;)*
+                ::serde::ser::SerializeMap::end(__map)
+            }
+        }
+    };
+
+    // Tagged structs serialize their body through `ciborium::tag::Required`.
+    let (body, tagged) = match container.tag {
+        None => (quote!(let __value = self; #body), quote!()),
+        Some(tag) => (
+            quote! {
+                ::serde::Serialize::serialize(
+                    &::ciborium::tag::Required::<_, #tag>(__Body(self)),
+                    __serializer,
+                )
+            },
+            quote! {
+                struct __Body #wrapper_impl (&'__a #name #ty_generics) #where_clause;
+
+                #[automatically_derived]
+                impl #wrapper_impl ::serde::Serialize for __Body #wrapper_ty #where_clause {
+                    fn serialize<__S: ::serde::Serializer>(
+                        &self,
+                        __serializer: __S,
+                    ) -> ::core::result::Result<__S::Ok, __S::Error> {
+                        let __value = self.0;
+                        #body
+                    }
+                }
+            },
+        ),
+    };
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, clippy::all)]
+        const _: () = {
+            #(#wrappers)*
+            #tagged
+
+            #[automatically_derived]
+            impl #impl_generics ::serde::Serialize for #name #ty_generics #where_clause {
+                fn serialize<__S: ::serde::Serializer>(
+                    &self,
+                    __serializer: __S,
+                ) -> ::core::result::Result<__S::Ok, __S::Error> {
+                    #body
+                }
+            }
+        };
+    })
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::cddl::schema;
+use ciborium::{cbor, de::from_reader, into_writer, Value};
+use ciborium_derive::{CborDeserialize, CborSerialize, Cddl};
+
+#[derive(Debug, PartialEq, CborSerialize, CborDeserialize, Cddl)]
+struct Keyed {
+    #[cbor(key = 1)]
+    alg: i32,
+
+    #[cbor(key = -2)]
+    curve: String,
+
+    #[cbor(key = "note")]
+    comment: Option<String>,
+
+    plain: bool,
+}
+
+#[derive(Debug, PartialEq, CborSerialize, CborDeserialize, Cddl)]
+#[cbor(as_array)]
+struct Positional {
+    id: u8,
+
+    #[cbor(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, CborSerialize, CborDeserialize, Cddl)]
+#[cbor(tag = 1234, as_array)]
+struct Tagged<T> {
+    inner: T,
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn keyed() {
+    let keyed = Keyed {
+        alg: -7,
+        curve: "P-256".into(),
+        comment: None,
+        plain: true,
+    };
+
+    let bytes = encode(&keyed);
+    let value: Value = from_reader(&bytes[..]).unwrap();
+    assert_eq!(
+        value,
+        cbor!({ 1 => -7, -2 => "P-256", "note" => null, "plain" => true }).unwrap()
+    );
+    assert_eq!(from_reader::<Keyed, _>(&bytes[..]).unwrap(), keyed);
+}
+
+#[test]
+fn keyed_lenient() {
+    // Unknown keys are skipped, missing options become `None`.
+    let value = cbor!({ "plain" => false, 7 => [1, 2], -2 => "X25519", 1 => 4 }).unwrap();
+    let keyed: Keyed = from_reader(&encode(&value)[..]).unwrap();
+    assert_eq!(
+        keyed,
+        Keyed {
+            alg: 4,
+            curve: "X25519".into(),
+            comment: None,
+            plain: false,
+        }
+    );
+}
+
+#[test]
+fn keyed_invalid() {
+    let missing = cbor!({ 1 => -7, "plain" => true }).unwrap();
+    let err = from_reader::<Keyed, _>(&encode(&missing)[..]).unwrap_err();
+    assert!(err.to_string().contains("missing field `curve`"), "{}", err);
+
+    let duplicate = Value::Map(vec![
+        (1.into(), 1.into()),
+        ((-2).into(), "a".into()),
+        ("plain".into(), true.into()),
+        (1.into(), 2.into()),
+    ]);
+    let err = from_reader::<Keyed, _>(&encode(&duplicate)[..]).unwrap_err();
+    assert!(err.to_string().contains("duplicate field `alg`"), "{}", err);
+}
+
+#[test]
+fn positional() {
+    let positional = Positional {
+        id: 9,
+        data: vec![1, 2],
+    };
+
+    let bytes = encode(&positional);
+    assert_eq!(hex::encode(&bytes), "8209420102");
+    assert_eq!(
+        from_reader::<Positional, _>(&bytes[..]).unwrap(),
+        positional
+    );
+
+    from_reader::<Positional, _>(&hex::decode("8109").unwrap()[..]).unwrap_err();
+}
+
+#[test]
+fn tagged() {
+    let tagged = Tagged { inner: 5u8 };
+
+    let bytes = encode(&tagged);
+    assert_eq!(hex::encode(&bytes), "d904d28105");
+    assert_eq!(from_reader::<Tagged<u8>, _>(&bytes[..]).unwrap(), tagged);
+
+    // The tag is required.
+    from_reader::<Tagged<u8>, _>(&hex::decode("8105").unwrap()[..]).unwrap_err();
+    from_reader::<Tagged<u8>, _>(&hex::decode("d904d38105").unwrap()[..]).unwrap_err();
+}
+
+#[derive(Cddl)]
+#[cbor(tag = 7)]
+#[allow(dead_code)]
+struct Envelope {
+    keyed: Keyed,
+    positional: Option<Positional>,
+    tagged: Tagged<String>,
+}
+
+#[derive(Cddl)]
+#[allow(dead_code)]
+struct Quoted {
+    #[cbor(key = "say \"hi\"\\\u{1}é")]
+    text: String,
+}
+
+#[test]
+fn cddl() {
+    assert_eq!(
+        schema::<Envelope>(),
+        concat!(
+            "Envelope = #6.7({ \"keyed\": Keyed, \"positional\": Positional / null, ",
+            "\"tagged\": #6.1234([ inner: tstr ]) })\n",
+            "Keyed = { 1: int, -2: tstr, \"note\": tstr / null, \"plain\": bool }\n",
+            "Positional = [ id: uint, data: any ]\n",
+        )
+    );
+}
+
+#[test]
+fn cddl_escapes() {
+    assert_eq!(
+        schema::<Quoted>(),
+        "Quoted = { \"say \\\"hi\\\"\\\\\\u0001é\": tstr }\n",
+    );
+}
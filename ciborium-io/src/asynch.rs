@@ -0,0 +1,55 @@
+use crate::{Read, Write};
+
+/// A trait indicating a type that can read bytes asynchronously
+///
+/// This is the asynchronous counterpart of [`Read`], and needs no runtime:
+/// it works with any executor, including those for `no_std` firmware such
+/// as Embassy or RTIC. Every [`Read`] is also an `AsyncRead` whose reads
+/// complete immediately.
+#[allow(async_fn_in_trait)]
+pub trait AsyncRead {
+    /// The error type
+    type Error;
+
+    /// Reads exactly `data.len()` bytes or fails
+    async fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+impl<R: Read> AsyncRead for R {
+    type Error = R::Error;
+
+    #[inline]
+    async fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        Read::read_exact(self, data)
+    }
+}
+
+/// A trait indicating a type that can write bytes asynchronously
+///
+/// This is the asynchronous counterpart of [`Write`]. Every [`Write`] is
+/// also an `AsyncWrite` whose writes complete immediately.
+#[allow(async_fn_in_trait)]
+pub trait AsyncWrite {
+    /// The error type
+    type Error;
+
+    /// Writes all bytes from `data` or fails
+    async fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Flushes all output
+    async fn flush(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<W: Write> AsyncWrite for W {
+    type Error = W::Error;
+
+    #[inline]
+    async fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        Write::write_all(self, data)
+    }
+
+    #[inline]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Write::flush(self)
+    }
+}
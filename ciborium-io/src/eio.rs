@@ -1,5 +1,8 @@
 use crate::{Read, Write};
 
+#[cfg(feature = "embedded-io-async")]
+use crate::{AsyncRead, AsyncWrite};
+
 /// Wrapper around W: embedded_io::Write implementing ciborium::Write
 pub struct EIOWriter<'a, W>(&'a mut W);
 
@@ -45,3 +48,55 @@ where
         embedded_io::Read::read_exact(self.0, data)
     }
 }
+
+/// Wrapper around W: embedded_io_async::Write implementing ciborium::AsyncWrite
+#[cfg(feature = "embedded-io-async")]
+pub struct EIOAsyncWriter<'a, W>(&'a mut W);
+
+#[cfg(feature = "embedded-io-async")]
+impl<'a, W> EIOAsyncWriter<'a, W> {
+    /// construct EIOAsyncWriter for embedded_io_async::Write
+    pub fn from(writer: &'a mut W) -> Self {
+        Self(writer)
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<'a, W> AsyncWrite for EIOAsyncWriter<'a, W>
+where
+    W: embedded_io_async::Write,
+{
+    type Error = W::Error;
+
+    async fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        embedded_io_async::Write::write_all(self.0, data).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        embedded_io_async::Write::flush(self.0).await
+    }
+}
+
+/// Wrapper around R: embedded_io_async::Read implementing ciborium::AsyncRead
+#[cfg(feature = "embedded-io-async")]
+pub struct EIOAsyncReader<'a, R>(&'a mut R);
+
+#[cfg(feature = "embedded-io-async")]
+impl<'a, R> EIOAsyncReader<'a, R> {
+    /// construct EIOAsyncReader for embedded_io_async::Read
+    pub fn from(reader: &'a mut R) -> Self {
+        Self(reader)
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<'a, R> AsyncRead for EIOAsyncReader<'a, R>
+where
+    R: embedded_io_async::Read,
+{
+    type Error = embedded_io_async::ReadExactError<R::Error>;
+
+    async fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        embedded_io_async::Read::read_exact(self.0, data).await
+    }
+}
@@ -12,9 +12,28 @@
 //! all `std::io` types. If the `alloc` feature is enabled, we provide
 //! implementations for `Vec<u8>`. In all cases, you get implementations
 //! for byte slices. You can, of course, implement the traits for your own
-//! types.
+//! types. The `Opaque` adapter wraps a reader or writer whose error type
+//! does not implement `Debug`.
+//!
+//! The blanket implementations rule out implementing the traits for your
+//! own types which also implement the `std::io` traits. The `std-adapters`
+//! feature provides everything else that needs `std`, including the
+//! `IoReader` and `IoWriter` adapters which implement the traits for a
+//! single wrapped `std::io` type; enable it instead of `std` to keep the
+//! blanket implementations out. Since features are unified, this only holds
+//! if nothing else in the build enables `std`.
+//!
+//! The `async` feature adds `AsyncRead` and `AsyncWrite`, asynchronous
+//! counterparts of these traits which need no runtime, so they work on
+//! embedded executors. Every `Read` and `Write` implements them. With the
+//! `embedded-io-async` feature, the `eio` module also adapts the traits of
+//! that crate. Both require Rust 1.75 or later.
+//!
+//! Without the `std` feature, the errors of the byte slice implementations
+//! implement `core::error::Error` when the `core-error` feature is enabled.
+//! This requires Rust 1.81 or later.
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "std-adapters"), no_std)]
 #![deny(missing_docs)]
 #![deny(clippy::all)]
 #![deny(clippy::cargo)]
@@ -26,6 +45,12 @@ extern crate alloc;
 #[cfg(feature = "embedded-io")]
 pub mod eio;
 
+#[cfg(feature = "async")]
+mod asynch;
+
+#[cfg(feature = "async")]
+pub use asynch::{AsyncRead, AsyncWrite};
+
 /// A trait indicating a type that can read bytes
 ///
 /// Note that this is similar to `std::io::Read`, but simplified for use in a
@@ -36,6 +61,29 @@ pub trait Read {
 
     /// Reads exactly `data.len()` bytes or fails
     fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Returns the contents of the internal buffer, filling it if empty
+    ///
+    /// Readers which keep their input in memory can expose it here so that
+    /// consumers may parse directly out of the buffer rather than issuing
+    /// many small `read_exact()` calls. The returned bytes are not consumed;
+    /// call `advance()` to do so. An empty slice does not indicate the end
+    /// of the input, only that no buffered bytes are available.
+    ///
+    /// The default implementation has no buffer and returns an empty slice.
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(&[])
+    }
+
+    /// Consumes `amount` bytes previously returned by `fill()`
+    ///
+    /// The `amount` must not exceed the length of the slice most recently
+    /// returned by `fill()`.
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        debug_assert_eq!(amount, 0);
+    }
 }
 
 /// A trait indicating a type that can write bytes
@@ -49,10 +97,60 @@ pub trait Write {
     /// Writes all bytes from `data` or fails
     fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error>;
 
+    /// Writes all bytes from each buffer in `bufs`, in order, or fails
+    ///
+    /// Writers which support gathering writes (such as sockets) can write
+    /// several buffers in a single call, rather than one call per buffer.
+    ///
+    /// The default implementation calls `write_all()` for each buffer.
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        for buf in bufs {
+            self.write_all(buf)?;
+        }
+
+        Ok(())
+    }
+
     /// Flushes all output
     fn flush(&mut self) -> Result<(), Self::Error>;
 }
 
+/// A trait indicating a reader that knows its position in a stream
+///
+/// Decoders start counting offsets at zero, so the offsets in their errors
+/// are relative to wherever the reader happened to be when decoding began.
+/// Readers which implement this trait let decoders report absolute offsets
+/// instead, which is useful when an item sits inside a larger framed
+/// stream.
+pub trait Position {
+    /// Returns the number of bytes consumed since the start of the stream
+    fn position(&self) -> u64;
+}
+
+impl<P: Position + ?Sized> Position for &mut P {
+    #[inline]
+    fn position(&self) -> u64 {
+        (**self).position()
+    }
+}
+
+#[cfg(feature = "std-adapters")]
+impl<T: AsRef<[u8]>> Position for std::io::Cursor<T> {
+    #[inline]
+    fn position(&self) -> u64 {
+        std::io::Cursor::position(self)
+    }
+}
+
+#[cfg(feature = "std-adapters")]
+impl<R: Position> Position for std::io::Take<R> {
+    #[inline]
+    fn position(&self) -> u64 {
+        self.get_ref().position()
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T: std::io::Read> Read for T {
     type Error = std::io::Error;
@@ -63,6 +161,105 @@ impl<T: std::io::Read> Read for T {
     }
 }
 
+/// An adapter implementing `Read` for a `std::io::Read` type
+///
+/// With the `std` feature, all `std::io::Read` types implement `Read`
+/// directly; this adapter does the same without the blanket implementation.
+#[cfg(feature = "std-adapters")]
+#[derive(Clone, Debug, Default)]
+pub struct IoReader<R>(pub R);
+
+#[cfg(feature = "std-adapters")]
+impl<R: std::io::Read> Read for IoReader<R> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read_exact(data)
+    }
+}
+
+#[cfg(feature = "std-adapters")]
+impl<R: Position> Position for IoReader<R> {
+    #[inline]
+    fn position(&self) -> u64 {
+        self.0.position()
+    }
+}
+
+/// An adapter implementing `Write` for a `std::io::Write` type
+///
+/// With the `std` feature, all `std::io::Write` types implement `Write`
+/// directly; this adapter does the same without the blanket implementation.
+#[cfg(feature = "std-adapters")]
+#[derive(Clone, Debug, Default)]
+pub struct IoWriter<W>(pub W);
+
+#[cfg(feature = "std-adapters")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(data)
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        write_all_vectored(&mut self.0, bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+/// An adapter exposing the buffer of a `std::io::BufRead` type
+///
+/// All `std::io::Read` types implement `Read`, but without access to any
+/// internal buffer. Wrapping a `std::io::BufRead` type (such as a
+/// `std::io::BufReader`) in this adapter makes its buffer available through
+/// `Read::fill()`, which allows decoders to parse short items in place.
+#[cfg(feature = "std-adapters")]
+#[derive(Debug)]
+pub struct Buffered<R>(R);
+
+#[cfg(feature = "std-adapters")]
+impl<R: std::io::BufRead> Buffered<R> {
+    /// Wraps a `std::io::BufRead` type
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self(reader)
+    }
+
+    /// Unwraps the `std::io::BufRead` type
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+#[cfg(feature = "std-adapters")]
+impl<R: std::io::BufRead> Read for Buffered<R> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read_exact(data)
+    }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        self.0.fill_buf()
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        self.0.consume(amount)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T: std::io::Write> Write for T {
     type Error = std::io::Error;
@@ -72,12 +269,67 @@ impl<T: std::io::Write> Write for T {
         self.write_all(data)
     }
 
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        write_all_vectored(self, bufs)
+    }
+
     #[inline]
     fn flush(&mut self) -> Result<(), Self::Error> {
         self.flush()
     }
 }
 
+/// Writes all of `bufs` using as few `write_vectored()` calls as possible
+#[cfg(feature = "std-adapters")]
+fn write_all_vectored<W: std::io::Write + ?Sized>(
+    writer: &mut W,
+    mut bufs: &[&[u8]],
+) -> std::io::Result<()> {
+    use std::io::{ErrorKind, IoSlice};
+
+    // The number of buffers passed to each `write_vectored()` call
+    const BATCH: usize = 8;
+
+    // The number of bytes of `bufs[0]` already written
+    let mut skip = 0;
+
+    loop {
+        while bufs.first().is_some_and(|buf| buf.len() == skip) {
+            bufs = &bufs[1..];
+            skip = 0;
+        }
+
+        if bufs.is_empty() {
+            return Ok(());
+        }
+
+        let mut slices = [IoSlice::new(&[]); BATCH];
+        for (slice, buf) in slices.iter_mut().zip(bufs) {
+            *slice = IoSlice::new(buf);
+        }
+        slices[0] = IoSlice::new(&bufs[0][skip..]);
+
+        let mut written = match writer.write_vectored(&slices[..bufs.len().min(BATCH)]) {
+            Ok(0) => return Err(ErrorKind::WriteZero.into()),
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        while let Some(buf) = bufs.first() {
+            if written < buf.len() - skip {
+                skip += written;
+                break;
+            }
+
+            written -= buf.len() - skip;
+            bufs = &bufs[1..];
+            skip = 0;
+        }
+    }
+}
+
 #[cfg(not(feature = "std"))]
 impl<R: Read + ?Sized> Read for &mut R {
     type Error = R::Error;
@@ -86,6 +338,16 @@ impl<R: Read + ?Sized> Read for &mut R {
     fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
         (**self).read_exact(data)
     }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        (**self).fill()
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        (**self).advance(amount)
+    }
 }
 
 #[cfg(not(feature = "std"))]
@@ -97,6 +359,11 @@ impl<W: Write + ?Sized> Write for &mut W {
         (**self).write_all(data)
     }
 
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        (**self).write_all_vectored(bufs)
+    }
+
     #[inline]
     fn flush(&mut self) -> Result<(), Self::Error> {
         (**self).flush()
@@ -108,6 +375,17 @@ impl<W: Write + ?Sized> Write for &mut W {
 #[derive(Clone, Debug)]
 pub struct EndOfFile(());
 
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for EndOfFile {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("unexpected end of file")
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "core-error"))]
+impl core::error::Error for EndOfFile {}
+
 #[cfg(not(feature = "std"))]
 impl Read for &[u8] {
     type Error = EndOfFile;
@@ -123,6 +401,16 @@ impl Read for &[u8] {
         *self = suffix;
         Ok(())
     }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        *self = &self[amount..];
+    }
 }
 
 /// An error indicating that the output cannot accept more bytes
@@ -130,6 +418,17 @@ impl Read for &[u8] {
 #[derive(Clone, Debug)]
 pub struct OutOfSpace(());
 
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for OutOfSpace {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("out of space")
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "core-error"))]
+impl core::error::Error for OutOfSpace {}
+
 #[cfg(not(feature = "std"))]
 impl Write for &mut [u8] {
     type Error = OutOfSpace;
@@ -168,9 +467,247 @@ impl Write for alloc::vec::Vec<u8> {
     }
 }
 
+/// A writer which fails once its output would exceed a byte budget
+///
+/// Writes which would take the total output past the limit fail with
+/// [`LimitError::Exceeded`] without writing any of their bytes, so producers
+/// bound by an MTU or a flash page fail fast instead of emitting truncated
+/// output.
+///
+/// ```
+/// use ciborium_io::{Limited, LimitError, Write};
+///
+/// let mut buffer = [0u8; 16];
+/// let mut writer = Limited::new(&mut buffer[..], 3);
+///
+/// writer.write_all(&[1, 2]).unwrap();
+/// assert!(matches!(writer.write_all(&[3, 4]), Err(LimitError::Exceeded)));
+/// assert_eq!(writer.remaining(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Limited<W> {
+    writer: W,
+    remaining: usize,
+}
+
+impl<W: Write> Limited<W> {
+    /// Wraps a writer, allowing at most `limit` bytes to be written to it
+    #[inline]
+    pub fn new(writer: W, limit: usize) -> Self {
+        Self {
+            writer,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of bytes which may still be written
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Unwraps the writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for Limited<W> {
+    type Error = LimitError<W::Error>;
+
+    #[inline]
+    fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        if data.len() > self.remaining {
+            return Err(LimitError::Exceeded);
+        }
+
+        self.writer.write_all(data).map_err(LimitError::Io)?;
+        self.remaining -= data.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        let len = bufs.iter().map(|buf| buf.len()).sum::<usize>();
+        if len > self.remaining {
+            return Err(LimitError::Exceeded);
+        }
+
+        self.writer
+            .write_all_vectored(bufs)
+            .map_err(LimitError::Io)?;
+        self.remaining -= len;
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.writer.flush().map_err(LimitError::Io)
+    }
+}
+
+/// An error returned by a [`Limited`] writer
+#[derive(Clone, Debug)]
+pub enum LimitError<E> {
+    /// An error occurred while writing to the underlying writer
+    Io(E),
+
+    /// The write would have exceeded the byte budget
+    Exceeded,
+}
+
+impl<E> core::fmt::Display for LimitError<E> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(..) => f.write_str("write to the underlying writer failed"),
+            Self::Exceeded => f.write_str("output limit exceeded"),
+        }
+    }
+}
+
+/// The error of the underlying writer is the source of [`LimitError::Io`]
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for LimitError<E> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Exceeded => None,
+        }
+    }
+}
+
+/// The error of the underlying writer is the source of [`LimitError::Io`]
+#[cfg(all(not(feature = "std"), feature = "core-error"))]
+impl<E: core::error::Error + 'static> core::error::Error for LimitError<E> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Exceeded => None,
+        }
+    }
+}
+
+/// An adapter hiding the error type of a reader or writer
+///
+/// Errors which carry the error of a reader or writer, such as those of
+/// `ciborium`, usually need it to implement `Debug` in order to format
+/// themselves. Wrapping a reader or writer whose error type does not gives
+/// it errors of type `Opaque<E>` instead, which format as `Opaque(..)`; the
+/// original error is the field of the wrapper.
+///
+/// ```
+/// use ciborium_io::{Opaque, Read};
+///
+/// struct Secret;
+///
+/// struct Broken;
+///
+/// impl Read for Broken {
+///     type Error = Secret;
+///
+///     fn read_exact(&mut self, _data: &mut [u8]) -> Result<(), Secret> {
+///         Err(Secret)
+///     }
+/// }
+///
+/// let error = Opaque(Broken).read_exact(&mut [0u8; 1]).unwrap_err();
+/// assert_eq!(format!("{:?}", error), "Opaque(..)");
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Opaque<T>(pub T);
+
+impl<T> core::fmt::Debug for Opaque<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Opaque(..)")
+    }
+}
+
+impl<R: Read> Read for Opaque<R> {
+    type Error = Opaque<R::Error>;
+
+    #[inline]
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read_exact(data).map_err(Opaque)
+    }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        self.0.fill().map_err(Opaque)
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        self.0.advance(amount)
+    }
+}
+
+impl<W: Write> Write for Opaque<W> {
+    type Error = Opaque<W::Error>;
+
+    #[inline]
+    fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(data).map_err(Opaque)
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.0.write_all_vectored(bufs).map_err(Opaque)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush().map_err(Opaque)
+    }
+}
+
+impl<P: Position> Position for Opaque<P> {
+    #[inline]
+    fn position(&self) -> u64 {
+        self.0.position()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::*;
+    #[cfg(feature = "std")]
+    use super::Buffered;
+    #[cfg(feature = "std-adapters")]
+    use super::Position;
+    use super::{LimitError, Limited, Read, Write};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn position() {
+        let mut cursor = std::io::Cursor::new([0u8; 8]);
+        Read::read_exact(&mut cursor, &mut [0u8; 3]).unwrap();
+        assert_eq!(Position::position(&cursor), 3);
+
+        let mut take = std::io::Read::take(&mut cursor, 4);
+        Read::read_exact(&mut take, &mut [0u8; 2]).unwrap();
+        assert_eq!(Position::position(&take), 5);
+    }
+
+    #[cfg(feature = "std-adapters")]
+    #[test]
+    fn adapters() {
+        let mut reader = super::IoReader(std::io::Cursor::new([1u8, 2, 3]));
+        let mut data = [0u8; 2];
+        reader.read_exact(&mut data).unwrap();
+        assert_eq!(data, [1, 2]);
+        assert_eq!(Position::position(&reader), 2);
+        assert!(reader.read_exact(&mut data).is_err());
+
+        let mut writer = super::IoWriter(Vec::new());
+        writer.write_all(&[1]).unwrap();
+        writer.write_all_vectored(&[&[2, 3], &[], &[4]]).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.0, [1, 2, 3, 4]);
+    }
 
     #[test]
     fn read_eof() {
@@ -216,6 +753,50 @@ mod test {
         assert_eq!(buffer[1], 1);
     }
 
+    #[test]
+    fn fill_default() {
+        struct Empty;
+
+        impl Read for Empty {
+            type Error = ();
+
+            fn read_exact(&mut self, _data: &mut [u8]) -> Result<(), Self::Error> {
+                Err(())
+            }
+        }
+
+        assert!(Empty.fill().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "std"))]
+    fn fill_slice() {
+        let mut reader = &[1u8, 2, 3][..];
+        let mut buffer = [0u8; 1];
+
+        assert_eq!(reader.fill().unwrap(), &[1, 2, 3]);
+        reader.advance(2);
+        assert_eq!(reader.fill().unwrap(), &[3]);
+
+        reader.read_exact(&mut buffer[..]).unwrap();
+        assert!(reader.fill().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn fill_buffered() {
+        let mut reader = Buffered::new(&[1u8, 2, 3][..]);
+        let mut buffer = [0u8; 1];
+
+        assert_eq!(reader.fill().unwrap(), &[1, 2, 3]);
+        reader.advance(2);
+        assert_eq!(reader.fill().unwrap(), &[3]);
+
+        reader.read_exact(&mut buffer[..]).unwrap();
+        assert_eq!(buffer[0], 3);
+        assert!(reader.fill().unwrap().is_empty());
+    }
+
     #[test]
     fn write_oos() {
         let mut writer = &mut [0u8; 0][..];
@@ -266,4 +847,99 @@ mod test {
         writer.write_all(&[1u8; 1][..]).unwrap();
         writer.write_all(&[1u8; 1][..]).unwrap();
     }
+
+    #[test]
+    fn write_vectored() {
+        let mut buffer = [0u8; 6];
+        let mut writer = &mut buffer[..];
+
+        writer.write_all_vectored(&[&[1, 2], &[], &[3]]).unwrap();
+        writer.write_all_vectored(&[&[4, 5], &[6, 7]]).unwrap_err();
+        assert_eq!(buffer[..3], [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_vectored_std() {
+        /// Accepts at most three bytes from the first two buffers per call
+        struct Short(alloc::vec::Vec<u8>, usize);
+
+        impl std::io::Write for Short {
+            fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+                self.write_vectored(&[std::io::IoSlice::new(data)])
+            }
+
+            fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+                self.1 += 1;
+
+                let mut written = 0;
+                for buf in bufs.iter().take(2) {
+                    let amount = buf.len().min(3 - written);
+                    self.0.extend_from_slice(&buf[..amount]);
+                    written += amount;
+                }
+
+                Ok(written)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = Short(alloc::vec::Vec::new(), 0);
+        let bufs: &[&[u8]] = &[&[1], &[], &[2, 3, 4, 5], &[], &[6], &[7, 8]];
+        Write::write_all_vectored(&mut writer, bufs).unwrap();
+
+        assert_eq!(writer.0, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(writer.1, 4);
+    }
+
+    #[test]
+    fn write_limited() {
+        let mut buffer = [0u8; 4];
+        let mut writer = Limited::new(&mut buffer[..], 3);
+
+        writer.write_all(&[1u8; 2][..]).unwrap();
+        assert!(matches!(
+            writer.write_all(&[2u8; 2][..]),
+            Err(LimitError::Exceeded)
+        ));
+        writer.write_all(&[3u8; 1][..]).unwrap();
+        assert!(matches!(
+            writer.write_all(&[4u8; 1][..]),
+            Err(LimitError::Exceeded)
+        ));
+
+        assert_eq!(writer.remaining(), 0);
+        assert_eq!(buffer, [1, 1, 3, 0]);
+    }
+
+    #[test]
+    fn write_limited_io() {
+        let mut buffer = [0u8; 1];
+        let mut writer = Limited::new(&mut buffer[..], 2);
+
+        assert!(matches!(
+            writer.write_all(&[1u8; 2][..]),
+            Err(LimitError::Io(..))
+        ));
+    }
+
+    #[test]
+    fn write_limited_vectored() {
+        let mut buffer = [0u8; 4];
+        let mut writer = Limited::new(&mut buffer[..], 3);
+
+        assert!(matches!(
+            writer.write_all_vectored(&[&[1u8; 2][..], &[2u8; 2][..]]),
+            Err(LimitError::Exceeded)
+        ));
+        writer
+            .write_all_vectored(&[&[1u8; 2][..], &[2u8; 1][..]])
+            .unwrap();
+
+        assert_eq!(writer.remaining(), 0);
+        assert_eq!(buffer, [1, 1, 2, 0]);
+    }
 }
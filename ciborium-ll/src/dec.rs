@@ -1,6 +1,6 @@
 use super::*;
 
-use ciborium_io::Read;
+use ciborium_io::{Position, Read};
 
 /// An error that occurred while decoding
 #[derive(Clone, Debug)]
@@ -13,7 +13,7 @@ pub enum Error<T> {
     /// An error occurred while parsing bytes
     ///
     /// Contains the offset into the stream where the syntax error occurred.
-    Syntax(usize),
+    Syntax(u64),
 }
 
 impl<T> From<T> for Error<T> {
@@ -23,6 +23,16 @@ impl<T> From<T> for Error<T> {
     }
 }
 
+impl<T: core::fmt::Debug> core::fmt::Display for Error<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl<T: core::fmt::Debug> core::error::Error for Error<T> {}
+
 /// A decoder for deserializing CBOR items
 ///
 /// This decoder manages the low-level decoding of CBOR items into `Header`
@@ -30,7 +40,7 @@ impl<T> From<T> for Error<T> {
 /// and text inputs.
 pub struct Decoder<R> {
     reader: R,
-    offset: usize,
+    offset: u64,
     buffer: Option<Title>,
 }
 
@@ -45,6 +55,22 @@ impl<R: Read> From<R> for Decoder<R> {
     }
 }
 
+impl<R: Read + Position> Decoder<R> {
+    /// Creates a decoder whose offsets start at the reader's position
+    ///
+    /// Unlike a decoder created with `From`, which counts offsets from zero,
+    /// the offsets of this decoder and its errors are absolute positions in
+    /// the stream, even if the reader was advanced beforehand.
+    #[inline]
+    pub fn with_position(reader: R) -> Self {
+        Self {
+            offset: reader.position(),
+            reader,
+            buffer: None,
+        }
+    }
+}
+
 impl<R: Read> Read for Decoder<R> {
     type Error = R::Error;
 
@@ -52,23 +78,57 @@ impl<R: Read> Read for Decoder<R> {
     fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
         assert!(self.buffer.is_none());
         self.reader.read_exact(data)?;
-        self.offset += data.len();
+        self.offset += data.len() as u64;
         Ok(())
     }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        assert!(self.buffer.is_none());
+        self.reader.fill()
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        assert!(self.buffer.is_none());
+        self.reader.advance(amount);
+        self.offset += amount as u64;
+    }
 }
 
 impl<R: Read> Decoder<R> {
     #[inline]
     fn pull_title(&mut self) -> Result<Title, Error<R::Error>> {
         if let Some(title) = self.buffer.take() {
-            self.offset += title.1.as_ref().len() + 1;
+            self.offset += title.1.as_ref().len() as u64 + 1;
             return Ok(title);
         }
 
+        // When the reader exposes its buffer, decode the title in place.
+        let buffered = self.reader.fill()?;
+        if let Some(&prefix) = buffered.first() {
+            let mut title = Self::title(prefix).ok_or(Error::Syntax(self.offset))?;
+            let len = title.1.as_ref().len();
+
+            if let Some(bytes) = buffered.get(1..=len) {
+                title.1.as_mut().copy_from_slice(bytes);
+                self.reader.advance(len + 1);
+                self.offset += len as u64 + 1;
+                return Ok(title);
+            }
+        }
+
         let mut prefix = [0u8; 1];
         self.read_exact(&mut prefix[..])?;
 
-        let major = match prefix[0] >> 5 {
+        let mut title = Self::title(prefix[0]).ok_or(Error::Syntax(self.offset - 1))?;
+        self.read_exact(title.1.as_mut())?;
+        Ok(title)
+    }
+
+    #[inline]
+    fn title(prefix: u8) -> Option<Title> {
+        let major = match prefix >> 5 {
             0 => Major::Positive,
             1 => Major::Negative,
             2 => Major::Bytes,
@@ -80,25 +140,24 @@ impl<R: Read> Decoder<R> {
             _ => unreachable!(),
         };
 
-        let mut minor = match prefix[0] & 0b00011111 {
+        let minor = match prefix & 0b00011111 {
             x if x < 24 => Minor::This(x),
             24 => Minor::Next1([0; 1]),
             25 => Minor::Next2([0; 2]),
             26 => Minor::Next4([0; 4]),
             27 => Minor::Next8([0; 8]),
             31 => Minor::More,
-            _ => return Err(Error::Syntax(self.offset - 1)),
+            _ => return None,
         };
 
-        self.read_exact(minor.as_mut())?;
-        Ok(Title(major, minor))
+        Some(Title(major, minor))
     }
 
     #[inline]
     fn push_title(&mut self, item: Title) {
         assert!(self.buffer.is_none());
         self.buffer = Some(item);
-        self.offset -= item.1.as_ref().len() + 1;
+        self.offset -= item.1.as_ref().len() as u64 + 1;
     }
 
     /// Pulls the next header from the input
@@ -126,12 +185,59 @@ impl<R: Read> Decoder<R> {
     ///
     /// The offset starts at zero when the decoder is created. Therefore, if
     /// bytes were already read from the reader before the decoder was created,
-    /// you must account for this.
+    /// you must account for this, or create the decoder with
+    /// [`Decoder::with_position()`].
     #[inline]
-    pub fn offset(&mut self) -> usize {
+    pub fn offset(&mut self) -> u64 {
         self.offset
     }
 
+    /// Gets a reference to the underlying reader
+    ///
+    /// A header pushed back with [`Decoder::push()`] has already been
+    /// consumed from the reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets a mutable reference to the underlying reader
+    ///
+    /// Reading from the reader directly bypasses the decoder, whose offset
+    /// will then no longer match the position of the reader.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the decoder to an earlier offset
+    ///
+    /// This is for use once the reader has itself been returned to the
+    /// matching point in its input, such as when replaying buffered input.
+    /// Any header pushed back with [`Decoder::push()`] is discarded.
+    #[inline]
+    pub fn rewind(&mut self, offset: u64) {
+        self.offset = offset;
+        self.buffer = None;
+    }
+
+    /// Unwraps the `Read`, consuming the `Decoder`
+    ///
+    /// A header pushed back with [`Decoder::push()`] has already been
+    /// consumed from the reader and is lost; use [`Decoder::into_parts()`]
+    /// to recover it.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Unwraps the `Read` along with any header pushed back into the input
+    #[inline]
+    pub fn into_parts(self) -> (R, Option<Header>) {
+        let header = self.buffer.and_then(|title| title.try_into().ok());
+        (self.reader, header)
+    }
+
     /// Process an incoming bytes item
     ///
     /// In CBOR, bytes can be segmented. The logic for this can be a bit tricky,
@@ -144,7 +250,7 @@ impl<R: Read> Decoder<R> {
     /// at the cost of memory usage. You should consider this trade off when
     /// deciding the size of your buffer.
     #[inline]
-    pub fn bytes(&mut self, len: Option<usize>) -> Segments<R, crate::seg::Bytes> {
+    pub fn bytes(&mut self, len: Option<u64>) -> Segments<'_, R, crate::seg::Bytes> {
         self.push(Header::Bytes(len));
         Segments::new(self, |header| match header {
             Header::Bytes(len) => Ok(len),
@@ -152,6 +258,20 @@ impl<R: Read> Decoder<R> {
         })
     }
 
+    /// Process an incoming text item without validating it
+    ///
+    /// This is like [`Decoder::text()`], except that the segments are
+    /// returned as raw bytes which need not be valid UTF-8. It allows
+    /// consumers to recover from text which is not well-formed.
+    #[inline]
+    pub fn text_bytes(&mut self, len: Option<u64>) -> Segments<'_, R, crate::seg::Bytes> {
+        self.push(Header::Text(len));
+        Segments::new(self, |header| match header {
+            Header::Text(len) => Ok(len),
+            _ => Err(()),
+        })
+    }
+
     /// Process an incoming text item
     ///
     /// In CBOR, text can be segmented. The logic for this can be a bit tricky,
@@ -164,7 +284,7 @@ impl<R: Read> Decoder<R> {
     /// at the cost of memory usage. You should consider this trade off when
     /// deciding the size of your buffer.
     #[inline]
-    pub fn text(&mut self, len: Option<usize>) -> Segments<R, crate::seg::Text> {
+    pub fn text(&mut self, len: Option<u64>) -> Segments<'_, R, crate::seg::Text> {
         self.push(Header::Text(len));
         Segments::new(self, |header| match header {
             Header::Text(len) => Ok(len),
@@ -2,6 +2,9 @@ use super::*;
 
 use ciborium_io::Write;
 
+/// The size of the stack buffer used to stage a header with its payload
+const STAGE: usize = 64;
+
 /// An encoder for serializing CBOR items
 ///
 /// This structure wraps a writer and provides convenience functions for
@@ -23,6 +26,11 @@ impl<W: Write> Write for Encoder<W> {
         self.0.write_all(data)
     }
 
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.0.write_all_vectored(bufs)
+    }
+
     #[inline]
     fn flush(&mut self) -> Result<(), Self::Error> {
         self.0.flush()
@@ -39,6 +47,34 @@ impl<W: Write> Encoder<W> {
     /// Push a `Header` to the wire
     #[inline(always)]
     pub fn push(&mut self, header: Header) -> Result<(), W::Error> {
+        let (buffer, len) = Self::encode(header);
+        self.0.write_all(&buffer[..len])
+    }
+
+    /// Push a `Header` followed by its payload to the wire
+    ///
+    /// Short payloads are staged together with the header so that the whole
+    /// item reaches the writer in a single call. Longer ones are passed to
+    /// the writer alongside the header in a single vectored write.
+    #[inline]
+    fn push_payload(&mut self, header: Header, payload: &[u8]) -> Result<(), W::Error> {
+        let (header, len) = Self::encode(header);
+
+        let mut buffer = [0u8; STAGE];
+        match buffer.get_mut(len..len + payload.len()) {
+            Some(staged) => {
+                staged.copy_from_slice(payload);
+                buffer[..len].copy_from_slice(&header[..len]);
+                self.0.write_all(&buffer[..len + payload.len()])
+            }
+
+            None => self.0.write_all_vectored(&[&header[..len], payload]),
+        }
+    }
+
+    /// Encodes a `Header`, returning the buffer and the length used
+    #[inline(always)]
+    fn encode(header: Header) -> ([u8; 9], usize) {
         let title = Title::from(header);
 
         let major = match title.0 {
@@ -61,8 +97,11 @@ impl<W: Write> Encoder<W> {
             Minor::More => 31,
         };
 
-        self.0.write_all(&[major << 5 | minor])?;
-        self.0.write_all(title.1.as_ref())
+        let bytes = title.1.as_ref();
+        let mut buffer = [0u8; 9];
+        buffer[0] = major << 5 | minor;
+        buffer[1..][..bytes.len()].copy_from_slice(bytes);
+        (buffer, bytes.len() + 1)
     }
 
     /// Serialize a byte slice as CBOR
@@ -80,14 +119,12 @@ impl<W: Write> Encoder<W> {
         let max = core::cmp::max(max, 1);
 
         if max >= value.len() {
-            self.push(Header::Bytes(Some(value.len())))?;
-            self.write_all(value)?;
+            self.push_payload(Header::Bytes(Some(value.len() as u64)), value)?;
         } else {
             self.push(Header::Bytes(None))?;
 
             for chunk in value.chunks(max) {
-                self.push(Header::Bytes(Some(chunk.len())))?;
-                self.write_all(chunk)?;
+                self.push_payload(Header::Bytes(Some(chunk.len() as u64)), chunk)?;
             }
 
             self.push(Header::Break)?;
@@ -109,8 +146,7 @@ impl<W: Write> Encoder<W> {
         let max = core::cmp::max(max, 4);
 
         if max >= value.len() {
-            self.push(Header::Text(Some(value.len())))?;
-            self.write_all(value.as_bytes())?;
+            self.push_payload(Header::Text(Some(value.len() as u64)), value.as_bytes())?;
         } else {
             self.push(Header::Text(None))?;
 
@@ -122,8 +158,7 @@ impl<W: Write> Encoder<W> {
                 }
 
                 let (prefix, suffix) = bytes.split_at(len);
-                self.push(Header::Text(Some(prefix.len())))?;
-                self.write_all(prefix)?;
+                self.push_payload(Header::Text(Some(prefix.len() as u64)), prefix)?;
                 bytes = suffix;
             }
 
@@ -45,7 +45,7 @@ pub enum Header {
     /// A best practice is to call `Decoder::bytes()` immediately after
     /// first pulling a bytes item header since this utility function
     /// encapsulates all the logic needed to handle segmentation.
-    Bytes(Option<usize>),
+    Bytes(Option<u64>),
 
     /// A text item
     ///
@@ -55,21 +55,21 @@ pub enum Header {
     /// A best practice is to call `Decoder::text()` immediately after
     /// first pulling a text item header since this utility function
     /// encapsulates all the logic needed to handle segmentation.
-    Text(Option<usize>),
+    Text(Option<u64>),
 
     /// An array item
     ///
     /// The value contained in this variant indicates the length of the array
     /// which follows (in items) or, if `None`, an indefinite length array
     /// terminated by a "break" value.
-    Array(Option<usize>),
+    Array(Option<u64>),
 
     /// An map item
     ///
     /// The value contained in this variant indicates the length of the map
     /// which follows (in item pairs) or, if `None`, an indefinite length map
     /// terminated by a "break" value.
-    Map(Option<usize>),
+    Map(Option<u64>),
 }
 
 impl TryFrom<Title> for Header {
@@ -90,20 +90,13 @@ impl TryFrom<Title> for Header {
 
         let int = |m| opt(m).ok_or(InvalidError(()));
 
-        let len = |m| {
-            opt(m)
-                .map(usize::try_from)
-                .transpose()
-                .or(Err(InvalidError(())))
-        };
-
         Ok(match title {
             Title(Major::Positive, minor) => Self::Positive(int(minor)?),
             Title(Major::Negative, minor) => Self::Negative(int(minor)?),
-            Title(Major::Bytes, minor) => Self::Bytes(len(minor)?),
-            Title(Major::Text, minor) => Self::Text(len(minor)?),
-            Title(Major::Array, minor) => Self::Array(len(minor)?),
-            Title(Major::Map, minor) => Self::Map(len(minor)?),
+            Title(Major::Bytes, minor) => Self::Bytes(opt(minor)),
+            Title(Major::Text, minor) => Self::Text(opt(minor)),
+            Title(Major::Array, minor) => Self::Array(opt(minor)),
+            Title(Major::Map, minor) => Self::Map(opt(minor)),
             Title(Major::Tag, minor) => Self::Tag(int(minor)?),
 
             Title(Major::Other, Minor::More) => Self::Break,
@@ -121,13 +114,13 @@ impl From<Header> for Title {
     fn from(header: Header) -> Self {
         let int = |i: u64| match i {
             x if x <= 23 => Minor::This(i as u8),
-            x if x <= core::u8::MAX as u64 => Minor::Next1([i as u8]),
-            x if x <= core::u16::MAX as u64 => Minor::Next2((i as u16).to_be_bytes()),
-            x if x <= core::u32::MAX as u64 => Minor::Next4((i as u32).to_be_bytes()),
+            x if x <= u8::MAX as u64 => Minor::Next1([i as u8]),
+            x if x <= u16::MAX as u64 => Minor::Next2((i as u16).to_be_bytes()),
+            x if x <= u32::MAX as u64 => Minor::Next4((i as u32).to_be_bytes()),
             x => Minor::Next8(x.to_be_bytes()),
         };
 
-        let len = |l: Option<usize>| l.map(|x| int(x as u64)).unwrap_or(Minor::More);
+        let len = |l: Option<u64>| l.map(int).unwrap_or(Minor::More);
 
         match header {
             Header::Positive(x) => Title(Major::Positive, int(x)),
@@ -122,6 +122,9 @@
 //! encoder.flush().unwrap();
 //! assert_eq!(b"\xa1\x07\x7f\x67Hello, \x66World!\xff", &buffer[..]);
 //! ```
+//!
+//! With the `alloc` feature, `SortedMap` writes a map whose entries are
+//! sorted by their encoded keys, for deterministic encodings.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
@@ -134,21 +137,51 @@ extern crate alloc;
 mod dec;
 mod enc;
 mod hdr;
+#[cfg(feature = "alloc")]
+mod map;
 mod seg;
 
 pub use dec::*;
 pub use enc::*;
 pub use hdr::*;
-pub use seg::{Segment, Segments};
+#[cfg(feature = "alloc")]
+pub use map::*;
+pub use seg::{from_utf8, Segment, Segments};
 
 /// Simple value constants
+///
+/// These follow the IANA "CBOR Simple Values" registry of RFC 8949.
 pub mod simple {
-    #![allow(missing_docs)]
-
+    /// The simple value `false`
     pub const FALSE: u8 = 20;
+
+    /// The simple value `true`
     pub const TRUE: u8 = 21;
+
+    /// The simple value `null`
     pub const NULL: u8 = 22;
+
+    /// The simple value `undefined`
     pub const UNDEFINED: u8 = 23;
+
+    /// Returns whether a simple value is reserved
+    ///
+    /// Values 24 to 31 are reserved and not well-formed as simple values:
+    /// 24 is the two-byte form itself, and the others encode floats and
+    /// the "break" stop code.
+    #[inline]
+    pub const fn is_reserved(value: u8) -> bool {
+        matches!(value, 24..=31)
+    }
+
+    /// Returns whether a simple value is assigned a meaning
+    ///
+    /// Only [`FALSE`], [`TRUE`], [`NULL`] and [`UNDEFINED`] are assigned;
+    /// all other values which are not reserved are unassigned.
+    #[inline]
+    pub const fn is_assigned(value: u8) -> bool {
+        matches!(value, FALSE..=UNDEFINED)
+    }
 }
 
 /// Tag constants
@@ -225,7 +258,11 @@ mod tests {
         };
     }
 
-    #[allow(clippy::excessive_precision)]
+    #[allow(
+        unknown_lints,
+        clippy::excessive_precision,
+        clippy::legacy_numeric_constants
+    )]
     #[test]
     fn leaf() {
         use core::f64::{INFINITY, NAN};
@@ -289,6 +326,10 @@ mod tests {
             (Header::Bytes(Some(4)), "44", true),
             (Header::Text(Some(0)), "60", true),
             (Header::Text(Some(4)), "64", true),
+            (Header::Bytes(Some(u64::MAX)), "5bffffffffffffffff", true),
+            (Header::Text(Some(1 << 32)), "7b0000000100000000", true),
+            (Header::Array(Some(u64::MAX)), "9bffffffffffffffff", true),
+            (Header::Map(Some(1 << 32)), "bb0000000100000000", true),
         ];
 
         for (header, bytes, encode) in data.iter().cloned() {
@@ -489,4 +530,183 @@ mod tests {
             assert_eq!(&bytes[..], &buffer[..1024 - len]);
         }
     }
+
+    #[test]
+    fn staged() {
+        /// Records the size of every write
+        #[derive(Default)]
+        struct Writes([usize; 16], usize);
+
+        impl ciborium_io::Write for Writes {
+            type Error = ();
+
+            fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+                self.0[self.1] = data.len();
+                self.1 += 1;
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        let mut encoder = Encoder::from(Writes::default());
+        encoder.push(Header::Positive(1000000)).unwrap();
+        encoder.text("hello", None).unwrap();
+        encoder.bytes(&[0u8; 62], None).unwrap();
+        encoder.bytes(&[0u8; 100], None).unwrap();
+        encoder.bytes(&[0u8; 6], 4).unwrap();
+
+        let writes = encoder.into_inner();
+        assert_eq!(&writes.0[..writes.1], &[5, 6, 64, 2, 100, 1, 5, 3, 1]);
+    }
+
+    #[test]
+    fn vectored() {
+        /// Records the size of every write, gathering vectored writes
+        #[derive(Default)]
+        struct Writes([usize; 16], usize);
+
+        impl ciborium_io::Write for Writes {
+            type Error = ();
+
+            fn write_all(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+                self.write_all_vectored(&[data])
+            }
+
+            fn write_all_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+                self.0[self.1] = bufs.iter().map(|buf| buf.len()).sum();
+                self.1 += 1;
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        let mut encoder = Encoder::from(Writes::default());
+        encoder.bytes(&[0u8; 62], None).unwrap();
+        encoder.bytes(&[0u8; 100], None).unwrap();
+        encoder.text(&"x".repeat(300), None).unwrap();
+
+        let writes = encoder.into_inner();
+        assert_eq!(&writes.0[..writes.1], &[64, 102, 303]);
+    }
+
+    #[test]
+    fn with_position() {
+        /// A slice which remembers how much of it has been read
+        struct Tracked<'a>(&'a [u8], usize);
+
+        impl ciborium_io::Read for Tracked<'_> {
+            type Error = ();
+
+            fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+                let bytes = self.0.get(self.1..self.1 + data.len()).ok_or(())?;
+                data.copy_from_slice(bytes);
+                self.1 += data.len();
+                Ok(())
+            }
+        }
+
+        impl ciborium_io::Position for Tracked<'_> {
+            fn position(&self) -> u64 {
+                self.1 as u64
+            }
+        }
+
+        let bytes = [0x00, 0x00, 0x01, 0x1c];
+
+        let mut decoder = Decoder::with_position(Tracked(&bytes, 2));
+        assert_eq!(decoder.offset(), 2);
+        assert_eq!(decoder.pull().unwrap(), Header::Positive(1));
+        assert_eq!(decoder.offset(), 3);
+        assert!(matches!(decoder.pull(), Err(Error::Syntax(3))));
+
+        let mut decoder = Decoder::from(Tracked(&bytes, 2));
+        assert_eq!(decoder.pull().unwrap(), Header::Positive(1));
+        assert!(matches!(decoder.pull(), Err(Error::Syntax(1))));
+    }
+
+    #[test]
+    fn large_offset() {
+        /// A slice found 5 GiB into a stream
+        struct Far<'a>(&'a [u8]);
+
+        impl ciborium_io::Read for Far<'_> {
+            type Error = ();
+
+            fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+                self.0.read_exact(data).or(Err(()))
+            }
+        }
+
+        impl ciborium_io::Position for Far<'_> {
+            fn position(&self) -> u64 {
+                5 << 30
+            }
+        }
+
+        let bytes = [0x01, 0x1c];
+
+        let mut decoder = Decoder::with_position(Far(&bytes));
+        assert_eq!(decoder.pull().unwrap(), Header::Positive(1));
+        assert_eq!(decoder.offset(), (5 << 30) + 1);
+        assert!(matches!(decoder.pull(), Err(Error::Syntax(x)) if x == (5 << 30) + 1));
+    }
+
+    #[test]
+    fn into_parts() {
+        let bytes = [0x01, 0x62, b'h', b'i', 0xff];
+
+        let mut decoder = Decoder::from(&bytes[..]);
+        assert_eq!(decoder.pull().unwrap(), Header::Positive(1));
+        let header = decoder.pull().unwrap();
+        decoder.push(header);
+
+        let (reader, pushed) = decoder.into_parts();
+        assert_eq!(pushed, Some(Header::Text(Some(2))));
+        assert_eq!(reader, b"hi\xff");
+
+        let mut decoder = Decoder::from(&bytes[..]);
+        assert_eq!(decoder.pull().unwrap(), Header::Positive(1));
+        assert_eq!(decoder.into_parts(), (&bytes[1..], None));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sorted_map() {
+        let mut map = SortedMap::new();
+        let mut entry = |k: Header, v: u64| {
+            map.entry(|e| e.push(k), |e| e.push(Header::Positive(v)))
+                .unwrap()
+        };
+
+        assert!(entry(Header::Text(Some(0)), 1));
+        assert!(entry(Header::Positive(100), 2));
+        assert!(entry(Header::Negative(0), 3));
+        assert!(entry(Header::Positive(10), 4));
+        assert!(!entry(Header::Positive(100), 5));
+
+        let mut buffer = [0u8; 11];
+        map.finish(&mut Encoder::from(&mut buffer[..])).unwrap();
+        assert_eq!(buffer, *b"\xa4\x0a\x04\x18\x64\x02\x20\x03\x60\x01\x00");
+
+        let mut buffer = [0u8; 1];
+        SortedMap::new()
+            .finish(&mut Encoder::from(&mut buffer[..]))
+            .unwrap();
+        assert_eq!(buffer, [0xa0]);
+    }
+
+    #[test]
+    fn simple_registry() {
+        let assigned = (0..=255).filter(|x| simple::is_assigned(*x));
+        let reserved = (0..=255).filter(|x| simple::is_reserved(*x));
+
+        assert!(assigned.eq([20, 21, 22, 23]));
+        assert!(reserved.eq(24..=31));
+    }
 }
@@ -0,0 +1,87 @@
+use super::*;
+
+use alloc::vec::Vec;
+use ciborium_io::Write;
+
+/// A map whose entries are written in deterministic order
+///
+/// Entries are encoded into memory as they are added, and written out by
+/// [`SortedMap::finish()`] sorted by the bytewise lexicographic order of
+/// their encoded keys, as required by the core deterministic encoding of
+/// [RFC 8949 section 4.2.1](https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1).
+/// Each entry is added as a key together with its value, so a map can
+/// never be left with a dangling key, and the map header always carries
+/// the number of entries actually written.
+///
+/// ```rust
+/// use ciborium_ll::{Encoder, Header, SortedMap};
+///
+/// let mut map = SortedMap::new();
+/// map.entry(|k| k.text("b", None), |v| v.push(Header::Positive(2))).unwrap();
+/// map.entry(|k| k.text("a", None), |v| v.push(Header::Positive(1))).unwrap();
+///
+/// let mut buffer = [0u8; 7];
+/// map.finish(&mut Encoder::from(&mut buffer[..])).unwrap();
+/// assert_eq!(buffer, [0xa2, 0x61, b'a', 0x01, 0x61, b'b', 0x02]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SortedMap {
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl SortedMap {
+    /// Creates an empty map
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no entries
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds an entry, encoding its key with `key` and its value with `value`
+    ///
+    /// Each function should encode exactly one item. If the map already
+    /// has an entry with the same encoded key, `value` is not called and
+    /// `false` is returned; otherwise the entry is added and `true` is
+    /// returned.
+    pub fn entry<E, K, V>(&mut self, key: K, value: V) -> Result<bool, E>
+    where
+        K: FnOnce(&mut Encoder<&mut Vec<u8>>) -> Result<(), E>,
+        V: FnOnce(&mut Encoder<&mut Vec<u8>>) -> Result<(), E>,
+    {
+        let mut k = Vec::new();
+        key(&mut Encoder::from(&mut k))?;
+
+        let index = match self.entries.binary_search_by(|(x, ..)| x.cmp(&k)) {
+            Ok(..) => return Ok(false),
+            Err(index) => index,
+        };
+
+        let mut v = Vec::new();
+        value(&mut Encoder::from(&mut v))?;
+
+        self.entries.insert(index, (k, v));
+        Ok(true)
+    }
+
+    /// Writes the map, with its entries in order
+    pub fn finish<W: Write>(self, encoder: &mut Encoder<W>) -> Result<(), W::Error> {
+        encoder.push(Header::Map(Some(self.entries.len() as u64)))?;
+
+        for (key, value) in &self.entries {
+            encoder.write_all_vectored(&[key, value])?;
+        }
+
+        Ok(())
+    }
+}
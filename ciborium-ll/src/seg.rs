@@ -53,6 +53,21 @@ impl Parser for Bytes {
     }
 }
 
+/// Validates UTF-8, using SIMD when the `simdutf8` feature is enabled
+///
+/// This behaves exactly like [`core::str::from_utf8()`], including the
+/// details of the error, and is what [`Text`] segments are validated with.
+#[inline]
+pub fn from_utf8(bytes: &[u8]) -> Result<&str, core::str::Utf8Error> {
+    // The fast validator reports no error details; recompute them on failure.
+    #[cfg(feature = "simdutf8")]
+    if let Ok(s) = simdutf8::basic::from_utf8(bytes) {
+        return Ok(s);
+    }
+
+    core::str::from_utf8(bytes)
+}
+
 /// A text parser
 ///
 /// This parser converts the input bytes to a `str`. This parser preserves
@@ -77,7 +92,7 @@ impl Parser for Text {
         // Copy previously invalid data into place.
         bytes[..self.stored].clone_from_slice(&self.buffer[..self.stored]);
 
-        Ok(match core::str::from_utf8(bytes) {
+        Ok(match from_utf8(bytes) {
             Ok(s) => {
                 self.stored = 0;
                 s
@@ -113,16 +128,16 @@ impl Parser for Text {
 /// read out in parsed chunks based on the size of the input scratch buffer.
 pub struct Segment<'r, R, P> {
     reader: &'r mut Decoder<R>,
-    unread: usize,
-    offset: usize,
+    unread: u64,
+    offset: u64,
     parser: P,
 }
 
 impl<'r, R: Read, P: Parser> Segment<'r, R, P> {
     /// Gets the number of unprocessed bytes
     #[inline]
-    pub fn left(&self) -> usize {
-        self.unread + self.parser.saved()
+    pub fn left(&self) -> u64 {
+        self.unread + self.parser.saved() as u64
     }
 
     /// Gets the next parsed chunk within the segment
@@ -142,14 +157,15 @@ impl<'r, R: Read, P: Parser> Segment<'r, R, P> {
             _ => (),
         }
 
-        // Determine how many bytes to read.
-        let size = min(buffer.len(), prev + self.unread);
+        // Determine how many bytes to read; this never exceeds the buffer,
+        // even when the segment is longer than `usize` can count.
+        let size = min(buffer.len() as u64, prev as u64 + self.unread) as usize;
         let full = &mut buffer[..size];
         let next = &mut full[min(size, prev)..];
 
         // Read additional bytes.
         self.reader.read_exact(next)?;
-        self.unread -= next.len();
+        self.unread -= next.len() as u64;
 
         self.parser
             .parse(full)
@@ -173,14 +189,14 @@ pub struct Segments<'r, R, P> {
     reader: &'r mut Decoder<R>,
     state: State,
     parser: PhantomData<P>,
-    unwrap: fn(Header) -> Result<Option<usize>, ()>,
+    unwrap: fn(Header) -> Result<Option<u64>, ()>,
 }
 
 impl<'r, R, P> Segments<'r, R, P> {
     #[inline]
     pub(crate) fn new(
         decoder: &'r mut Decoder<R>,
-        unwrap: fn(Header) -> Result<Option<usize>, ()>,
+        unwrap: fn(Header) -> Result<Option<u64>, ()>,
     ) -> Self {
         Self {
             reader: decoder,
@@ -196,7 +212,7 @@ impl<'r, R: Read, P: Parser> Segments<'r, R, P> {
     ///
     /// Returns `Ok(None)` at the conclusion of the stream.
     #[inline]
-    pub fn pull(&mut self) -> Result<Option<Segment<R, P>>, Error<R::Error>> {
+    pub fn pull(&mut self) -> Result<Option<Segment<'_, R, P>>, Error<R::Error>> {
         while self.state != State::Finished {
             let offset = self.reader.offset();
             match self.reader.pull()? {
@@ -238,7 +254,7 @@ mod tests {
 
     #[test]
     fn segments() {
-        fn t(data: &[u8], len: usize) {
+        fn t(data: &[u8], len: u64) {
             let mut dec = Decoder::from(data);
             let mut segs = Segments::<_, Bytes>::new(&mut dec, |header| match header {
                 Header::Bytes(len) => Ok(len),
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the memory and time taken to decode a large document
+//!
+//! Run with `cargo bench --bench footprint --features bumpalo,compact`. This
+//! decodes a document of many small records, as is typical of logs and
+//! telemetry, into [`Value`], into the smaller `compact::Value` and into the
+//! arena `Value`, and reports the heap held by the result, the peak heap
+//! while decoding and the fastest decode time.
+//!
+//! # Shrinking `Value`
+//!
+//! `Value` is 32 bytes on 64-bit targets. `Bytes`, `Text`, `Array` and `Map`
+//! each hold a 24-byte `Vec` or `String`, and the `i128` inside `Integer`
+//! needs 16-byte alignment. `compact::Value` holds `Box<[u8]>`, `Box<str>`,
+//! `Box<[Value]>` and `Box<[(Value, Value)]>` instead, which are 16 bytes
+//! each, and an `Integer` stored as a sign and a 64-bit magnitude, so it is
+//! 24 bytes. Decoding shrinks each collection once it is complete, which may
+//! reallocate; that is the cost measured here against the heap saved.
+//!
+//! Since the variant types of `Value` are public, switching `Value` itself
+//! over must wait for the next major release. Until then, the compact
+//! representation is available with the `compact` feature, and converts to
+//! and from `Value` without loss.
+//!
+//! Measured on x86-64 Linux with a release build, decoding 100,000 records
+//! (6.5 MB of CBOR), the fastest of 5 runs:
+//!
+//! | decoded into | `size_of` | heap held | peak heap | time    |
+//! |--------------|-----------|-----------|-----------|---------|
+//! | `Value`      | 32        | 53.1 MB   | 53.1 MB   | ~112 ms |
+//! | compact      | 24        | 37.7 MB   | 40.8 MB   | ~124 ms |
+//! | arena        | 32        | 134.2 MB  | 134.2 MB  | ~126 ms |
+//!
+//! The `Value` holds 1,400,000 values within arrays, maps and tags, and
+//! 4.2 MB of spare capacity in its vectors and strings. The compact value
+//! holds 29% less heap for about 10% more decode time; its peak is above
+//! what it holds because of the vectors it shrank. The arena figures
+//! include the unused tail of the arena's last chunk, and the vectors it
+//! outgrew while decoding.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "compact")]
+use ciborium::compact;
+use ciborium::{cbor, Value};
+
+/// An allocator which keeps track of the current and peak heap use
+struct Counter;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counter = Counter;
+
+const RECORDS: u64 = 100_000;
+const RUNS: usize = 5;
+
+/// Encodes a document of `RECORDS` small records
+fn document() -> Vec<u8> {
+    let records = (0..RECORDS)
+        .map(|i| {
+            cbor!({
+                "id" => i,
+                "name" => format!("record-{}", i),
+                "score" => i as f64 / 7.0,
+                "tags" => [1, -2, 3],
+                "digest" => Value::Bytes(i.to_be_bytes().to_vec()),
+            })
+            .unwrap()
+        })
+        .collect();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&Value::Array(records), &mut bytes).unwrap();
+    bytes
+}
+
+/// Runs `decode`, returning the heap held by its result, the peak heap
+/// while it ran and how long it took
+fn measure<T>(decode: impl Fn() -> T) -> (usize, usize, Duration) {
+    let mut result = (0, 0, Duration::MAX);
+
+    for _ in 0..RUNS {
+        let before = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(before, Ordering::Relaxed);
+
+        let start = Instant::now();
+        let value = decode();
+        let elapsed = start.elapsed();
+
+        let held = CURRENT.load(Ordering::Relaxed) - before;
+        let peak = PEAK.load(Ordering::Relaxed) - before;
+        drop(value);
+
+        result = (held, peak, result.2.min(elapsed));
+    }
+
+    result
+}
+
+/// Counts the values held within arrays, maps and tags, and the bytes of
+/// spare capacity in the vectors and strings of `value`
+fn walk(value: &Value, slots: &mut usize, spare: &mut usize) {
+    let size = core::mem::size_of::<Value>();
+
+    match value {
+        Value::Bytes(x) => *spare += x.capacity() - x.len(),
+        Value::Text(x) => *spare += x.capacity() - x.len(),
+        Value::Tag(_, x) => {
+            *slots += 1;
+            walk(x, slots, spare);
+        }
+        Value::Array(x) => {
+            *slots += x.len();
+            *spare += (x.capacity() - x.len()) * size;
+            x.iter().for_each(|x| walk(x, slots, spare));
+        }
+        Value::Map(x) => {
+            *slots += x.len() * 2;
+            *spare += (x.capacity() - x.len()) * size * 2;
+            for (k, v) in x {
+                walk(k, slots, spare);
+                walk(v, slots, spare);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn report(name: &str, size: usize, (held, peak, time): (usize, usize, Duration)) {
+    println!(
+        "{:<8} size_of {:>2}  held {:>5.1} MB  peak {:>5.1} MB  time {:>4} ms",
+        name,
+        size,
+        held as f64 / 1e6,
+        peak as f64 / 1e6,
+        time.as_millis(),
+    );
+}
+
+fn main() {
+    let bytes = document();
+    println!(
+        "{} records, {:.1} MB of CBOR",
+        RECORDS,
+        bytes.len() as f64 / 1e6
+    );
+
+    let value = measure(|| ciborium::from_reader::<Value, _>(&bytes[..]).unwrap());
+    report("Value", core::mem::size_of::<Value>(), value);
+
+    let (mut slots, mut spare) = (0, 0);
+    let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+    walk(&value, &mut slots, &mut spare);
+    println!(
+        "{} values held in containers, {:.1} MB of spare capacity",
+        slots,
+        spare as f64 / 1e6
+    );
+
+    #[cfg(feature = "compact")]
+    {
+        let compact = measure(|| ciborium::from_reader::<compact::Value, _>(&bytes[..]).unwrap());
+        report("compact", core::mem::size_of::<compact::Value>(), compact);
+    }
+
+    #[cfg(feature = "bumpalo")]
+    {
+        let arena = measure(|| {
+            let bump = bumpalo::Bump::new();
+            ciborium::arena::from_slice(&bytes, &bump).unwrap();
+            bump
+        });
+        report(
+            "arena",
+            core::mem::size_of::<ciborium::arena::Value>(),
+            arena,
+        );
+    }
+}
@@ -0,0 +1,622 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adapters changing how values are represented
+//!
+//! Each module here is a helper for serde's `with` attribute, which changes
+//! how a single field is represented without wrapping its type:
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use ciborium::{cbor, Value};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Record {
+//!     #[serde(with = "ciborium::adapters::bytes")]
+//!     digest: Vec<u8>,
+//!
+//!     #[serde(with = "ciborium::adapters::text")]
+//!     serial: u128,
+//!
+//!     #[serde(with = "ciborium::adapters::pairs")]
+//!     labels: BTreeMap<u8, String>,
+//! }
+//!
+//! let record = Record {
+//!     digest: vec![1, 2],
+//!     serial: 7,
+//!     labels: [(1, "a".into())].into(),
+//! };
+//!
+//! let mut bytes = Vec::new();
+//! ciborium::into_writer(&record, &mut bytes).unwrap();
+//!
+//! let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+//! assert_eq!(value, cbor!({
+//!     "digest" => Value::Bytes(vec![1, 2]),
+//!     "serial" => "7",
+//!     "labels" => [[1, "a"]],
+//! }).unwrap());
+//!
+//! let decoded: Record = ciborium::from_reader(&bytes[..]).unwrap();
+//! assert_eq!(decoded, record);
+//! ```
+//!
+//! The [`PairList`] type instead wraps a map whose entry order matters, and
+//! [`Extensible`] wraps an enum to keep the variants it does not know.
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+
+use crate::value::{Error, Value};
+
+/// Collects bytes given as a byte string or an array
+struct Bytes<T>(PhantomData<T>);
+
+impl<'de, T: From<Vec<u8>>> Visitor<'de> for Bytes<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        Ok(v.to_vec().into())
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+        Ok(v.into())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(bytes.into())
+    }
+}
+
+/// Serializes bytes as a byte string
+///
+/// By default, serde serializes `Vec<u8>` and `[u8; N]` as an array of
+/// integers. This module serializes any `AsRef<[u8]>` as a byte string,
+/// and deserializes any `From<Vec<u8>>` from a byte string or an array.
+pub mod bytes {
+    use super::*;
+
+    /// Serializes the bytes as a byte string
+    #[inline]
+    pub fn serialize<T: ?Sized + AsRef<[u8]>, S: Serializer>(
+        bytes: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes.as_ref())
+    }
+
+    /// Deserializes the bytes from a byte string or an array
+    #[inline]
+    pub fn deserialize<'de, T: From<Vec<u8>>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_byte_buf(Bytes(PhantomData))
+    }
+}
+
+/// Serializes bytes as a hexadecimal text string
+///
+/// Bytes are written as lowercase digits, and read in either case.
+pub mod hex {
+    use super::*;
+
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    fn nibble(digit: u8) -> Option<u8> {
+        match digit {
+            b'0'..=b'9' => Some(digit - b'0'),
+            b'a'..=b'f' => Some(digit - b'a' + 10),
+            b'A'..=b'F' => Some(digit - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Serializes the bytes as a hexadecimal string
+    pub fn serialize<T: ?Sized + AsRef<[u8]>, S: Serializer>(
+        bytes: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut text = String::with_capacity(bytes.as_ref().len() * 2);
+        for byte in bytes.as_ref() {
+            text.push(DIGITS[usize::from(byte >> 4)].into());
+            text.push(DIGITS[usize::from(byte & 0xf)].into());
+        }
+
+        serializer.serialize_str(&text)
+    }
+
+    /// Deserializes the bytes from a hexadecimal string
+    #[inline]
+    pub fn deserialize<'de, T: From<Vec<u8>>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        struct Hex<T>(PhantomData<T>);
+
+        impl<'de, T: From<Vec<u8>>> Visitor<'de> for Hex<T> {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a hexadecimal string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+                if v.len() % 2 != 0 {
+                    return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+                }
+
+                v.as_bytes()
+                    .chunks(2)
+                    .map(|pair| Some(nibble(pair[0])? << 4 | nibble(pair[1])?))
+                    .collect::<Option<Vec<u8>>>()
+                    .map(Into::into)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(Hex(PhantomData))
+    }
+}
+
+/// Serializes a value as a text string using its `Display` and `FromStr`
+///
+/// This is mostly useful for integers which other parties cannot represent
+/// exactly, such as 128-bit identifiers.
+pub mod text {
+    use super::*;
+    use core::str::FromStr;
+
+    /// Serializes the value as its `Display` output
+    #[inline]
+    pub fn serialize<T: ?Sized + fmt::Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes the value by parsing a text string
+    #[inline]
+    pub fn deserialize<'de, T: FromStr, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error>
+    where
+        T::Err: fmt::Display,
+    {
+        struct Text<T>(PhantomData<T>);
+
+        impl<'de, T: FromStr> Visitor<'de> for Text<T>
+        where
+            T::Err: fmt::Display,
+        {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Text(PhantomData))
+    }
+}
+
+/// Serializes a map as an array of `[key, value]` arrays
+///
+/// This preserves maps whose keys another party cannot use as map keys,
+/// and any map implementing `FromIterator` can be deserialized.
+pub mod pairs {
+    use super::*;
+
+    /// Serializes the map as an array of pairs
+    #[inline]
+    pub fn serialize<'a, T, K, V, S>(map: &'a T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a T: IntoIterator<Item = (&'a K, &'a V)>,
+        K: 'a + serde::Serialize,
+        V: 'a + serde::Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    /// Deserializes the map from an array of pairs
+    #[inline]
+    pub fn deserialize<'de, T, K, V, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromIterator<(K, V)>,
+        K: de::Deserialize<'de>,
+        V: de::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct Pairs<T, K, V>(PhantomData<(T, K, V)>);
+
+        impl<'de, T, K, V> Visitor<'de> for Pairs<T, K, V>
+        where
+            T: FromIterator<(K, V)>,
+            K: de::Deserialize<'de>,
+            V: de::Deserialize<'de>,
+        {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an array of pairs")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+                let mut pairs = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(pair) = seq.next_element()? {
+                    pairs.push(pair);
+                }
+
+                Ok(pairs.into_iter().collect())
+            }
+        }
+
+        deserializer.deserialize_seq(Pairs(PhantomData))
+    }
+}
+
+/// A map kept as a vector of entries in wire order
+///
+/// Unlike collecting into a `BTreeMap` or `HashMap`, this keeps the entries
+/// in the order in which they were decoded, including any repeated keys,
+/// and serializes them back as a map in that same order. It is the
+/// opposite of the [`pairs`] module, which represents a map type as an
+/// array of pairs.
+///
+/// ```
+/// use ciborium::adapters::PairList;
+///
+/// // {"b": 1, "a": 2, "b": 3}
+/// let bytes = [0xa3, 0x61, 0x62, 0x01, 0x61, 0x61, 0x02, 0x61, 0x62, 0x03];
+///
+/// let pairs: PairList<String, u8> = ciborium::from_reader(&bytes[..]).unwrap();
+/// assert_eq!(pairs.0, [("b".into(), 1), ("a".into(), 2), ("b".into(), 3)]);
+///
+/// let mut encoded = Vec::new();
+/// ciborium::into_writer(&pairs, &mut encoded).unwrap();
+/// assert_eq!(encoded, bytes);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PairList<K, V>(pub Vec<(K, V)>);
+
+impl<K, V> From<Vec<(K, V)>> for PairList<K, V> {
+    #[inline]
+    fn from(entries: Vec<(K, V)>) -> Self {
+        Self(entries)
+    }
+}
+
+impl<K, V> From<PairList<K, V>> for Vec<(K, V)> {
+    #[inline]
+    fn from(pairs: PairList<K, V>) -> Self {
+        pairs.0
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for PairList<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K: serde::Serialize, V: serde::Serialize> serde::Serialize for PairList<K, V> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<'de, K: de::Deserialize<'de>, V: de::Deserialize<'de>> de::Deserialize<'de>
+    for PairList<K, V>
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Entries<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K: de::Deserialize<'de>, V: de::Deserialize<'de>> Visitor<'de> for Entries<K, V> {
+            type Value = PairList<K, V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+
+                Ok(PairList(entries))
+            }
+        }
+
+        deserializer.deserialize_map(Entries(PhantomData))
+    }
+}
+
+/// An enum which keeps variants it does not recognize
+///
+/// Protocols grow new variants over time. Decoding an enum fails when the
+/// peer sends a variant it does not declare; decoding an `Extensible` enum
+/// instead captures the unrecognized variant name (or index) with its
+/// payload, and serializes it back unchanged. Variants which are recognized
+/// but whose payloads are invalid still fail.
+///
+/// Only externally tagged enums are supported: a unit variant is a bare
+/// name, and any other variant a single-entry map keyed by its name.
+///
+/// ```
+/// use ciborium::{adapters::Extensible, cbor, Value};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// enum Shape {
+///     Circle(u8),
+///     Square(u8),
+/// }
+///
+/// let value = cbor!([{ "Circle" => 1 }, { "Star" => [5, 2] }]).unwrap();
+/// let shapes: Vec<Extensible<Shape>> = value.deserialized().unwrap();
+///
+/// assert_eq!(shapes[0], Extensible::Known(Shape::Circle(1)));
+/// assert_eq!(shapes[1], Extensible::Unknown {
+///     variant: "Star".into(),
+///     content: Some(cbor!([5, 2]).unwrap()),
+/// });
+///
+/// assert_eq!(Value::serialized(&shapes).unwrap(), value);
+/// ```
+///
+/// # Limitations
+///
+/// The variants of `T` are found by probing its `Deserialize`
+/// implementation, which reveals them only when it passes them to
+/// `deserialize_enum()`, as a derived externally tagged enum does. Untagged,
+/// internally or adjacently tagged enums, and hand-written implementations
+/// which call `deserialize_any()`, reveal nothing. For these, every input
+/// is taken for a known variant, so an unrecognized one fails to decode
+/// just as it would without the wrapper.
+///
+/// The input is first decoded into a [`Value`] and then into `T`. The
+/// second step does not see the options of the original deserializer, and
+/// its errors carry no offset into the input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Extensible<T> {
+    /// A variant declared by `T`
+    Known(T),
+
+    /// A variant not declared by `T`
+    Unknown {
+        /// The variant name or index
+        variant: Value,
+
+        /// The payload, or `None` for a unit variant
+        content: Option<Value>,
+    },
+}
+
+impl<T: serde::Serialize> serde::Serialize for Extensible<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Extensible::Known(x) => x.serialize(serializer),
+            Extensible::Unknown {
+                variant,
+                content: None,
+            } => variant.serialize(serializer),
+            Extensible::Unknown {
+                variant,
+                content: Some(content),
+            } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(variant, content)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de, T: de::DeserializeOwned> de::Deserialize<'de> for Extensible<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        let (variant, content) = match &value {
+            Value::Map(entries) if entries.len() == 1 => (&entries[0].0, Some(&entries[0].1)),
+            variant => (variant, None),
+        };
+
+        let known = match (variants::<T>(), variant) {
+            (Some(names), Value::Text(name)) => names.contains(&name.as_str()),
+            (Some(names), Value::Integer(index)) => {
+                usize::try_from(*index).is_ok_and(|index| index < names.len())
+            }
+            // `T` is not an enum, or this is not an enum variant at all.
+            _ => true,
+        };
+
+        match known {
+            true => value.deserialized().map(Extensible::Known),
+            false => Ok(Extensible::Unknown {
+                variant: variant.clone(),
+                content: content.cloned(),
+            }),
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+/// Returns the variant names of `T`, if it is an enum
+///
+/// A derived `Deserialize` implementation passes its variant names to
+/// `deserialize_enum()`, where this deserializer records them.
+fn variants<T: de::DeserializeOwned>() -> Option<&'static [&'static str]> {
+    struct Probe<'a>(&'a mut Option<&'static [&'static str]>);
+
+    impl<'de> Deserializer<'de> for Probe<'_> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+            Err(Error::Custom(String::new()))
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            variants: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Error> {
+            *self.0 = Some(variants);
+            Err(Error::Custom(String::new()))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+
+    let mut variants = None;
+    let _ = T::deserialize(Probe(&mut variants));
+    variants
+}
+
+/// Serializes a `SystemTime` as an epoch-based date/time (tag 1)
+///
+/// Times are written as integer seconds from the Unix epoch when they have
+/// no fractional part, and as float seconds otherwise. Either is accepted,
+/// with or without the tag.
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "ciborium::adapters::timestamp")]
+///     at: SystemTime,
+/// }
+///
+/// let event = Event { at: SystemTime::UNIX_EPOCH + Duration::from_secs(1363896240) };
+///
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&event, &mut bytes).unwrap();
+/// assert_eq!(&bytes[4..], [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0]);
+/// ```
+#[cfg(feature = "std")]
+pub mod timestamp {
+    use super::*;
+    use crate::tag::{Accepted, Required};
+    use serde::{Deserialize, Serialize};
+    use std::time::{Duration, SystemTime};
+
+    /// Seconds relative to the Unix epoch
+    enum Epoch {
+        Integer(i128),
+        Float(f64),
+    }
+
+    impl serde::Serialize for Epoch {
+        #[inline]
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Epoch::Integer(x) => serializer.serialize_i128(*x),
+                Epoch::Float(x) => serializer.serialize_f64(*x),
+            }
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Epoch {
+        #[inline]
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct EpochVisitor;
+
+            impl<'de> Visitor<'de> for EpochVisitor {
+                type Value = Epoch;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    formatter.write_str("seconds since the epoch")
+                }
+
+                #[inline]
+                fn visit_i64<E: de::Error>(self, v: i64) -> Result<Epoch, E> {
+                    Ok(Epoch::Integer(v.into()))
+                }
+
+                #[inline]
+                fn visit_u64<E: de::Error>(self, v: u64) -> Result<Epoch, E> {
+                    Ok(Epoch::Integer(v.into()))
+                }
+
+                #[inline]
+                fn visit_i128<E: de::Error>(self, v: i128) -> Result<Epoch, E> {
+                    Ok(Epoch::Integer(v))
+                }
+
+                #[inline]
+                fn visit_f64<E: de::Error>(self, v: f64) -> Result<Epoch, E> {
+                    Ok(Epoch::Float(v))
+                }
+            }
+
+            deserializer.deserialize_any(EpochVisitor)
+        }
+    }
+
+    /// Serializes the time with tag 1
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        let epoch = match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) if d.subsec_nanos() == 0 => Epoch::Integer(d.as_secs().into()),
+            Ok(d) => Epoch::Float(d.as_secs_f64()),
+            Err(e) if e.duration().subsec_nanos() == 0 => {
+                Epoch::Integer(-i128::from(e.duration().as_secs()))
+            }
+            Err(e) => Epoch::Float(-e.duration().as_secs_f64()),
+        };
+
+        Required::<_, 1>(epoch).serialize(serializer)
+    }
+
+    /// Deserializes the time, with or without tag 1
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        let out_of_range = || de::Error::custom("timestamp out of range");
+
+        let (duration, negative) = match Accepted::<Epoch, 1>::deserialize(deserializer)?.0 {
+            Epoch::Integer(x) => {
+                let secs = u64::try_from(x.unsigned_abs()).map_err(|_| out_of_range())?;
+                (Duration::from_secs(secs), x < 0)
+            }
+
+            Epoch::Float(x) => {
+                let duration = Duration::try_from_secs_f64(x.abs()).map_err(|_| out_of_range())?;
+                (duration, x < 0.0)
+            }
+        };
+
+        match negative {
+            false => SystemTime::UNIX_EPOCH.checked_add(duration),
+            true => SystemTime::UNIX_EPOCH.checked_sub(duration),
+        }
+        .ok_or_else(out_of_range)
+    }
+}
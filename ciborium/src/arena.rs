@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dynamic CBOR values allocated in an arena
+//!
+//! Decoding a large document into a [`Value`](crate::value::Value) performs
+//! an allocation for every string, array and map, and dropping it walks the
+//! whole tree again. The [`Value`] in this module instead places all of its
+//! contents in a [`bumpalo::Bump`] arena: decoding is a series of pointer
+//! bumps, and the whole document is freed at once when the arena is.
+//!
+//! ```
+//! use bumpalo::Bump;
+//! use ciborium::arena::{from_slice, Value};
+//!
+//! let bytes = [0xa1, 0x61, 0x61, 0x82, 0x01, 0x62, 0x68, 0x69];
+//!
+//! let arena = Bump::new();
+//! let value = from_slice(&bytes, &arena).unwrap();
+//!
+//! let (key, array) = value.as_map().unwrap()[0];
+//! assert_eq!(key, Value::Text("a"));
+//! assert_eq!(array.as_array().unwrap()[1], Value::Text("hi"));
+//! ```
+//!
+//! Since an arena `Value` owns nothing, it is `Copy` and never needs to be
+//! dropped.
+
+use crate::de::{from_reader_seed, from_slice_seed, Error};
+use crate::value::Integer;
+
+use alloc::{boxed::Box, string::String};
+use core::fmt;
+
+use bumpalo::{collections::Vec, Bump};
+use ciborium_io::Read;
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeTupleVariant, Serializer};
+
+/// A dynamic CBOR value whose contents live in an arena
+///
+/// The variants mirror those of [`crate::value::Value`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub enum Value<'a> {
+    /// An integer
+    Integer(Integer),
+
+    /// Bytes
+    Bytes(&'a [u8]),
+
+    /// A float
+    Float(f64),
+
+    /// A string
+    Text(&'a str),
+
+    /// A boolean
+    Bool(bool),
+
+    /// Null
+    Null,
+
+    /// Tag
+    Tag(u64, &'a Value<'a>),
+
+    /// An array
+    Array(&'a [Value<'a>]),
+
+    /// A map
+    Map(&'a [(Value<'a>, Value<'a>)]),
+}
+
+impl<'a> Value<'a> {
+    /// Returns the integer, if the value is one
+    pub fn as_integer(&self) -> Option<Integer> {
+        match self {
+            Value::Integer(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if the value is a byte string
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            Value::Bytes(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is a text string
+    pub fn as_text(&self) -> Option<&'a str> {
+        match self {
+            Value::Text(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if the value is an array
+    pub fn as_array(&self) -> Option<&'a [Value<'a>]> {
+        match self {
+            Value::Array(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if the value is a map
+    pub fn as_map(&self) -> Option<&'a [(Value<'a>, Value<'a>)]> {
+        match self {
+            Value::Map(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl From<Value<'_>> for crate::value::Value {
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Integer(x) => Self::Integer(x),
+            Value::Bytes(x) => Self::Bytes(x.into()),
+            Value::Float(x) => Self::Float(x),
+            Value::Text(x) => Self::Text(x.into()),
+            Value::Bool(x) => Self::Bool(x),
+            Value::Null => Self::Null,
+            Value::Tag(t, v) => Self::Tag(t, Box::new((*v).into())),
+            Value::Array(x) => Self::Array(x.iter().map(|v| (*v).into()).collect()),
+            Value::Map(x) => Self::Map(x.iter().map(|(k, v)| ((*k).into(), (*v).into())).collect()),
+        }
+    }
+}
+
+impl Serialize for Value<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Integer(x) => crate::value::Value::Integer(*x).serialize(serializer),
+            Value::Bytes(x) => serializer.serialize_bytes(x),
+            Value::Float(x) => crate::value::Value::Float(*x).serialize(serializer),
+            Value::Text(x) => serializer.serialize_str(x),
+            Value::Bool(x) => serializer.serialize_bool(*x),
+            Value::Null => serializer.serialize_unit(),
+
+            Value::Tag(t, v) => {
+                let mut acc = serializer.serialize_tuple_variant("@@TAG@@", 0, "@@TAGGED@@", 2)?;
+                acc.serialize_field(t)?;
+                acc.serialize_field(v)?;
+                acc.end()
+            }
+
+            Value::Array(x) => {
+                let mut acc = serializer.serialize_seq(Some(x.len()))?;
+                for v in x.iter() {
+                    acc.serialize_element(v)?;
+                }
+                acc.end()
+            }
+
+            Value::Map(x) => {
+                let mut acc = serializer.serialize_map(Some(x.len()))?;
+                for (k, v) in x.iter() {
+                    acc.serialize_entry(k, v)?;
+                }
+                acc.end()
+            }
+        }
+    }
+}
+
+/// A seed deserializing a [`Value`] into an arena
+///
+/// This can decode arena values from any deserializer, such as with
+/// [`crate::de::from_reader_seed()`].
+#[derive(Copy, Clone, Debug)]
+pub struct Seed<'a>(pub &'a Bump);
+
+impl<'de, 'a> DeserializeSeed<'de> for Seed<'a> {
+    type Value = Value<'a>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value<'a>, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+macro_rules! mkvisit {
+    ($($f:ident($v:ty)),+ $(,)?) => {
+        $(
+            #[inline]
+            fn $f<E: de::Error>(self, v: $v) -> Result<Self::Value, E> {
+                Ok(Value::Integer(v.into()))
+            }
+        )+
+    };
+}
+
+impl<'de, 'a> Visitor<'de> for Seed<'a> {
+    type Value = Value<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a valid CBOR item")
+    }
+
+    mkvisit! {
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+    }
+
+    #[inline]
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Integer::try_from(v)
+            .map(Value::Integer)
+            .map_err(|_| E::custom("integer too large"))
+    }
+
+    #[inline]
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Integer::try_from(v)
+            .map(Value::Integer)
+            .map_err(|_| E::custom("integer too large"))
+    }
+
+    #[inline]
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    #[inline]
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::Text(self.0.alloc_str(v)))
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.visit_str(&v)
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(self.0.alloc_slice_copy(v)))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        self.visit_bytes(&v)
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+        let mut seq = Vec::new_in(self.0);
+
+        while let Some(elem) = acc.next_element_seed(self)? {
+            seq.push(elem);
+        }
+
+        Ok(Value::Array(seq.into_bump_slice()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+        let mut map = Vec::new_in(self.0);
+
+        while let Some(kv) = acc.next_entry_seed(self, self)? {
+            map.push(kv);
+        }
+
+        Ok(Value::Map(map.into_bump_slice()))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, acc: A) -> Result<Self::Value, A::Error> {
+        use serde::de::VariantAccess;
+
+        struct Inner<'a>(&'a Bump);
+
+        impl<'de, 'a> Visitor<'de> for Inner<'a> {
+            type Value = Value<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a valid CBOR item")
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+                let tag: u64 = acc
+                    .next_element()?
+                    .ok_or_else(|| de::Error::custom("expected tag"))?;
+                let val = acc
+                    .next_element_seed(Seed(self.0))?
+                    .ok_or_else(|| de::Error::custom("expected val"))?;
+                Ok(Value::Tag(tag, self.0.alloc(val)))
+            }
+        }
+
+        let (_, data): (de::IgnoredAny, _) = acc.variant()?;
+        data.tuple_variant(2, Inner(self.0))
+    }
+}
+
+/// Deserializes a [`Value`] into `arena` from a type with [`impl
+/// ciborium_io::Read`](ciborium_io::Read)
+#[inline]
+pub fn from_reader<'a, R: Read>(reader: R, arena: &'a Bump) -> Result<Value<'a>, Error<R::Error>>
+where
+    R::Error: fmt::Debug,
+{
+    from_reader_seed(reader, Seed(arena))
+}
+
+/// Deserializes a [`Value`] into `arena` from a byte slice
+#[inline]
+pub fn from_slice<'a>(
+    slice: &[u8],
+    arena: &'a Bump,
+) -> Result<Value<'a>, Error<<&'a [u8] as Read>::Error>> {
+    from_slice_seed(slice, Seed(arena))
+}
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of CDDL schemas describing CBOR encodings
+//!
+//! The [Concise Data Definition Language][CDDL] describes the structure of
+//! CBOR data. Types implementing [`Cddl`] can describe their own encoding by
+//! ciborium, so that a machine-checkable specification of a wire format can
+//! be derived from the Rust types which define it.
+//!
+//! ```
+//! use ciborium::cddl::{schema, Cddl, Schema};
+//!
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! impl Cddl for Point {
+//!     fn cddl(schema: &mut Schema) -> String {
+//!         schema.rule("Point", |schema| {
+//!             format!("{{ \"x\": {}, \"y\": {} }}", i32::cddl(schema), i32::cddl(schema))
+//!         })
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     schema::<Vec<Point>>(),
+//!     "root = [* Point]\nPoint = { \"x\": int, \"y\": int }\n",
+//! );
+//! ```
+//!
+//! Structs using the `ciborium-derive` crate can derive this trait.
+//!
+//! [CDDL]: https://www.rfc-editor.org/rfc/rfc8610
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    format,
+    string::String,
+    vec::Vec,
+};
+
+use crate::tag::{Accepted, AcceptedOneOf, Captured, Required, RequiredOneOf, TagSet};
+use crate::value::Value;
+
+/// A type whose CBOR encoding can be described in CDDL
+pub trait Cddl {
+    /// Returns the CDDL type of this type's encoding
+    ///
+    /// Types which are described by a named rule register it with `schema`
+    /// (see [`Schema::rule`]) and return the rule's name.
+    fn cddl(schema: &mut Schema) -> String;
+}
+
+/// A collection of named CDDL rules
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    rules: Vec<(String, Option<String>)>,
+}
+
+impl Schema {
+    /// Returns an empty schema
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a named rule, returning its name
+    ///
+    /// The definition is only generated the first time a rule is registered.
+    /// Since the rule is known to the schema before `define` is called,
+    /// recursive types terminate.
+    pub fn rule(&mut self, name: &str, define: impl FnOnce(&mut Self) -> String) -> String {
+        if self.rules.iter().all(|(n, ..)| n != name) {
+            let index = self.rules.len();
+            self.rules.push((name.into(), None));
+            self.rules[index].1 = Some(define(self));
+        }
+
+        name.into()
+    }
+
+    /// Renders the rules, in the order they were registered
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        for (name, definition) in &self.rules {
+            let definition = definition.as_deref().unwrap_or("any");
+            output.push_str(&format!("{} = {}\n", name, definition));
+        }
+
+        output
+    }
+}
+
+/// Returns the CDDL schema of the encoding of `T`
+///
+/// The first rule of the schema describes `T`. If `T` is not described by a
+/// named rule, that first rule is called `root`.
+pub fn schema<T: Cddl + ?Sized>() -> String {
+    let mut schema = Schema::new();
+    let root = T::cddl(&mut schema);
+
+    match schema.rules.first() {
+        Some((name, ..)) if *name == root => schema.render(),
+        _ => format!("root = {}\n{}", root, schema.render()),
+    }
+}
+
+macro_rules! implcddl {
+    ($($t:ty => $cddl:literal),* $(,)?) => {
+        $(
+            impl Cddl for $t {
+                #[inline]
+                fn cddl(_: &mut Schema) -> String {
+                    $cddl.into()
+                }
+            }
+        )*
+    };
+}
+
+implcddl! {
+    () => "null",
+    bool => "bool",
+    u8 => "uint",
+    u16 => "uint",
+    u32 => "uint",
+    u64 => "uint",
+    usize => "uint",
+    u128 => "uint / biguint",
+    i8 => "int",
+    i16 => "int",
+    i32 => "int",
+    i64 => "int",
+    isize => "int",
+    i128 => "int / bigint",
+    f32 => "float",
+    f64 => "float",
+    char => "tstr",
+    str => "tstr",
+    String => "tstr",
+    Value => "any",
+}
+
+macro_rules! implwrapper {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl<T: Cddl + ?Sized> Cddl for $t {
+                #[inline]
+                fn cddl(schema: &mut Schema) -> String {
+                    T::cddl(schema)
+                }
+            }
+        )*
+    };
+}
+
+implwrapper!(&T, &mut T, Box<T>);
+
+macro_rules! implarray {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl<T: Cddl> Cddl for $t {
+                #[inline]
+                fn cddl(schema: &mut Schema) -> String {
+                    format!("[* {}]", T::cddl(schema))
+                }
+            }
+        )*
+    };
+}
+
+implarray!([T], Vec<T>, VecDeque<T>, BTreeSet<T>);
+
+#[cfg(feature = "std")]
+impl<T: Cddl, S> Cddl for std::collections::HashSet<T, S> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("[* {}]", T::cddl(schema))
+    }
+}
+
+impl<T: Cddl, const N: usize> Cddl for [T; N] {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("[{}*{} {}]", N, N, T::cddl(schema))
+    }
+}
+
+impl<K: Cddl, V: Cddl> Cddl for BTreeMap<K, V> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("{{* {} => {}}}", K::cddl(schema), V::cddl(schema))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Cddl, V: Cddl, S> Cddl for std::collections::HashMap<K, V, S> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("{{* {} => {}}}", K::cddl(schema), V::cddl(schema))
+    }
+}
+
+impl<T: Cddl> Cddl for Option<T> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("{} / null", T::cddl(schema))
+    }
+}
+
+macro_rules! impltuple {
+    ($($t:ident),+) => {
+        impl<$($t: Cddl),+> Cddl for ($($t,)+) {
+            #[inline]
+            fn cddl(schema: &mut Schema) -> String {
+                let items: &[String] = &[$($t::cddl(schema)),+];
+                format!("[{}]", items.join(", "))
+            }
+        }
+    };
+}
+
+impltuple!(A);
+impltuple!(A, B);
+impltuple!(A, B, C);
+impltuple!(A, B, C, D);
+impltuple!(A, B, C, D, E);
+impltuple!(A, B, C, D, E, F);
+impltuple!(A, B, C, D, E, F, G);
+impltuple!(A, B, C, D, E, F, G, H);
+
+impl<V: Cddl> Cddl for Captured<V> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        let value = V::cddl(schema);
+        format!("#6.<uint>({}) / {}", value, value)
+    }
+}
+
+impl<V: Cddl, const TAG: u64> Cddl for Required<V, TAG> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        format!("#6.{}({})", TAG, V::cddl(schema))
+    }
+}
+
+impl<V: Cddl, const TAG: u64> Cddl for Accepted<V, TAG> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        let value = V::cddl(schema);
+        format!("#6.{}({}) / {}", TAG, value, value)
+    }
+}
+
+/// Describes the tags of a set applied to `value`
+fn tags<S: TagSet>(value: &str) -> Vec<String> {
+    S::TAGS
+        .iter()
+        .map(|tag| format!("#6.{}({})", tag, value))
+        .collect()
+}
+
+impl<V: Cddl, S: TagSet> Cddl for RequiredOneOf<V, S> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        tags::<S>(&V::cddl(schema)).join(" / ")
+    }
+}
+
+impl<V: Cddl, S: TagSet> Cddl for AcceptedOneOf<V, S> {
+    #[inline]
+    fn cddl(schema: &mut Schema) -> String {
+        let value = V::cddl(schema);
+        let mut choices = tags::<S>(&value);
+        choices.push(value);
+        choices.join(" / ")
+    }
+}
@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A smaller dynamic CBOR value, planned to replace the current one
+//!
+//! A [`crate::value::Value`] is 32 bytes on 64-bit targets: its `Vec` and
+//! `String` payloads are 24 bytes each, and the `i128` inside its `Integer`
+//! needs 16-byte alignment. The [`Value`] in this module holds boxed slices
+//! instead, which are 16 bytes, and an [`Integer`] stored as a sign and a
+//! 64-bit magnitude, the way CBOR encodes it. It is 24 bytes, and since
+//! decoding shrinks every string, array and map to its length, it keeps no
+//! spare capacity either.
+//!
+//! Changing the variant types of [`crate::value::Value`] breaks its users,
+//! so this representation is offered alongside it until the next major
+//! release. Conversions between the two are lossless.
+//!
+//! ```
+//! use ciborium::compact::Value;
+//!
+//! let bytes = [0xa1, 0x61, 0x61, 0x82, 0x01, 0x62, 0x68, 0x69];
+//! let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+//!
+//! let (key, array) = &value.as_map().unwrap()[0];
+//! assert_eq!(key.as_text(), Some("a"));
+//! assert_eq!(array.as_array().unwrap()[1].as_text(), Some("hi"));
+//!
+//! let full = ciborium::Value::from(value.clone());
+//! assert_eq!(Value::from(full), value);
+//! ```
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use serde::de::{self, Deserialize, Deserializer, EnumAccess, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeTupleVariant, Serializer};
+
+/// An integer in the range of CBOR integers, stored as a sign and a 64-bit
+/// magnitude
+///
+/// This has the range, ordering and hash of [`crate::value::Integer`], and
+/// converts to and from it, but only needs 8-byte alignment.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Integer {
+    negative: bool,
+    magnitude: u64,
+}
+
+impl From<crate::value::Integer> for Integer {
+    #[inline]
+    fn from(value: crate::value::Integer) -> Self {
+        let value = i128::from(value);
+
+        // The range of `crate::value::Integer` is that of CBOR integers, so
+        // the magnitude always fits.
+        match value < 0 {
+            true => Self {
+                negative: true,
+                magnitude: (-1 - value) as u64,
+            },
+            false => Self {
+                negative: false,
+                magnitude: value as u64,
+            },
+        }
+    }
+}
+
+impl From<Integer> for crate::value::Integer {
+    #[inline]
+    fn from(value: Integer) -> Self {
+        match value.negative {
+            true => Self::try_from(-1 - i128::from(value.magnitude)).unwrap(),
+            false => Self::from(value.magnitude),
+        }
+    }
+}
+
+impl From<Integer> for i128 {
+    #[inline]
+    fn from(value: Integer) -> Self {
+        match value.negative {
+            true => -1 - i128::from(value.magnitude),
+            false => i128::from(value.magnitude),
+        }
+    }
+}
+
+impl From<u64> for Integer {
+    #[inline]
+    fn from(value: u64) -> Self {
+        crate::value::Integer::from(value).into()
+    }
+}
+
+impl From<i64> for Integer {
+    #[inline]
+    fn from(value: i64) -> Self {
+        crate::value::Integer::from(value).into()
+    }
+}
+
+impl PartialOrd for Integer {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Integer {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        i128::from(*self).cmp(&i128::from(*other))
+    }
+}
+
+impl Hash for Integer {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        i128::from(*self).hash(state)
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Integer").field(&i128::from(*self)).finish()
+    }
+}
+
+impl fmt::Display for Integer {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&i128::from(*self), f)
+    }
+}
+
+/// A dynamic CBOR value holding boxed slices
+///
+/// The variants mirror those of [`crate::value::Value`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Value {
+    /// An integer
+    Integer(Integer),
+
+    /// Bytes
+    Bytes(Box<[u8]>),
+
+    /// A float
+    Float(f64),
+
+    /// A string
+    Text(Box<str>),
+
+    /// A boolean
+    Bool(bool),
+
+    /// Null
+    Null,
+
+    /// Tag
+    Tag(u64, Box<Value>),
+
+    /// An array
+    Array(Box<[Value]>),
+
+    /// A map
+    Map(Box<[(Value, Value)]>),
+}
+
+impl Value {
+    /// Returns the integer, if the value is one
+    pub fn as_integer(&self) -> Option<Integer> {
+        match self {
+            Value::Integer(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if the value is a byte string
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is a text string
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if the value is an array
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if the value is a map
+    pub fn as_map(&self) -> Option<&[(Value, Value)]> {
+        match self {
+            Value::Map(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl From<Value> for crate::value::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Integer(x) => Self::Integer(x.into()),
+            Value::Bytes(x) => Self::Bytes(x.into()),
+            Value::Float(x) => Self::Float(x),
+            Value::Text(x) => Self::Text(x.into()),
+            Value::Bool(x) => Self::Bool(x),
+            Value::Null => Self::Null,
+            Value::Tag(t, v) => Self::Tag(t, Box::new((*v).into())),
+            Value::Array(x) => Self::Array(x.into_vec().into_iter().map(Into::into).collect()),
+            Value::Map(x) => Self::Map(
+                x.into_vec()
+                    .into_iter()
+                    .map(|(k, v)| (k.into(), v.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<crate::value::Value> for Value {
+    fn from(value: crate::value::Value) -> Self {
+        use crate::value::Value as Full;
+
+        match value {
+            Full::Integer(x) => Self::Integer(x.into()),
+            Full::Bytes(x) => Self::Bytes(x.into()),
+            Full::Float(x) => Self::Float(x),
+            Full::Text(x) => Self::Text(x.into()),
+            Full::Bool(x) => Self::Bool(x),
+            Full::Null => Self::Null,
+            Full::Tag(t, v) => Self::Tag(t, Box::new((*v).into())),
+            Full::Array(x) => Self::Array(x.into_iter().map(Into::into).collect()),
+            Full::Map(x) => Self::Map(x.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Integer(x) => crate::value::Value::Integer((*x).into()).serialize(serializer),
+            Value::Bytes(x) => serializer.serialize_bytes(x),
+            Value::Float(x) => crate::value::Value::Float(*x).serialize(serializer),
+            Value::Text(x) => serializer.serialize_str(x),
+            Value::Bool(x) => serializer.serialize_bool(*x),
+            Value::Null => serializer.serialize_unit(),
+
+            Value::Tag(t, v) => {
+                let mut acc = serializer.serialize_tuple_variant("@@TAG@@", 0, "@@TAGGED@@", 2)?;
+                acc.serialize_field(t)?;
+                acc.serialize_field(v)?;
+                acc.end()
+            }
+
+            Value::Array(x) => {
+                let mut acc = serializer.serialize_seq(Some(x.len()))?;
+                for v in x.iter() {
+                    acc.serialize_element(v)?;
+                }
+                acc.end()
+            }
+
+            Value::Map(x) => {
+                let mut acc = serializer.serialize_map(Some(x.len()))?;
+                for (k, v) in x.iter() {
+                    acc.serialize_entry(k, v)?;
+                }
+                acc.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+macro_rules! mkvisit {
+    ($($f:ident($v:ty)),+ $(,)?) => {
+        $(
+            #[inline]
+            fn $f<E: de::Error>(self, v: $v) -> Result<Self::Value, E> {
+                Ok(Value::Integer(crate::value::Integer::from(v).into()))
+            }
+        )+
+    };
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a valid CBOR item")
+    }
+
+    mkvisit! {
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+    }
+
+    #[inline]
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        crate::value::Integer::try_from(v)
+            .map(|x| Value::Integer(x.into()))
+            .map_err(|_| E::custom("integer too large"))
+    }
+
+    #[inline]
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        crate::value::Integer::try_from(v)
+            .map(|x| Value::Integer(x.into()))
+            .map_err(|_| E::custom("integer too large"))
+    }
+
+    #[inline]
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    #[inline]
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::Text(v.into()))
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::Text(v.into_boxed_str()))
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(v.into()))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(v.into_boxed_slice()))
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+        let mut seq = Vec::with_capacity(acc.size_hint().filter(|&l| l < 1024).unwrap_or(0));
+
+        while let Some(elem) = acc.next_element()? {
+            seq.push(elem);
+        }
+
+        Ok(Value::Array(seq.into_boxed_slice()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+        let mut map = Vec::with_capacity(acc.size_hint().filter(|&l| l < 1024).unwrap_or(0));
+
+        while let Some(kv) = acc.next_entry()? {
+            map.push(kv);
+        }
+
+        Ok(Value::Map(map.into_boxed_slice()))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, acc: A) -> Result<Self::Value, A::Error> {
+        use serde::de::VariantAccess;
+
+        struct Inner;
+
+        impl<'de> Visitor<'de> for Inner {
+            type Value = Value;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a valid CBOR item")
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, mut acc: A) -> Result<Self::Value, A::Error> {
+                let tag: u64 = acc
+                    .next_element()?
+                    .ok_or_else(|| de::Error::custom("expected tag"))?;
+                let val = acc
+                    .next_element()?
+                    .ok_or_else(|| de::Error::custom("expected val"))?;
+                Ok(Value::Tag(tag, Box::new(val)))
+            }
+        }
+
+        let (_, data): (de::IgnoredAny, _) = acc.variant()?;
+        data.tuple_variant(2, Inner)
+    }
+}
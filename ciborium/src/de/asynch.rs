@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{from_slice_exact, Error};
+
+use ciborium_io::AsyncRead;
+use ciborium_ll::{Decoder, Header};
+use serde::de;
+
+/// The deepest nesting of arrays, maps and indefinite-length strings
+const DEPTH: usize = 32;
+
+/// Marks a container which ends with a break rather than after a count
+const INDEFINITE: u64 = u64::MAX;
+
+/// Deserializes one item as CBOR from a type with [`impl
+/// ciborium_io::AsyncRead`](ciborium_io::AsyncRead)
+///
+/// The item is first read into `buffer`, awaiting the reader as needed,
+/// and then deserialized from there; strings and byte strings may borrow
+/// from the buffer, as with [`from_slice()`](super::from_slice). No more
+/// than the item is read, so the next item can be read from the reader
+/// afterwards. Reading fails if the item does not fit in the buffer,
+/// or if it nests more than 32 levels deep.
+///
+/// This needs no runtime, so it can be used on embedded executors without
+/// `std`:
+///
+/// ```
+/// # embassy_futures::block_on(async {
+/// let input = [0x82, 0x01, 0x02, 0x03];
+/// let mut reader = &input[..];
+/// let mut buffer = [0u8; 64];
+///
+/// let value: [u8; 2] = ciborium::de::from_async_reader(&mut reader, &mut buffer).await.unwrap();
+/// assert_eq!(value, [1, 2]);
+/// assert_eq!(reader, [0x03]);
+/// # });
+/// ```
+pub async fn from_async_reader<'a, T: de::Deserialize<'a>, R: AsyncRead>(
+    reader: &mut R,
+    buffer: &'a mut [u8],
+) -> Result<T, Error<R::Error>>
+where
+    R::Error: core::fmt::Debug,
+{
+    let len = read_item(reader, buffer).await?;
+    from_slice_exact(&buffer[..len]).map_err(|e| e.map_io(|_| Error::Syntax(len as u64)))
+}
+
+/// Reads one complete item into `buffer`, returning its length
+async fn read_item<R: AsyncRead>(
+    reader: &mut R,
+    buffer: &mut [u8],
+) -> Result<usize, Error<R::Error>> {
+    // The items left in each open container; `left` is the innermost.
+    let mut stack = [0u64; DEPTH];
+    let mut depth = 0;
+    let mut left = 1;
+    let mut len = 0;
+
+    loop {
+        while left == 0 {
+            if depth == 0 {
+                return Ok(len);
+            }
+
+            depth -= 1;
+            left = stack[depth];
+        }
+
+        let offset = len;
+        let header = read_header(reader, buffer, &mut len).await?;
+
+        // A tag prefixes the item which follows it.
+        if left != INDEFINITE && !matches!(header, Header::Tag(..)) {
+            left -= 1;
+        }
+
+        let items = match header {
+            Header::Break if left == INDEFINITE => {
+                left = 0;
+                continue;
+            }
+
+            Header::Break => return Err(Error::Syntax(offset as u64)),
+
+            Header::Bytes(Some(n)) | Header::Text(Some(n)) => {
+                // A length beyond `usize` cannot fit in the buffer either.
+                let n = usize::try_from(n).unwrap_or(usize::MAX);
+                read(reader, buffer, &mut len, n).await?;
+                continue;
+            }
+
+            Header::Bytes(None) | Header::Text(None) => INDEFINITE,
+            Header::Array(None) | Header::Map(None) => INDEFINITE,
+            Header::Array(Some(n)) => n,
+            Header::Map(Some(n)) => n.checked_mul(2).ok_or(Error::Syntax(offset as u64))?,
+            _ => continue,
+        };
+
+        if depth == DEPTH {
+            return Err(Error::RecursionLimitExceeded);
+        }
+
+        stack[depth] = left;
+        depth += 1;
+        left = items;
+    }
+}
+
+/// Reads a header into `buffer` and decodes it
+async fn read_header<R: AsyncRead>(
+    reader: &mut R,
+    buffer: &mut [u8],
+    len: &mut usize,
+) -> Result<Header, Error<R::Error>> {
+    let offset = *len;
+    read(reader, buffer, len, 1).await?;
+
+    let extra = match buffer[offset] & 0b00011111 {
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => 0,
+    };
+
+    read(reader, buffer, len, extra).await?;
+
+    Decoder::from(&buffer[offset..*len])
+        .pull()
+        .map_err(|_| Error::Syntax(offset as u64))
+}
+
+/// Reads `count` more bytes into `buffer`
+async fn read<R: AsyncRead>(
+    reader: &mut R,
+    buffer: &mut [u8],
+    len: &mut usize,
+    count: usize,
+) -> Result<(), Error<R::Error>> {
+    let end = len
+        .checked_add(count)
+        .filter(|end| *end <= buffer.len())
+        .ok_or_else(|| Error::semantic(*len as u64, "item does not fit in the buffer"))?;
+
+    reader.read_exact(&mut buffer[*len..end]).await?;
+    *len = end;
+    Ok(())
+}
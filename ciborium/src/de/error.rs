@@ -1,11 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use alloc::boxed::Box;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::{Debug, Display, Formatter, Result};
 
+use ciborium_ll::Header;
+
 use serde::de::{Error as DeError, StdError};
 
 /// An error occurred during deserialization
+///
+/// An error with [`Context`] only formats the context with the alternate
+/// flag (`{:#}`), over several lines; otherwise it formats as the error it
+/// wraps.
+#[non_exhaustive]
 #[derive(Clone, Debug)]
 pub enum Error<T> {
     /// An error occurred while reading bytes
@@ -16,27 +25,120 @@ pub enum Error<T> {
     /// An error occurred while parsing bytes
     ///
     /// Contains the offset into the stream where the syntax error occurred.
-    Syntax(usize),
+    Syntax(u64),
 
     /// An error occurred while processing a parsed value
     ///
     /// Contains a description of the error that occurred and (optionally)
     /// the offset into the stream indicating the start of the item being
     /// processed when the error occurred.
-    Semantic(Option<usize>, String),
+    Semantic(Option<u64>, String),
 
     /// The input caused serde to recurse too much
     ///
     /// This error prevents a stack overflow.
     RecursionLimitExceeded,
+
+    /// An error along with where in the input it occurred
+    ///
+    /// This is only returned when
+    /// [`Options::error_context`](super::Options::error_context) is enabled.
+    /// The error it wraps is never itself a `Context`.
+    Context(Box<Error<T>>, Box<Context>),
+}
+
+/// Where in the input an error occurred
+///
+/// This is attached to errors by [`Error::Context`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Context {
+    /// The offset at which the error occurred
+    ///
+    /// This is the offset of the error itself, if it has one, and otherwise
+    /// how far the input had been read when it occurred.
+    pub offset: u64,
+
+    /// The offset of the first byte of `bytes`
+    pub start: u64,
+
+    /// The raw input around `offset`
+    ///
+    /// When deserializing from a slice, this holds up to 8 bytes on either
+    /// side of `offset`. Other readers can only offer the bytes they still
+    /// hold in their buffer, which follow the point the input was read up to.
+    pub bytes: Vec<u8>,
+
+    /// The arrays, maps and tags enclosing the error, outermost first
+    pub stack: Vec<Frame>,
+}
+
+/// An array, map or tag enclosing an error
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// The offset of the header of the item
+    pub offset: u64,
+
+    /// The header of the item
+    pub header: Header,
 }
 
 impl<T> Error<T> {
     /// A helper method for composing a semantic error
     #[inline]
-    pub fn semantic(offset: impl Into<Option<usize>>, msg: impl Into<String>) -> Self {
+    pub fn semantic(offset: impl Into<Option<u64>>, msg: impl Into<String>) -> Self {
         Self::Semantic(offset.into(), msg.into())
     }
+
+    /// Returns the context of the error, if it has any
+    #[inline]
+    pub fn context(&self) -> Option<&Context> {
+        match self {
+            Error::Context(_, context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Removes the context of the error, if it has any
+    #[inline]
+    pub fn without_context(self) -> Self {
+        match self {
+            Error::Context(error, _) => *error,
+            error => error,
+        }
+    }
+
+    /// Adds `offset` to the offset of the error, if it has one
+    ///
+    /// This makes the offset of an error in an item relative to the input
+    /// which the item was cut from.
+    #[inline]
+    pub(crate) fn rebase(self, offset: u64) -> Self {
+        match self {
+            Error::Syntax(x) => Error::Syntax(x + offset),
+            Error::Semantic(x, msg) => Error::Semantic(x.map(|x| x + offset), msg),
+            Error::Context(error, mut context) => {
+                context.offset += offset;
+                context.start += offset;
+                for frame in &mut context.stack {
+                    frame.offset += offset;
+                }
+
+                Error::Context(Box::new(error.rebase(offset)), context)
+            }
+            error => error,
+        }
+    }
+
+    /// Converts the I/O error, if there is one, with `func`
+    pub(crate) fn map_io<U>(self, func: impl FnOnce(T) -> Error<U>) -> Error<U> {
+        match self {
+            Error::Io(e) => func(e),
+            Error::Syntax(x) => Error::Syntax(x),
+            Error::Semantic(x, msg) => Error::Semantic(x, msg),
+            Error::RecursionLimitExceeded => Error::RecursionLimitExceeded,
+            Error::Context(e, context) => Error::Context(Box::new(e.map_io(func)), context),
+        }
+    }
 }
 
 impl<T> From<T> for Error<T> {
@@ -57,9 +159,31 @@ impl<T> From<ciborium_ll::Error<T>> for Error<T> {
 }
 
 impl<T: Debug> Display for Error<T> {
-    #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{:?}", self)
+        let (error, context) = match self {
+            Error::Context(error, context) if f.alternate() => (error, context),
+            Error::Context(error, _) => return write!(f, "{:?}", error),
+            error => return write!(f, "{:?}", error),
+        };
+
+        write!(f, "{:?}\n  input at {}:", error, context.start)?;
+        for (offset, byte) in (context.start..).zip(&context.bytes) {
+            match offset == context.offset {
+                true => write!(f, " [{:02x}]", byte)?,
+                false => write!(f, " {:02x}", byte)?,
+            }
+        }
+
+        for (i, frame) in context.stack.iter().enumerate() {
+            match i {
+                0 => f.write_str("\n  within: ")?,
+                _ => f.write_str(" > ")?,
+            }
+
+            write!(f, "{:?} at {}", frame.header, frame.offset)?;
+        }
+
+        Ok(())
     }
 }
 
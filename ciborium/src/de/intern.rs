@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{collections::BTreeMap, collections::BTreeSet, rc::Rc, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+/// A cache of strings shared between decoded values
+///
+/// Decoding thousands of records with identical keys into `String` keys
+/// allocates every key of every record. An `Interner` hands out a shared
+/// `Rc<str>` instead, so that each distinct key is allocated only once. It
+/// is used as a [`DeserializeSeed`], with
+/// [`from_reader_seed()`](super::from_reader_seed) or
+/// [`from_slice_seed()`](super::from_slice_seed):
+///
+/// ```
+/// use ciborium::de::{from_reader_seed, Interner};
+/// use ciborium::cbor;
+///
+/// let value = cbor!([{ "id" => 1, "ok" => true }, { "id" => 2, "ok" => false }]).unwrap();
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&value, &mut bytes).unwrap();
+///
+/// let mut interner = Interner::new();
+/// let records = from_reader_seed(&bytes[..], interner.records::<ciborium::Value>()).unwrap();
+///
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[1]["id"], ciborium::Value::from(2));
+/// assert_eq!(interner.len(), 2);
+/// ```
+///
+/// `&mut Interner` is itself a seed decoding a single interned string, for
+/// use in custom seeds.
+#[derive(Clone, Debug, Default)]
+pub struct Interner(BTreeSet<Rc<str>>);
+
+impl Interner {
+    /// Returns an empty interner
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of `string`, adding it if necessary
+    pub fn intern(&mut self, string: &str) -> Rc<str> {
+        match self.0.get(string) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned: Rc<str> = string.into();
+                self.0.insert(interned.clone());
+                interned
+            }
+        }
+    }
+
+    /// Returns the number of distinct strings interned
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether no strings have been interned
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a seed decoding a map with interned text keys
+    #[inline]
+    pub fn map<V>(&mut self) -> Map<'_, V> {
+        Map(self, PhantomData)
+    }
+
+    /// Returns a seed decoding an array of maps with interned text keys
+    #[inline]
+    pub fn records<V>(&mut self) -> Records<'_, V> {
+        Records(self, PhantomData)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for &mut Interner {
+    type Value = Rc<str>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Rc<str>, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for &mut Interner {
+    type Value = Rc<str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Rc<str>, E> {
+        Ok(self.intern(v))
+    }
+}
+
+/// A seed decoding a map with interned text keys
+///
+/// See [`Interner::map()`].
+#[derive(Debug)]
+pub struct Map<'a, V>(&'a mut Interner, PhantomData<V>);
+
+impl<'de, V: de::Deserialize<'de>> DeserializeSeed<'de> for Map<'_, V> {
+    type Value = BTreeMap<Rc<str>, V>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, V: de::Deserialize<'de>> Visitor<'de> for Map<'_, V> {
+    type Value = BTreeMap<Rc<str>, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map with text keys")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut output = BTreeMap::new();
+        while let Some(key) = map.next_key_seed(&mut *self.0)? {
+            output.insert(key, map.next_value()?);
+        }
+
+        Ok(output)
+    }
+}
+
+/// A seed decoding an array of maps with interned text keys
+///
+/// See [`Interner::records()`].
+#[derive(Debug)]
+pub struct Records<'a, V>(&'a mut Interner, PhantomData<V>);
+
+impl<'de, V: de::Deserialize<'de>> DeserializeSeed<'de> for Records<'_, V> {
+    type Value = Vec<BTreeMap<Rc<str>, V>>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, V: de::Deserialize<'de>> Visitor<'de> for Records<'_, V> {
+    type Value = Vec<BTreeMap<Rc<str>, V>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an array of maps with text keys")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut output = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(record) = seq.next_element_seed(self.0.map())? {
+            output.push(record);
+        }
+
+        Ok(output)
+    }
+}
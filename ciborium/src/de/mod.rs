@@ -2,17 +2,35 @@
 
 //! Serde deserialization support for CBOR
 
+#[cfg(feature = "async")]
+mod asynch;
 mod error;
-
-pub use error::Error;
-
-use alloc::{string::String, vec::Vec};
-
-use ciborium_io::Read;
+mod intern;
+mod options;
+#[cfg(feature = "std")]
+mod recover;
+mod replay;
+mod stats;
+
+#[cfg(feature = "async")]
+pub use asynch::from_async_reader;
+pub use error::{Context, Error, Frame};
+pub use intern::{Interner, Map, Records};
+pub use options::Options;
+#[cfg(feature = "std")]
+pub use recover::{Recover, Skipped};
+pub use replay::Replay;
+pub use stats::Stats;
+
+use alloc::{borrow::Cow, boxed::Box, format, string::String, vec::Vec};
+use core::cmp::Ordering;
+
+use ciborium_io::{Position, Read};
 use ciborium_ll::*;
-use serde::de::{self, value::BytesDeserializer, Deserializer as _};
+use serde::de::{self, value::BytesDeserializer, Deserializer as _, IntoDeserializer};
 
 use crate::tag::TagAccess;
+use crate::value::{array_index, integer_key};
 
 trait Expected<E: de::Error> {
     fn expected(self, kind: &'static str) -> E;
@@ -47,19 +65,428 @@ impl<E: de::Error> Expected<E> for Header {
     }
 }
 
-/// Deserializer
-pub struct Deserializer<'b, R> {
+/// A serde deserializer for CBOR
+///
+/// Most users should use [`from_reader`] instead. This type is exposed for
+/// libraries which compose with other serde tooling and therefore need to
+/// accept a `&mut Deserializer<R>`.
+///
+/// The deserializer borrows a scratch buffer which holds strings and byte
+/// strings while they are visited. See [`Options`] for the other settings.
+///
+/// ```
+/// use ciborium::de::{Deserializer, Options};
+/// use serde::Deserialize;
+///
+/// let mut scratch = [0u8; 64];
+/// let mut deserializer = Deserializer::new(&[0x83, 0x01, 0x02, 0x03][..], &mut scratch, Options::new());
+/// let value = Vec::<u8>::deserialize(&mut deserializer).unwrap();
+/// assert_eq!(value, [1, 2, 3]);
+/// ```
+pub struct Deserializer<'b, R, B = Copied> {
     decoder: Decoder<R>,
     scratch: &'b mut [u8],
     recurse: usize,
+    options: Options,
+    stats: Option<Stats>,
+    borrow: core::marker::PhantomData<B>,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Determines whether a [`Deserializer`] can borrow from its input
+///
+/// This trait is sealed. Deserializers reading from a [`Read`] use
+/// [`Copied`]; those created by [`from_slice()`] and related functions use
+/// [`Borrowed`].
+pub trait Borrow<'de, R>: sealed::Sealed {
+    #[doc(hidden)]
+    fn borrow(reader: &R, len: usize) -> Option<&'de [u8]>;
+
+    /// Returns the input around `offset`, along with the offset of its start
+    ///
+    /// The reader has been read up to `position`.
+    #[doc(hidden)]
+    fn window(reader: &mut R, position: u64, offset: u64) -> (u64, Vec<u8>)
+    where
+        R: Read;
+}
+
+/// How many bytes on either side of an error are kept in its context
+const WINDOW: usize = 8;
+
+/// Input is copied out of the reader
+pub enum Copied {}
+
+/// Input is borrowed from a slice outliving the deserializer
+pub enum Borrowed {}
+
+impl sealed::Sealed for Copied {}
+impl sealed::Sealed for Borrowed {}
+
+impl<'de, R> Borrow<'de, R> for Copied {
+    #[inline]
+    fn borrow(_: &R, _: usize) -> Option<&'de [u8]> {
+        None
+    }
+
+    #[inline]
+    fn window(reader: &mut R, position: u64, _: u64) -> (u64, Vec<u8>)
+    where
+        R: Read,
+    {
+        // Only what is still buffered can be read without consuming input.
+        let bytes = reader.fill().unwrap_or_default();
+        (position, bytes[..bytes.len().min(WINDOW * 2)].to_vec())
+    }
+}
+
+impl<'de> Borrow<'de, Slice<'de>> for Borrowed {
+    #[inline]
+    fn borrow(reader: &Slice<'de>, len: usize) -> Option<&'de [u8]> {
+        reader.rest().get(..len)
+    }
+
+    #[inline]
+    fn window(reader: &mut Slice<'de>, _: u64, offset: u64) -> (u64, Vec<u8>) {
+        // Offsets into a slice always fit in `usize`.
+        let offset = (offset as usize).min(reader.input.len());
+        let start = offset.saturating_sub(WINDOW);
+        let end = offset.saturating_add(WINDOW).min(reader.input.len());
+        (start as u64, reader.input[start..end].to_vec())
+    }
+}
+
+/// A reader over a slice which exposes the entire slice as its buffer
+pub struct Slice<'de> {
+    input: &'de [u8],
+    position: usize,
+}
+
+impl<'de> Slice<'de> {
+    /// Returns the input which has not been read yet
+    #[inline]
+    fn rest(&self) -> &'de [u8] {
+        &self.input[self.position..]
+    }
+}
+
+impl<'de> Read for Slice<'de> {
+    type Error = <&'de [u8] as Read>::Error;
+
+    #[inline]
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        let mut rest = self.rest();
+        rest.read_exact(data)?;
+        self.position = self.input.len() - rest.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.rest())
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        assert!(amount <= self.input.len() - self.position);
+        self.position += amount;
+    }
 }
 
 fn noop(_: u8) {}
 
+/// Validates UTF-8, replacing invalid sequences if the options allow it
+#[inline]
+fn text<E>(bytes: &[u8], options: Options, offset: u64) -> Result<Cow<'_, str>, Error<E>> {
+    match from_utf8(bytes) {
+        Ok(s) => Ok(Cow::Borrowed(s)),
+        Err(..) if !options.lossy_utf8 => Err(Error::Syntax(offset)),
+        Err(..) if !options.allocate => Err(Error::semantic(offset, "allocation required")),
+        Err(..) => Ok(String::from_utf8_lossy(bytes)),
+    }
+}
+
+/// Converts the magnitude of an integer to a float, if it converts exactly
+#[inline]
+fn float<E>(magnitude: u128, offset: u64) -> Result<f64, Error<E>> {
+    // Magnitudes never exceed 2^64, so the cast back cannot saturate.
+    let x = magnitude as f64;
+    match x as u128 == magnitude {
+        true => Ok(x),
+        false => Err(Error::semantic(
+            offset,
+            "integer is not exactly representable",
+        )),
+    }
+}
+
 impl<'a, R: Read> Deserializer<'a, R>
 where
     R::Error: core::fmt::Debug,
 {
+    /// Creates a new deserializer reading from `reader`
+    ///
+    /// The `scratch` buffer is used as temporary space for strings and byte
+    /// strings; see [`Options::scratch_size`] for its effect.
+    #[inline]
+    pub fn new(reader: R, scratch: &'a mut [u8], options: Options) -> Self {
+        Self {
+            decoder: reader.into(),
+            scratch,
+            recurse: options.recursion_limit,
+            options,
+            stats: None,
+            borrow: core::marker::PhantomData,
+        }
+    }
+
+    /// Creates a new deserializer reading from `reader` at its position
+    ///
+    /// This is like [`Deserializer::new()`], except that the offsets in
+    /// errors are absolute positions in the stream rather than relative to
+    /// where deserialization started.
+    ///
+    /// ```
+    /// use ciborium::de::{Deserializer, Error, Options};
+    /// use serde::Deserialize;
+    /// use std::io::Cursor;
+    ///
+    /// // A frame header followed by a truncated array
+    /// let mut cursor = Cursor::new([0xff, 0xff, 0x82, 0x01, 0x1c]);
+    /// cursor.set_position(2);
+    ///
+    /// let mut scratch = [0u8; 16];
+    /// let mut deserializer = Deserializer::with_position(&mut cursor, &mut scratch, Options::new());
+    /// let error = Vec::<u8>::deserialize(&mut deserializer).unwrap_err();
+    /// assert!(matches!(error, Error::Syntax(4)));
+    /// ```
+    #[inline]
+    pub fn with_position(reader: R, scratch: &'a mut [u8], options: Options) -> Self
+    where
+        R: Position,
+    {
+        Self {
+            decoder: Decoder::with_position(reader),
+            scratch,
+            recurse: options.recursion_limit,
+            options,
+            stats: None,
+            borrow: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de> Deserializer<'a, Slice<'de>, Borrowed> {
+    /// Creates a new deserializer borrowing from `slice`
+    ///
+    /// Strings and byte strings are visited as borrowed from the slice, so
+    /// they can be deserialized into `&str` and `&[u8]`. The `scratch`
+    /// buffer is only used for segmented strings and byte strings.
+    #[inline]
+    pub fn from_slice(slice: &'de [u8], scratch: &'a mut [u8], options: Options) -> Self {
+        Self {
+            decoder: Slice {
+                input: slice,
+                position: 0,
+            }
+            .into(),
+            scratch,
+            recurse: options.recursion_limit,
+            options,
+            stats: None,
+            borrow: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, R: Read, B> Deserializer<'a, Replay<R>, B>
+where
+    R::Error: core::fmt::Debug,
+{
+    /// Attempts to decode with `func`, rolling back the input if it fails
+    ///
+    /// If `func` fails, the input is returned to where it was before the
+    /// attempt, so that another way of decoding it can be tried. This works
+    /// over any reader, since the input consumed by the attempt is kept by
+    /// the [`Replay`] until the attempt ends.
+    ///
+    /// ```
+    /// use ciborium::de::{Deserializer, Options, Replay};
+    /// use serde::Deserialize;
+    ///
+    /// // A version 2 message: [2, "hello"]
+    /// let bytes = [0x82, 0x02, 0x65, b'h', b'e', b'l', b'l', b'o'];
+    ///
+    /// let mut scratch = [0u8; 16];
+    /// let reader = Replay::new(&bytes[..]);
+    /// let mut deserializer = Deserializer::new(reader, &mut scratch, Options::new());
+    ///
+    /// let v1 = deserializer.speculate(|de| <(u8, u8)>::deserialize(de));
+    /// assert!(v1.is_err());
+    ///
+    /// let v2 = deserializer.speculate(|de| <(u8, String)>::deserialize(de));
+    /// assert_eq!(v2.unwrap(), (2, "hello".into()));
+    /// ```
+    ///
+    /// This must be called between items: a header which has been read
+    /// ahead of the attempt is discarded.
+    pub fn speculate<T, E>(
+        &mut self,
+        func: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let offset = self.decoder.offset();
+        let stats = self.stats;
+        self.decoder.get_mut().checkpoint();
+
+        let result = func(self);
+        match result {
+            Ok(..) => self.decoder.get_mut().commit(),
+            Err(..) => {
+                self.decoder.get_mut().rollback();
+                self.decoder.rewind(offset);
+                self.stats = stats;
+            }
+        }
+
+        result
+    }
+}
+
+impl<'a, R: Read, B> Deserializer<'a, R, B>
+where
+    R::Error: core::fmt::Debug,
+{
+    /// Takes `len` bytes out of the input, if they outlive the deserializer
+    #[inline]
+    fn borrowed<'de>(&mut self, len: u64) -> Option<&'de [u8]>
+    where
+        B: Borrow<'de, R>,
+    {
+        let len = usize::try_from(len).ok()?;
+        let bytes = B::borrow(self.decoder.get_ref(), len)?;
+        self.decoder.advance(len);
+        Some(bytes)
+    }
+
+    /// Unwraps the reader, consuming the deserializer
+    ///
+    /// After a value has been deserialized successfully, the reader is
+    /// positioned just past it, so the rest of the input can be handed back
+    /// to the transport layer:
+    ///
+    /// ```
+    /// use ciborium::de::{Deserializer, Options};
+    /// use serde::Deserialize;
+    ///
+    /// // A handshake message ("hi") followed by raw bytes
+    /// let bytes = [0x62, b'h', b'i', 0xaa, 0xbb];
+    ///
+    /// let mut scratch = [0u8; 16];
+    /// let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    /// let hello = String::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(hello, "hi");
+    /// assert_eq!(deserializer.into_inner(), [0xaa, 0xbb]);
+    /// ```
+    ///
+    /// After an error, the position of the reader within the input is
+    /// unspecified.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.decoder.into_inner()
+    }
+
+    /// Unwraps the reader after an item, along with the offset reached
+    ///
+    /// Fails if a header has been read past the end of the item.
+    #[inline]
+    fn finish(mut self) -> Result<(R, u64), Error<R::Error>> {
+        let offset = self.decoder.offset();
+        match self.decoder.into_parts() {
+            (reader, None) => Ok((reader, offset)),
+            (_, Some(..)) => Err(Error::semantic(offset, "trailing data")),
+        }
+    }
+
+    /// Returns the offset of the next item in the input
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) fn offset(&mut self) -> u64 {
+        self.decoder.offset()
+    }
+
+    /// Fails unless the options permit allocating
+    #[inline]
+    fn allocate(&mut self, offset: u64) -> Result<(), Error<R::Error>> {
+        match self.options.allocate {
+            true => Ok(()),
+            false => Err(Error::semantic(offset, "allocation required")),
+        }
+    }
+
+    /// Returns whether a simple value is unassigned and read as null
+    #[inline]
+    fn unknown_simple(&self, value: u8) -> bool {
+        self.options.unknown_simple_as_null && !simple::is_assigned(value)
+    }
+
+    /// Counts a tag preceding an item, failing once there are too many
+    #[inline]
+    fn tagged(&mut self, tags: &mut usize, offset: u64) -> Result<(), Error<R::Error>> {
+        *tags += 1;
+        match *tags > self.options.tag_limit {
+            true => Err(Error::semantic(offset, "tag limit exceeded")),
+            false => Ok(()),
+        }
+    }
+
+    /// Pulls the next header, counting it if stats are being collected
+    #[inline]
+    fn pull(&mut self) -> Result<Header, Error<R::Error>> {
+        let header = self.decoder.pull()?;
+        if let Some(stats) = &mut self.stats {
+            let depth = self.options.recursion_limit.saturating_sub(self.recurse);
+            stats.record(header, depth + 1);
+        }
+
+        Ok(header)
+    }
+
+    /// Pushes back a header read by [`pull()`](Self::pull), uncounting it
+    #[inline]
+    fn push(&mut self, header: Header) {
+        if let Some(stats) = &mut self.stats {
+            stats.unrecord(header);
+        }
+
+        self.decoder.push(header);
+    }
+
+    /// Skips a tag which the visited type does not capture
+    #[inline]
+    fn skip_tag(&mut self, tags: &mut usize, offset: u64, tag: u64) -> Result<(), Error<R::Error>> {
+        match self.options.skip_tags {
+            true => self.tagged(tags, offset),
+            false => Err(Error::semantic(offset, format!("unexpected tag {}", tag))),
+        }
+    }
+
+    /// Pulls the next header and its offset, skipping tags which the visited
+    /// type does not capture
+    #[inline]
+    fn pull_untagged(&mut self) -> Result<(u64, Header), Error<R::Error>> {
+        let mut tags = 0;
+        loop {
+            let offset = self.decoder.offset();
+            match self.pull()? {
+                Header::Tag(tag) => self.skip_tag(&mut tags, offset, tag)?,
+                header => return Ok((offset, header)),
+            }
+        }
+    }
+
     #[inline]
     fn recurse<V, F: FnOnce(&mut Self) -> Result<V, Error<R::Error>>>(
         &mut self,
@@ -75,6 +502,529 @@ where
         result
     }
 
+    /// Decodes the contents of the array, map or tag at `offset` with `func`
+    ///
+    /// When error context is enabled, an error is given its context as it
+    /// leaves the innermost item, and each item it leaves is added to the
+    /// context's stack.
+    #[inline]
+    fn nest<'de, V, F>(
+        &mut self,
+        offset: u64,
+        header: Header,
+        func: F,
+    ) -> Result<V, Error<R::Error>>
+    where
+        B: Borrow<'de, R>,
+        F: FnOnce(&mut Self) -> Result<V, Error<R::Error>>,
+    {
+        self.recurse(func)
+            .map_err(|error| match self.attach(error) {
+                Error::Context(error, mut context) => {
+                    context.stack.insert(0, Frame { offset, header });
+                    Error::Context(error, context)
+                }
+                error => error,
+            })
+    }
+
+    /// Wraps `error` in its context, if error context is enabled
+    fn attach<'de>(&mut self, error: Error<R::Error>) -> Error<R::Error>
+    where
+        B: Borrow<'de, R>,
+    {
+        if !self.options.error_context || matches!(error, Error::Context(..)) {
+            return error;
+        }
+
+        let position = self.decoder.offset();
+        let offset = match error {
+            Error::Syntax(x) | Error::Semantic(Some(x), _) => x,
+            _ => position,
+        };
+
+        let (start, bytes) = B::window(self.decoder.get_mut(), position, offset);
+        let context = Context {
+            offset,
+            start,
+            bytes,
+            stack: Vec::new(),
+        };
+
+        Error::Context(Box::new(error), Box::new(context))
+    }
+
+    /// Skips over the next item in the input
+    ///
+    /// The item, including any nested items, is skipped at the decoder level
+    /// without being visited, and the contents of strings and byte strings
+    /// are discarded without copying them out or validating them. This is
+    /// what happens to fields of a map which a struct does not declare, so
+    /// deserializing a struct projects only its fields out of a larger map.
+    ///
+    /// ```
+    /// use ciborium::de::{Deserializer, Options};
+    /// use serde::Deserialize;
+    ///
+    /// // [1, 2, 3] followed by "next"
+    /// let bytes = [0x83, 0x01, 0x02, 0x03, 0x64, b'n', b'e', b'x', b't'];
+    ///
+    /// let mut scratch = [0u8; 16];
+    /// let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    /// deserializer.skip_item().unwrap();
+    /// assert_eq!(String::deserialize(&mut deserializer).unwrap(), "next");
+    /// ```
+    pub fn skip_item(&mut self) -> Result<(), Error<R::Error>> {
+        let mut tags = 0;
+        loop {
+            let offset = self.decoder.offset();
+
+            return match self.pull()? {
+                Header::Tag(..) => {
+                    self.tagged(&mut tags, offset)?;
+                    continue;
+                }
+
+                Header::Positive(..)
+                | Header::Negative(..)
+                | Header::Float(..)
+                | Header::Simple(..) => Ok(()),
+
+                Header::Bytes(Some(len)) | Header::Text(Some(len)) => self.discard(len),
+
+                Header::Bytes(None) => self.discard_segments(|h| match h {
+                    Header::Bytes(Some(len)) => Some(len),
+                    _ => None,
+                }),
+
+                Header::Text(None) => self.discard_segments(|h| match h {
+                    Header::Text(Some(len)) => Some(len),
+                    _ => None,
+                }),
+
+                Header::Array(len) => self.recurse(|me| me.skip_items(len, 1)),
+                Header::Map(len) => self.recurse(|me| me.skip_items(len, 2)),
+
+                Header::Break => Err(Error::Syntax(offset)),
+            };
+        }
+    }
+
+    /// Skips the items of an array (`per == 1`) or map (`per == 2`)
+    #[inline]
+    fn skip_items(&mut self, len: Option<u64>, per: usize) -> Result<(), Error<R::Error>> {
+        match len {
+            Some(len) => {
+                for _ in 0..len {
+                    for _ in 0..per {
+                        self.skip_item()?;
+                    }
+                }
+            }
+
+            None => loop {
+                match self.pull()? {
+                    Header::Break => break,
+                    header => self.push(header),
+                }
+
+                for _ in 0..per {
+                    self.skip_item()?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Starts counting the items read from the input
+    ///
+    /// From then on, every item the deserializer reads, whether it is
+    /// deserialized or skipped, is counted by type, along with the depth of
+    /// its nesting and the sizes of its strings and collections. Services
+    /// can collect these from production payloads to monitor them and tune
+    /// their [`Options`]. Calling this again starts the counts over.
+    ///
+    /// ```
+    /// use ciborium::de::{Deserializer, Options};
+    /// use ciborium::Value;
+    /// use serde::Deserialize;
+    ///
+    /// // {"a": [1, -1], "b": "xyz"}
+    /// let bytes = [0xa2, 0x61, 0x61, 0x82, 0x01, 0x20, 0x61, 0x62, 0x63, 0x78, 0x79, 0x7a];
+    ///
+    /// let mut scratch = [0u8; 16];
+    /// let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    /// deserializer.collect_stats();
+    ///
+    /// Value::deserialize(&mut deserializer).unwrap();
+    /// let stats = deserializer.stats().unwrap();
+    ///
+    /// assert_eq!((stats.maps, stats.arrays, stats.text), (1, 1, 3));
+    /// assert_eq!(stats.max_depth, 3);
+    /// assert_eq!(stats.string_bytes, 5);
+    /// assert_eq!(stats.largest_collection, 2);
+    /// ```
+    #[inline]
+    pub fn collect_stats(&mut self) {
+        self.stats = Some(Stats::default());
+    }
+
+    /// Returns the counts collected since [`collect_stats()`](Self::collect_stats)
+    ///
+    /// Returns `None` if stats are not being collected.
+    #[inline]
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats
+    }
+
+    /// Discards the segments of an indefinite-length string
+    #[inline]
+    fn discard_segments(
+        &mut self,
+        segment: impl Fn(Header) -> Option<u64>,
+    ) -> Result<(), Error<R::Error>> {
+        loop {
+            let offset = self.decoder.offset();
+
+            match self.decoder.pull()? {
+                Header::Break => return Ok(()),
+                header => match segment(header) {
+                    Some(len) => self.discard(len)?,
+                    None => return Err(Error::Syntax(offset)),
+                },
+            }
+        }
+    }
+
+    /// Discards the next `len` bytes of input
+    #[inline]
+    fn discard(&mut self, mut len: u64) -> Result<(), Error<R::Error>> {
+        let mut chunk = [0u8; 64];
+
+        // The length may exceed `usize`, but each step is bounded by a buffer.
+        while len > 0 {
+            let buffered = (self.decoder.fill()?.len() as u64).min(len) as usize;
+            if buffered > 0 {
+                self.decoder.advance(buffered);
+                len -= buffered as u64;
+                continue;
+            }
+
+            let buffer = match self.scratch.len() > chunk.len() {
+                true => &mut *self.scratch,
+                false => &mut chunk[..],
+            };
+
+            let amount = (buffer.len() as u64).min(len) as usize;
+            self.decoder.read_exact(&mut buffer[..amount])?;
+            len -= amount as u64;
+        }
+
+        Ok(())
+    }
+
+    /// Copies the next item out of the input onto `out`
+    ///
+    /// Each header is written in its shortest form, which may differ from
+    /// the input.
+    fn capture(&mut self, out: &mut Vec<u8>) -> Result<(), Error<R::Error>> {
+        let offset = self.decoder.offset();
+        let header = self.pull()?;
+        let _ = Encoder::from(&mut *out).push(header);
+
+        match header {
+            Header::Bytes(Some(len)) | Header::Text(Some(len)) => self.copy(len, out),
+
+            Header::Bytes(None) | Header::Text(None) => loop {
+                let offset = self.decoder.offset();
+                let chunk = self.decoder.pull()?;
+                let _ = Encoder::from(&mut *out).push(chunk);
+
+                match (header, chunk) {
+                    (_, Header::Break) => return Ok(()),
+                    (Header::Bytes(..), Header::Bytes(Some(len))) => self.copy(len, out)?,
+                    (Header::Text(..), Header::Text(Some(len))) => self.copy(len, out)?,
+                    _ => return Err(Error::Syntax(offset)),
+                }
+            },
+
+            Header::Array(len) => self.recurse(|me| me.capture_items(len, 1, out)),
+            Header::Map(len) => self.recurse(|me| me.capture_items(len, 2, out)),
+            Header::Tag(..) => self.recurse(|me| me.capture(out)),
+            Header::Break => Err(Error::Syntax(offset)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Copies the items of an array (`per == 1`) or map (`per == 2`)
+    fn capture_items(
+        &mut self,
+        len: Option<u64>,
+        per: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Error<R::Error>> {
+        match len {
+            Some(len) => {
+                for _ in 0..len {
+                    for _ in 0..per {
+                        self.capture(out)?;
+                    }
+                }
+            }
+
+            None => loop {
+                match self.pull()? {
+                    Header::Break => {
+                        let _ = Encoder::from(&mut *out).push(Header::Break);
+                        break;
+                    }
+                    header => self.push(header),
+                }
+
+                for _ in 0..per {
+                    self.capture(out)?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Copies the next `len` bytes of input onto `out`
+    fn copy(&mut self, mut len: u64, out: &mut Vec<u8>) -> Result<(), Error<R::Error>> {
+        let mut chunk = [0u8; 64];
+
+        while len > 0 {
+            let buffered = self.decoder.fill()?;
+            let amount = (buffered.len() as u64).min(len) as usize;
+            if amount > 0 {
+                out.extend_from_slice(&buffered[..amount]);
+                self.decoder.advance(amount);
+                len -= amount as u64;
+                continue;
+            }
+
+            let amount = (chunk.len() as u64).min(len) as usize;
+            self.decoder.read_exact(&mut chunk[..amount])?;
+            out.extend_from_slice(&chunk[..amount]);
+            len -= amount as u64;
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes `seed` from `item`, which was copied from `offset`
+    fn copied<'de, S: de::DeserializeSeed<'de>>(
+        &mut self,
+        item: &[u8],
+        offset: u64,
+        seed: S,
+    ) -> Result<S::Value, Error<R::Error>> {
+        let mut deserializer = Deserializer::<_, Copied> {
+            decoder: Decoder::from(item),
+            scratch: &mut *self.scratch,
+            recurse: self.recurse,
+            options: self.options.error_context(false),
+            stats: None,
+            borrow: core::marker::PhantomData,
+        };
+
+        seed.deserialize(&mut deserializer)
+            .map_err(|e| e.rebase(offset).map_io(|_| Error::Syntax(offset)))
+    }
+
+    /// Consumes the next `len` bytes of input, matching them against the
+    /// front of `expected`
+    ///
+    /// The matched bytes are removed from `expected`. The input is consumed
+    /// even if it does not match.
+    #[inline]
+    fn discard_matching(
+        &mut self,
+        len: u64,
+        expected: &mut &[u8],
+    ) -> Result<bool, Error<R::Error>> {
+        if len > expected.len() as u64 {
+            self.discard(len)?;
+            return Ok(false);
+        }
+
+        let len = len as usize;
+        let (head, tail) = expected.split_at(len);
+        *expected = tail;
+
+        let mut chunk = [0u8; 64];
+        let mut equal = true;
+        let mut offset = 0;
+
+        while offset < len {
+            let buffered = self.decoder.fill()?;
+            let amount = buffered.len().min(len - offset);
+            if amount > 0 {
+                equal &= buffered[..amount] == head[offset..][..amount];
+                self.decoder.advance(amount);
+            } else {
+                let amount = chunk.len().min(len - offset);
+                self.decoder.read_exact(&mut chunk[..amount])?;
+                equal &= chunk[..amount] == head[offset..][..amount];
+                offset += amount;
+                continue;
+            }
+
+            offset += amount;
+        }
+
+        Ok(equal)
+    }
+
+    /// Consumes a map key, returning whether it matches a pointer token
+    fn key_matches(&mut self, token: &str) -> Result<bool, Error<R::Error>> {
+        let mut tags = 0;
+        loop {
+            let offset = self.decoder.offset();
+            let mut expected = token.as_bytes();
+
+            return match self.pull()? {
+                Header::Tag(..) => {
+                    self.tagged(&mut tags, offset)?;
+                    continue;
+                }
+
+                Header::Text(Some(len)) => {
+                    Ok(self.discard_matching(len, &mut expected)? && expected.is_empty())
+                }
+
+                Header::Text(None) => {
+                    let mut equal = true;
+
+                    loop {
+                        let offset = self.decoder.offset();
+
+                        match self.decoder.pull()? {
+                            Header::Break => break,
+                            Header::Text(Some(len)) => {
+                                equal &= self.discard_matching(len, &mut expected)?
+                            }
+                            _ => return Err(Error::Syntax(offset)),
+                        }
+                    }
+
+                    Ok(equal && expected.is_empty())
+                }
+
+                Header::Positive(x) => Ok(integer_key(token) == Some(i128::from(x))),
+                Header::Negative(x) => Ok(integer_key(token) == Some(-1 - i128::from(x))),
+
+                header => {
+                    self.push(header);
+                    self.skip_item()?;
+                    Ok(false)
+                }
+            };
+        }
+    }
+
+    /// Advances the input to the item addressed by a JSON Pointer
+    ///
+    /// Returns false if no such item exists.
+    fn seek(&mut self, pointer: &str) -> Result<bool, Error<R::Error>> {
+        if pointer.is_empty() {
+            return Ok(true);
+        }
+
+        let pointer = match pointer.strip_prefix('/') {
+            Some(pointer) => pointer,
+            None => return Err(de::Error::custom("pointer must be empty or start with '/'")),
+        };
+
+        for token in pointer.split('/') {
+            let token = match token.contains('~') {
+                true => Cow::Owned(token.replace("~1", "/").replace("~0", "~")),
+                false => Cow::Borrowed(token),
+            };
+
+            if !self.seek_token(&token)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Advances the input into the child of a container named by `token`
+    fn seek_token(&mut self, token: &str) -> Result<bool, Error<R::Error>> {
+        let mut tags = 0;
+        let (len, map) = loop {
+            let offset = self.decoder.offset();
+            match self.pull()? {
+                Header::Tag(..) => {
+                    self.tagged(&mut tags, offset)?;
+                    continue;
+                }
+                Header::Array(len) => break (len, false),
+                Header::Map(len) => break (len, true),
+                _ => return Ok(false),
+            }
+        };
+
+        let index = array_index(token).map(|i| i as u64);
+        if !map && index.is_none() {
+            return Ok(false);
+        }
+
+        for i in 0.. {
+            match len {
+                Some(len) if i == len => return Ok(false),
+                Some(..) => (),
+                None => match self.pull()? {
+                    Header::Break => return Ok(false),
+                    header => self.push(header),
+                },
+            }
+
+            match map {
+                true if self.key_matches(token)? => return Ok(true),
+                false if Some(i) == index => return Ok(true),
+                _ => self.skip_item()?,
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// Passes the next `len` bytes of input to `func`
+    ///
+    /// The bytes are handed over directly from the reader's buffer when it
+    /// holds all of them. Otherwise, they are copied into the scratch buffer.
+    /// Returns `None` if they fit in neither.
+    #[inline]
+    fn read_bytes<T>(
+        &mut self,
+        len: u64,
+        func: impl FnOnce(&[u8]) -> T,
+    ) -> Result<Option<T>, Error<R::Error>> {
+        let len = match usize::try_from(len) {
+            Ok(len) => len,
+            Err(..) => return Ok(None),
+        };
+
+        if let Some(bytes) = self.decoder.fill()?.get(..len) {
+            let value = func(bytes);
+            self.decoder.advance(len);
+            return Ok(Some(value));
+        }
+
+        match self.scratch.get_mut(..len) {
+            Some(scratch) => {
+                self.decoder.read_exact(scratch)?;
+                Ok(Some(func(scratch)))
+            }
+
+            None => Ok(None),
+        }
+    }
+
     #[inline]
     fn integer<A: FnMut(u8)>(
         &mut self,
@@ -82,10 +1032,12 @@ where
         should_append: bool,
         mut append: A,
     ) -> Result<(bool, u128), Error<R::Error>> {
+        let mut tags = 0;
         loop {
+            let offset = self.decoder.offset();
             let header = match header.take() {
                 Some(h) => h,
-                None => self.decoder.pull()?,
+                None => self.pull()?,
             };
 
             let neg = match header {
@@ -93,7 +1045,24 @@ where
                 Header::Negative(x) => return Ok((true, x.into())),
                 Header::Tag(tag::BIGPOS) => false,
                 Header::Tag(tag::BIGNEG) => true,
-                Header::Tag(..) => continue,
+                Header::Tag(tag) => {
+                    self.skip_tag(&mut tags, offset, tag)?;
+                    continue;
+                }
+                Header::Float(x) if self.options.coerce_numbers && self.options.floats => {
+                    // The cast truncates and saturates, so the float is an
+                    // integer in range exactly when it survives the round trip.
+                    let magnitude = if x < 0.0 { -x } else { x };
+                    let raw = magnitude as u128;
+                    if raw as f64 != magnitude || magnitude >= u128::MAX as f64 {
+                        return Err(Error::semantic(offset, "float is not an integer"));
+                    }
+
+                    return match x < 0.0 {
+                        false => Ok((false, raw)),
+                        true => Ok((true, raw - 1)),
+                    };
+                }
                 header => return Err(header.expected("integer")),
             };
 
@@ -101,7 +1070,7 @@ where
             let mut value = [0u8; 16];
             let mut index = 0usize;
 
-            return match self.decoder.pull()? {
+            return match self.pull()? {
                 Header::Bytes(len) => {
                     let mut segments = self.decoder.bytes(len);
                     while let Some(mut segment) = segments.pull()? {
@@ -147,7 +1116,8 @@ where
     }
 }
 
-impl<'de, 'a, 'b, R: Read> de::Deserializer<'de> for &'a mut Deserializer<'b, R>
+impl<'de, 'a, 'b, R: Read, B: Borrow<'de, R>> de::Deserializer<'de>
+    for &'a mut Deserializer<'b, R, B>
 where
     R::Error: core::fmt::Debug,
 {
@@ -155,8 +1125,18 @@ where
 
     #[inline]
     fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        let header = self.decoder.pull()?;
-        self.decoder.push(header);
+        let offset = self.decoder.offset();
+        let header = self.pull()?;
+
+        // Items held entirely in the reader's buffer need no scratch space.
+        let limit = match header {
+            Header::Bytes(..) | Header::Text(..) => {
+                self.scratch.len().max(self.decoder.fill()?.len()) as u64
+            }
+            _ => 0,
+        };
+
+        self.push(header);
 
         match header {
             Header::Positive(..) => self.deserialize_u64(visitor),
@@ -166,12 +1146,12 @@ where
             },
 
             Header::Bytes(len) => match len {
-                Some(len) if len <= self.scratch.len() => self.deserialize_bytes(visitor),
+                Some(len) if len <= limit => self.deserialize_bytes(visitor),
                 _ => self.deserialize_byte_buf(visitor),
             },
 
             Header::Text(len) => match len {
-                Some(len) if len <= self.scratch.len() => self.deserialize_str(visitor),
+                Some(len) if len <= limit => self.deserialize_str(visitor),
                 _ => self.deserialize_string(visitor),
             },
 
@@ -179,29 +1159,47 @@ where
             Header::Map(..) => self.deserialize_map(visitor),
 
             Header::Tag(tag) => {
-                let _: Header = self.decoder.pull()?;
+                let _: Header = self.pull()?;
 
                 match tag {
                     tag::BIGPOS | tag::BIGNEG => {
                         let mut bytes = Vec::new();
-                        let result =
-                            match self.integer(Some(Header::Tag(tag)), true, |b| bytes.push(b))? {
-                                (false, _) if !bytes.is_empty() => {
-                                    let access =
-                                        TagAccess::new(BytesDeserializer::new(&bytes), Some(tag));
-                                    return visitor.visit_enum(access);
+                        let result = match self.integer(
+                            Some(Header::Tag(tag)),
+                            self.options.allocate,
+                            |b| bytes.push(b),
+                        )? {
+                            (false, _) if !bytes.is_empty() => {
+                                let access =
+                                    TagAccess::new(BytesDeserializer::new(&bytes), Some(tag));
+                                return visitor.visit_enum(access);
+                            }
+                            (false, raw) => match u64::try_from(raw) {
+                                Ok(x) if self.options.untagged_enums => {
+                                    return visitor.visit_u64(x)
                                 }
-                                (false, raw) => return visitor.visit_u128(raw),
-                                (true, raw) => i128::try_from(raw).map(|x| x ^ !0),
-                            };
-
-                        match result {
-                            Ok(x) => visitor.visit_i128(x),
+                                _ => return visitor.visit_u128(raw),
+                            },
+                            (true, raw) => i128::try_from(raw).map(|x| x ^ !0),
+                        };
+
+                        match result.map(|x| (x, i64::try_from(x))) {
+                            Ok((_, Ok(x))) if self.options.untagged_enums => visitor.visit_i64(x),
+                            Ok((x, _)) => visitor.visit_i128(x),
                             Err(..) => Err(de::Error::custom("integer too large")),
                         }
                     }
 
-                    _ => self.recurse(|me| {
+                    // Serde's buffered content cannot represent tags, so the
+                    // tag is dropped just as it is for typed deserialization.
+                    _ if self.options.untagged_enums && !self.options.skip_tags => {
+                        Err(Error::semantic(offset, format!("unexpected tag {}", tag)))
+                    }
+                    _ if self.options.untagged_enums => {
+                        self.nest(offset, header, |me| me.deserialize_any(visitor))
+                    }
+
+                    _ => self.nest(offset, header, |me| {
                         let access = TagAccess::new(me, Some(tag));
                         visitor.visit_enum(access)
                     }),
@@ -214,6 +1212,10 @@ where
             Header::Simple(simple::TRUE) => self.deserialize_bool(visitor),
             Header::Simple(simple::NULL) => self.deserialize_option(visitor),
             Header::Simple(simple::UNDEFINED) => self.deserialize_option(visitor),
+            Header::Simple(..) if self.options.unknown_simple_as_null => {
+                self.pull()?;
+                visitor.visit_none()
+            }
             h @ Header::Simple(..) => Err(h.expected("known simple value")),
 
             h @ Header::Break => Err(h.expected("non-break")),
@@ -222,15 +1224,11 @@ where
 
     #[inline]
     fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            let offset = self.decoder.offset();
-
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-                Header::Simple(simple::FALSE) => visitor.visit_bool(false),
-                Header::Simple(simple::TRUE) => visitor.visit_bool(true),
-                _ => Err(Error::semantic(offset, "expected bool")),
-            };
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Simple(simple::FALSE) => visitor.visit_bool(false),
+            Header::Simple(simple::TRUE) => visitor.visit_bool(true),
+            _ => Err(Error::semantic(offset, "expected bool")),
         }
     }
 
@@ -241,12 +1239,22 @@ where
 
     #[inline]
     fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-                Header::Float(x) => visitor.visit_f64(x),
-                h => Err(h.expected("float")),
-            };
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Float(..) if !self.options.floats => {
+                Err(Error::semantic(offset, "floats are not accepted"))
+            }
+            Header::Float(x) if !self.options.non_finite && !x.is_finite() => {
+                Err(Error::semantic(offset, "non-finite float"))
+            }
+            Header::Float(x) => visitor.visit_f64(x),
+            Header::Positive(x) if self.options.coerce_numbers => {
+                visitor.visit_f64(float(x.into(), offset)?)
+            }
+            Header::Negative(x) if self.options.coerce_numbers => {
+                visitor.visit_f64(-float(u128::from(x) + 1, offset)?)
+            }
+            h => Err(h.expected("float")),
         }
     }
 
@@ -318,92 +1326,129 @@ where
     }
 
     fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            let offset = self.decoder.offset();
-            let header = self.decoder.pull()?;
-
-            return match header {
-                Header::Tag(..) => continue,
-
-                Header::Text(Some(len)) if len <= 4 => {
-                    let mut buf = [0u8; 4];
-                    self.decoder.read_exact(&mut buf[..len])?;
-
-                    match core::str::from_utf8(&buf[..len]) {
-                        Ok(s) => match s.chars().count() {
-                            1 => visitor.visit_char(s.chars().next().unwrap()),
-                            _ => Err(header.expected("char")),
-                        },
-                        Err(..) => Err(Error::Syntax(offset)),
-                    }
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Text(Some(len)) if len <= 4 => {
+                let mut buf = [0u8; 4];
+                let buf = &mut buf[..len as usize];
+                self.decoder.read_exact(buf)?;
+
+                match core::str::from_utf8(buf) {
+                    Ok(s) => match s.chars().count() {
+                        1 => visitor.visit_char(s.chars().next().unwrap()),
+                        _ => Err(header.expected("char")),
+                    },
+                    Err(..) => Err(Error::Syntax(offset)),
                 }
+            }
 
-                _ => Err(header.expected("char")),
-            };
+            _ => Err(header.expected("char")),
         }
     }
 
     fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            let offset = self.decoder.offset();
-
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            header @ (Header::Text(Some(len)) | Header::Bytes(Some(len)))
+                if matches!(header, Header::Text(..)) || self.options.coerce_strings =>
+            {
+                if let Some(bytes) = self.borrowed(len) {
+                    return match text(bytes, self.options, offset)? {
+                        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+                        Cow::Owned(s) => visitor.visit_string(s),
+                    };
+                }
 
-                Header::Text(Some(len)) if len <= self.scratch.len() => {
-                    self.decoder.read_exact(&mut self.scratch[..len])?;
+                let options = self.options;
+                let visit = |bytes: &[u8]| visitor.visit_str(&text(bytes, options, offset)?);
 
-                    match core::str::from_utf8(&self.scratch[..len]) {
-                        Ok(s) => visitor.visit_str(s),
-                        Err(..) => Err(Error::Syntax(offset)),
-                    }
+                match self.read_bytes(len, visit)? {
+                    Some(result) => result,
+                    None => Err(header.expected("str")),
                 }
+            }
 
-                header => Err(header.expected("str")),
-            };
+            header => Err(header.expected("str")),
         }
     }
 
     fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            header @ (Header::Text(len) | Header::Bytes(len))
+                if matches!(header, Header::Text(..)) || self.options.coerce_strings =>
+            {
+                if let Some(bytes) = len.and_then(|len| self.borrowed(len)) {
+                    return match text(bytes, self.options, offset)? {
+                        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+                        Cow::Owned(s) => visitor.visit_string(s),
+                    };
+                }
 
-                Header::Text(len) => {
-                    let mut buffer = String::new();
+                self.allocate(offset)?;
+
+                // Segments are validated as a whole when they are not
+                // necessarily valid text on their own.
+                if self.options.lossy_utf8 || !matches!(header, Header::Text(..)) {
+                    let mut buffer = Vec::new();
 
-                    let mut segments = self.decoder.text(len);
+                    let mut segments = match header {
+                        Header::Text(..) => self.decoder.text_bytes(len),
+                        _ => self.decoder.bytes(len),
+                    };
                     while let Some(mut segment) = segments.pull()? {
                         while let Some(chunk) = segment.pull(self.scratch)? {
-                            buffer.push_str(chunk);
+                            buffer.extend_from_slice(chunk);
                         }
                     }
 
-                    visitor.visit_string(buffer)
+                    return match String::from_utf8(buffer) {
+                        Ok(s) => visitor.visit_string(s),
+                        Err(e) if self.options.lossy_utf8 => {
+                            visitor.visit_string(String::from_utf8_lossy(e.as_bytes()).into())
+                        }
+                        Err(..) => Err(Error::Syntax(offset)),
+                    };
                 }
 
-                header => Err(header.expected("string")),
-            };
+                let mut buffer = String::new();
+
+                let mut segments = self.decoder.text(len);
+                while let Some(mut segment) = segments.pull()? {
+                    while let Some(chunk) = segment.pull(self.scratch)? {
+                        buffer.push_str(chunk);
+                    }
+                }
+
+                visitor.visit_string(buffer)
+            }
+
+            header => Err(header.expected("string")),
         }
     }
 
     fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            header @ (Header::Bytes(Some(len)) | Header::Text(Some(len)))
+                if !matches!(header, Header::Text(..)) || self.options.coerce_strings =>
+            {
+                if let Some(bytes) = self.borrowed(len) {
+                    return visitor.visit_borrowed_bytes(bytes);
+                }
 
-                Header::Bytes(Some(len)) if len <= self.scratch.len() => {
-                    self.decoder.read_exact(&mut self.scratch[..len])?;
-                    visitor.visit_bytes(&self.scratch[..len])
+                match self.read_bytes(len, |bytes| visitor.visit_bytes(bytes))? {
+                    Some(result) => result,
+                    None => Err(header.expected("bytes")),
                 }
+            }
 
-                Header::Array(len) => self.recurse(|me| {
-                    let access = Access(me, len);
-                    visitor.visit_seq(access)
-                }),
+            Header::Array(len) => self.nest(offset, Header::Array(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_seq(access)
+            }),
 
-                header => Err(header.expected("bytes")),
-            };
+            header => Err(header.expected("bytes")),
         }
     }
 
@@ -411,73 +1456,80 @@ where
         self,
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            header @ (Header::Bytes(len) | Header::Text(len))
+                if !matches!(header, Header::Text(..)) || self.options.coerce_strings =>
+            {
+                if let Some(bytes) = len.and_then(|len| self.borrowed(len)) {
+                    return visitor.visit_borrowed_bytes(bytes);
+                }
 
-                Header::Bytes(len) => {
-                    let mut buffer = Vec::new();
+                self.allocate(offset)?;
+                let mut buffer = Vec::new();
 
-                    let mut segments = self.decoder.bytes(len);
-                    while let Some(mut segment) = segments.pull()? {
-                        while let Some(chunk) = segment.pull(self.scratch)? {
-                            buffer.extend_from_slice(chunk);
-                        }
+                let mut segments = match header {
+                    Header::Text(..) => self.decoder.text_bytes(len),
+                    _ => self.decoder.bytes(len),
+                };
+                while let Some(mut segment) = segments.pull()? {
+                    while let Some(chunk) = segment.pull(self.scratch)? {
+                        buffer.extend_from_slice(chunk);
                     }
-
-                    visitor.visit_byte_buf(buffer)
                 }
 
-                Header::Array(len) => self.recurse(|me| {
-                    let access = Access(me, len);
-                    visitor.visit_seq(access)
-                }),
+                visitor.visit_byte_buf(buffer)
+            }
 
-                header => Err(header.expected("byte buffer")),
-            };
+            Header::Array(len) => self.nest(offset, Header::Array(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_seq(access)
+            }),
+
+            header => Err(header.expected("byte buffer")),
         }
     }
 
     fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-
-                Header::Array(len) => self.recurse(|me| {
-                    let access = Access(me, len);
-                    visitor.visit_seq(access)
-                }),
-
-                Header::Bytes(len) => {
-                    let mut buffer = Vec::new();
-
-                    let mut segments = self.decoder.bytes(len);
-                    while let Some(mut segment) = segments.pull()? {
-                        while let Some(chunk) = segment.pull(self.scratch)? {
-                            buffer.extend_from_slice(chunk);
-                        }
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Array(len) => self.nest(offset, Header::Array(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_seq(access)
+            }),
+
+            header @ (Header::Bytes(len) | Header::Text(len))
+                if !matches!(header, Header::Text(..)) || self.options.coerce_strings =>
+            {
+                self.allocate(offset)?;
+                let mut buffer = Vec::new();
+
+                let mut segments = match header {
+                    Header::Text(..) => self.decoder.text_bytes(len),
+                    _ => self.decoder.bytes(len),
+                };
+                while let Some(mut segment) = segments.pull()? {
+                    while let Some(chunk) = segment.pull(self.scratch)? {
+                        buffer.extend_from_slice(chunk);
                     }
-
-                    visitor.visit_seq(BytesAccess::<R>(0, buffer, core::marker::PhantomData))
                 }
 
-                header => Err(header.expected("array")),
-            };
+                visitor.visit_seq(BytesAccess::<R>(0, buffer, core::marker::PhantomData))
+            }
+
+            header => Err(header.expected("array")),
         }
     }
 
     fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-
-                Header::Map(len) => self.recurse(|me| {
-                    let access = Access(me, len);
-                    visitor.visit_map(access)
-                }),
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Map(len) => self.nest(offset, Header::Map(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_map(access)
+            }),
 
-                header => Err(header.expected("map")),
-            };
+            header => Err(header.expected("map")),
         }
     }
 
@@ -487,7 +1539,21 @@ where
         _fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
-        self.deserialize_map(visitor)
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Map(len) => self.nest(offset, Header::Map(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_map(access)
+            }),
+
+            // Structs encoded as arrays hold their fields by position.
+            Header::Array(len) => self.nest(offset, Header::Array(len), |me| {
+                let access = Access(me, len, Vec::new());
+                visitor.visit_seq(access)
+            }),
+
+            header => Err(header.expected("map")),
+        }
     }
 
     fn deserialize_tuple<V: de::Visitor<'de>>(
@@ -511,27 +1577,25 @@ where
         self,
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
-        loop {
-            let offset = self.decoder.offset();
-
-            return match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-
-                Header::Text(Some(len)) if len <= self.scratch.len() => {
-                    self.decoder.read_exact(&mut self.scratch[..len])?;
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            header @ Header::Text(Some(len)) => {
+                let options = self.options;
+                let visit = |bytes: &[u8]| visitor.visit_str(&text(bytes, options, offset)?);
 
-                    match core::str::from_utf8(&self.scratch[..len]) {
-                        Ok(s) => visitor.visit_str(s),
-                        Err(..) => Err(Error::Syntax(offset)),
-                    }
+                match self.read_bytes(len, visit)? {
+                    Some(result) => result,
+                    None => Err(header.expected("str or bytes")),
                 }
-                Header::Bytes(Some(len)) if len <= self.scratch.len() => {
-                    self.decoder.read_exact(&mut self.scratch[..len])?;
-                    visitor.visit_bytes(&self.scratch[..len])
+            }
+            header @ Header::Bytes(Some(len)) => {
+                match self.read_bytes(len, |bytes| visitor.visit_bytes(bytes))? {
+                    Some(result) => result,
+                    None => Err(header.expected("str or bytes")),
                 }
+            }
 
-                header => Err(header.expected("str or bytes")),
-            };
+            header => Err(header.expected("str or bytes")),
         }
     }
 
@@ -539,16 +1603,18 @@ where
         self,
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
-        self.deserialize_any(visitor)
+        self.skip_item()?;
+        visitor.visit_unit()
     }
 
     #[inline]
     fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        match self.decoder.pull()? {
+        match self.pull()? {
             Header::Simple(simple::UNDEFINED) => visitor.visit_none(),
             Header::Simple(simple::NULL) => visitor.visit_none(),
+            Header::Simple(x) if self.unknown_simple(x) => visitor.visit_none(),
             header => {
-                self.decoder.push(header);
+                self.push(header);
                 visitor.visit_some(self)
             }
         }
@@ -556,13 +1622,12 @@ where
 
     #[inline]
     fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
-        loop {
-            return match self.decoder.pull()? {
-                Header::Simple(simple::UNDEFINED) => visitor.visit_unit(),
-                Header::Simple(simple::NULL) => visitor.visit_unit(),
-                Header::Tag(..) => continue,
-                header => Err(header.expected("unit")),
-            };
+        let (_, header) = self.pull_untagged()?;
+        match header {
+            Header::Simple(simple::UNDEFINED) => visitor.visit_unit(),
+            Header::Simple(simple::NULL) => visitor.visit_unit(),
+            Header::Simple(x) if self.unknown_simple(x) => visitor.visit_unit(),
+            header => Err(header.expected("unit")),
         }
     }
 
@@ -578,10 +1643,19 @@ where
     #[inline]
     fn deserialize_newtype_struct<V: de::Visitor<'de>>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
-        visitor.visit_newtype_struct(self)
+        if name != "@@STREAM@@" {
+            return visitor.visit_newtype_struct(self);
+        }
+
+        let (offset, header) = self.pull_untagged()?;
+        match header {
+            Header::Bytes(len) => visitor.visit_seq(Chunks::new(self, false, offset, len)),
+            Header::Text(len) => visitor.visit_seq(Chunks::new(self, true, offset, len)),
+            header => Err(header.expected("bytes")),
+        }
     }
 
     #[inline]
@@ -592,33 +1666,36 @@ where
         visitor: V,
     ) -> Result<V::Value, Self::Error> {
         if name == "@@TAG@@" {
-            let tag = match self.decoder.pull()? {
+            let offset = self.decoder.offset();
+            let header = self.pull()?;
+            let tag = match header {
                 Header::Tag(x) => Some(x),
                 header => {
-                    self.decoder.push(header);
+                    self.push(header);
                     None
                 }
             };
 
-            return self.recurse(|me| {
+            return self.nest(offset, header, |me| {
                 let access = TagAccess::new(me, tag);
                 visitor.visit_enum(access)
             });
         }
 
-        loop {
-            match self.decoder.pull()? {
-                Header::Tag(..) => continue,
-                Header::Map(Some(1)) => (),
-                header @ Header::Text(..) => self.decoder.push(header),
-                header => return Err(header.expected("enum")),
+        let (offset, header) = self.pull_untagged()?;
+        let header = match header {
+            header @ Header::Map(Some(1)) => header,
+            header @ (Header::Text(..) | Header::Positive(..)) => {
+                self.push(header);
+                header
             }
+            header => return Err(header.expected("enum")),
+        };
 
-            return self.recurse(|me| {
-                let access = Access(me, Some(0));
-                visitor.visit_enum(access)
-            });
-        }
+        self.nest(offset, header, |me| {
+            let access = Access(me, Some(0), Vec::new());
+            visitor.visit_enum(access)
+        })
     }
 
     #[inline]
@@ -627,9 +1704,12 @@ where
     }
 }
 
-struct Access<'a, 'b, R>(&'a mut Deserializer<'b, R>, Option<usize>);
+/// Access to the items of an array or map, with how many are left
+///
+/// For a map whose key order is checked, this also holds the previous key.
+struct Access<'a, 'b, R, B>(&'a mut Deserializer<'b, R, B>, Option<u64>, Vec<u8>);
 
-impl<'de, 'a, 'b, R: Read> de::SeqAccess<'de> for Access<'a, 'b, R>
+impl<'de, 'a, 'b, R: Read, B: Borrow<'de, R>> de::SeqAccess<'de> for Access<'a, 'b, R, B>
 where
     R::Error: core::fmt::Debug,
 {
@@ -643,9 +1723,9 @@ where
         match self.1 {
             Some(0) => return Ok(None),
             Some(x) => self.1 = Some(x - 1),
-            None => match self.0.decoder.pull()? {
+            None => match self.0.pull()? {
                 Header::Break => return Ok(None),
-                header => self.0.decoder.push(header),
+                header => self.0.push(header),
             },
         }
 
@@ -655,10 +1735,12 @@ where
     #[inline]
     fn size_hint(&self) -> Option<usize> {
         self.1
+            .map(|len| usize::try_from(len).unwrap_or(usize::MAX))
+            .map(|len| len.min(self.0.options.max_size_hint))
     }
 }
 
-impl<'de, 'a, 'b, R: Read> de::MapAccess<'de> for Access<'a, 'b, R>
+impl<'de, 'a, 'b, R: Read, B: Borrow<'de, R>> de::MapAccess<'de> for Access<'a, 'b, R, B>
 where
     R::Error: core::fmt::Debug,
 {
@@ -672,13 +1754,29 @@ where
         match self.1 {
             Some(0) => return Ok(None),
             Some(x) => self.1 = Some(x - 1),
-            None => match self.0.decoder.pull()? {
+            None => match self.0.pull()? {
                 Header::Break => return Ok(None),
-                header => self.0.decoder.push(header),
+                header => self.0.push(header),
             },
         }
 
-        seed.deserialize(&mut *self.0).map(Some)
+        let order = match self.0.options.key_order {
+            Some(order) => order,
+            None => return seed.deserialize(&mut *self.0).map(Some),
+        };
+
+        let offset = self.0.decoder.offset();
+        let mut key = Vec::new();
+        self.0.capture(&mut key)?;
+
+        // The first key is compared with no bytes at all, which sort first.
+        if order.cmp_encoded(&self.2, &key) != Ordering::Less {
+            return Err(Error::semantic(offset, "map key out of order"));
+        }
+
+        let value = self.0.copied(&key, offset, seed)?;
+        self.2 = key;
+        Ok(Some(value))
     }
 
     #[inline]
@@ -692,10 +1790,12 @@ where
     #[inline]
     fn size_hint(&self) -> Option<usize> {
         self.1
+            .map(|len| usize::try_from(len).unwrap_or(usize::MAX))
+            .map(|len| len.min(self.0.options.max_size_hint))
     }
 }
 
-impl<'de, 'a, 'b, R: Read> de::EnumAccess<'de> for Access<'a, 'b, R>
+impl<'de, 'a, 'b, R: Read, B: Borrow<'de, R>> de::EnumAccess<'de> for Access<'a, 'b, R, B>
 where
     R::Error: core::fmt::Debug,
 {
@@ -707,12 +1807,22 @@ where
         self,
         seed: V,
     ) -> Result<(V::Value, Self::Variant), Self::Error> {
-        let variant = seed.deserialize(&mut *self.0)?;
+        // Variants may be keyed by index, unlike struct fields.
+        let variant = match self.0.pull_untagged()? {
+            (_, Header::Positive(index)) => {
+                seed.deserialize(IntoDeserializer::<Self::Error>::into_deserializer(index))?
+            }
+            (_, header) => {
+                self.0.push(header);
+                seed.deserialize(&mut *self.0)?
+            }
+        };
+
         Ok((variant, self))
     }
 }
 
-impl<'de, 'a, 'b, R: Read> de::VariantAccess<'de> for Access<'a, 'b, R>
+impl<'de, 'a, 'b, R: Read, B: Borrow<'de, R>> de::VariantAccess<'de> for Access<'a, 'b, R, B>
 where
     R::Error: core::fmt::Debug,
 {
@@ -780,12 +1890,108 @@ where
     }
 }
 
+/// Hands out a byte or text string one scratch buffer at a time
+///
+/// Text is validated as it is read; a character split between two chunks
+/// is held back in the scratch buffer until the rest of it arrives.
+struct Chunks<'a, 'b, R, B> {
+    de: &'a mut Deserializer<'b, R, B>,
+    text: bool,
+    segmented: bool,
+    offset: u64,
+    unread: u64,
+    saved: usize,
+}
+
+impl<'a, 'b, R, B> Chunks<'a, 'b, R, B> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<'b, R, B>, text: bool, offset: u64, len: Option<u64>) -> Self {
+        Self {
+            de,
+            text,
+            segmented: len.is_none(),
+            offset,
+            unread: len.unwrap_or(0),
+            saved: 0,
+        }
+    }
+}
+
+impl<'de, 'a, 'b, R: Read, B> de::SeqAccess<'de> for Chunks<'a, 'b, R, B>
+where
+    R::Error: core::fmt::Debug,
+{
+    type Error = Error<R::Error>;
+
+    fn next_element_seed<U: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: U,
+    ) -> Result<Option<U::Value>, Self::Error> {
+        use de::value::StrDeserializer;
+
+        while self.unread == 0 {
+            // Each segment of a text string must be valid on its own.
+            if self.saved > 0 {
+                return Err(Error::Syntax(self.offset));
+            }
+
+            if !self.segmented {
+                return Ok(None);
+            }
+
+            self.offset = self.de.decoder.offset();
+            match self.de.decoder.pull()? {
+                Header::Break => {
+                    self.segmented = false;
+                    return Ok(None);
+                }
+
+                Header::Bytes(Some(len)) if !self.text => self.unread = len,
+                Header::Text(Some(len)) if self.text => self.unread = len,
+                _ => return Err(Error::Syntax(self.offset)),
+            }
+        }
+
+        let size = (self.de.scratch.len() as u64).min(self.saved as u64 + self.unread) as usize;
+        if size <= self.saved {
+            return Err(Error::semantic(self.offset, "scratch buffer too small"));
+        }
+
+        let chunk = &mut self.de.scratch[..size];
+        self.de.decoder.read_exact(&mut chunk[self.saved..])?;
+        self.unread -= (size - self.saved) as u64;
+
+        if !self.text {
+            self.saved = 0;
+            return seed.deserialize(BytesDeserializer::new(chunk)).map(Some);
+        }
+
+        let valid = match core::str::from_utf8(chunk) {
+            Ok(..) => size,
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(..) => return Err(Error::Syntax(self.offset)),
+        };
+
+        let (text, rest) = chunk.split_at(valid);
+        let text = core::str::from_utf8(text).unwrap();
+        let value = seed.deserialize(StrDeserializer::<Self::Error>::new(text))?;
+
+        self.saved = rest.len();
+        chunk.copy_within(valid.., 0);
+        Ok(Some(value))
+    }
+}
+
 /// Deserializes as CBOR from a type with [`impl
 /// ciborium_io::Read`](ciborium_io::Read) using a 4KB buffer on the stack.
 ///
 /// If you want to deserialize faster at the cost of more memory, consider using
 /// [`from_reader_with_buffer`](from_reader_with_buffer) with a larger buffer,
 /// for example 64KB.
+///
+/// Readers implementing `std::io::BufRead` should be wrapped in
+/// [`ciborium_io::Buffered`] so that headers can be decoded directly out of
+/// their buffer instead of through many small reads.
 #[inline]
 pub fn from_reader<T: de::DeserializeOwned, R: Read>(reader: R) -> Result<T, Error<R::Error>>
 where
@@ -806,15 +2012,215 @@ pub fn from_reader_with_buffer<T: de::DeserializeOwned, R: Read>(
 where
     R::Error: core::fmt::Debug,
 {
-    let mut reader = Deserializer {
-        decoder: reader.into(),
-        scratch: scratch_buffer,
-        recurse: 256,
-    };
-
+    let mut reader = Deserializer::new(reader, scratch_buffer, Options::new());
     T::deserialize(&mut reader)
 }
 
+/// Deserializes as CBOR from a type with [`impl
+/// ciborium_io::Read`](ciborium_io::Read) using a
+/// [`DeserializeSeed`](de::DeserializeSeed) and a 4KB buffer on the stack.
+///
+/// This allows stateful deserialization (for example, into an arena or
+/// using an interner) which is not possible with [`from_reader`].
+#[inline]
+pub fn from_reader_seed<'de, S: de::DeserializeSeed<'de>, R: Read>(
+    reader: R,
+    seed: S,
+) -> Result<S::Value, Error<R::Error>>
+where
+    R::Error: core::fmt::Debug,
+{
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::new(reader, &mut scratch, Options::new());
+    seed.deserialize(&mut reader)
+}
+
+/// Deserializes as CBOR from a byte slice
+///
+/// Strings and byte strings are borrowed from the slice, so `T` may contain
+/// `&str` and `&[u8]` fields (the latter through `serde_bytes` or similar).
+#[inline]
+pub fn from_slice<'de, T: de::Deserialize<'de>>(
+    slice: &'de [u8],
+) -> Result<T, Error<<&'de [u8] as Read>::Error>> {
+    from_slice_seed(slice, core::marker::PhantomData)
+}
+
+/// Deserializes as CBOR from a byte slice using the specified [`Options`]
+///
+/// Combined with [`Options::allocate`], this deserializes without touching
+/// the heap: strings and byte strings are borrowed from the slice and
+/// inputs which would need an allocation are rejected. A 4KB buffer on the
+/// stack is used as scratch space for segmented strings; the
+/// [`Options::scratch_size`] setting is ignored.
+///
+/// ```
+/// use ciborium::de::{from_slice_with_options, Options};
+///
+/// let options = Options::new().allocate(false);
+///
+/// // A definite-length string is borrowed...
+/// let text: &str = from_slice_with_options(&[0x62, 0x68, 0x69], options).unwrap();
+/// assert_eq!(text, "hi");
+///
+/// // ...but an indefinite-length string would have to be collected.
+/// let result = from_slice_with_options::<String>(&[0x7f, 0x62, 0x68, 0x69, 0xff], options);
+/// assert!(result.is_err());
+/// ```
+#[inline]
+pub fn from_slice_with_options<'de, T: de::Deserialize<'de>>(
+    slice: &'de [u8],
+    options: Options,
+) -> Result<T, Error<<&'de [u8] as Read>::Error>> {
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::from_slice(slice, &mut scratch, options);
+    T::deserialize(&mut reader).map_err(|e| reader.attach(e))
+}
+
+/// Deserializes as CBOR from a byte slice holding exactly one item
+///
+/// Unlike [`from_slice`], which ignores anything after the item, this fails
+/// if any bytes remain. Inputs which are checked as a whole, such as signed
+/// payloads, should be decoded this way so that they cannot carry extra
+/// data.
+///
+/// ```
+/// use ciborium::de::{from_slice_exact, Error};
+///
+/// let value: u8 = from_slice_exact(&[0x01]).unwrap();
+/// assert_eq!(value, 1);
+///
+/// let result = from_slice_exact::<u8>(&[0x01, 0x02]);
+/// assert!(matches!(result, Err(Error::Semantic(Some(1), _))));
+/// ```
+#[inline]
+pub fn from_slice_exact<'de, T: de::Deserialize<'de>>(
+    slice: &'de [u8],
+) -> Result<T, Error<<&'de [u8] as Read>::Error>> {
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::from_slice(slice, &mut scratch, Options::new());
+    let value = T::deserialize(&mut reader)?;
+
+    match reader.finish()? {
+        (slice, _) if slice.rest().is_empty() => Ok(value),
+        (_, offset) => Err(Error::semantic(offset, "trailing data")),
+    }
+}
+
+/// Deserializes as CBOR from a string of hex digits holding exactly one item
+///
+/// Digits may be in either case. Invalid digits are reported as syntax
+/// errors at the offset of the byte they would have encoded, as with any
+/// other malformed input.
+///
+/// ```
+/// use ciborium::de::{from_hex_str, Error};
+///
+/// let value: (u8, String) = from_hex_str("82016161").unwrap();
+/// assert_eq!(value, (1, "a".into()));
+///
+/// let result = from_hex_str::<(u8, String)>("8201616x");
+/// assert!(matches!(result, Err(Error::Syntax(3))));
+/// ```
+#[cfg(feature = "hex")]
+pub fn from_hex_str<T: de::DeserializeOwned>(
+    digits: &str,
+) -> Result<T, Error<<&'static [u8] as Read>::Error>> {
+    let bytes = hex::decode(digits).map_err(|e| match e {
+        hex::FromHexError::InvalidHexCharacter { index, .. } => Error::Syntax(index as u64 / 2),
+        _ => Error::Syntax(digits.len() as u64 / 2),
+    })?;
+
+    from_slice_exact(&bytes)
+}
+
+/// Deserializes as CBOR from a reader holding exactly one item
+///
+/// Unlike [`from_reader`], which stops reading after the item, this reads
+/// on to the end of the input and fails if it finds any more bytes.
+#[cfg(feature = "std")]
+#[inline]
+pub fn from_reader_exact<T: de::DeserializeOwned, R: std::io::Read>(
+    reader: R,
+) -> Result<T, Error<std::io::Error>> {
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::new(reader, &mut scratch, Options::new());
+    let value = T::deserialize(&mut reader)?;
+
+    let (mut reader, offset) = reader.finish()?;
+    loop {
+        return match std::io::Read::read(&mut reader, &mut [0]) {
+            Ok(0) => Ok(value),
+            Ok(..) => Err(Error::semantic(offset, "trailing data")),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(Error::Io(e)),
+        };
+    }
+}
+
+/// Deserializes a single item out of a larger CBOR input
+///
+/// The item is addressed by a JSON Pointer ([RFC 6901]). Each token selects
+/// either the value of a map entry whose text or integer key matches it, or
+/// an array element by index. Tags along the way are ignored. All other
+/// items are skipped at the decoder level without being deserialized.
+///
+/// Indices and integer keys are written in decimal without leading zeros or
+/// a `+`, so `/01` selects nothing. Returns `None` if no item exists at the
+/// given path.
+///
+/// ```
+/// use ciborium::cbor;
+///
+/// let value = cbor!({ "header" => { "alg" => -7 }, "payload" => [1, 2, 3] }).unwrap();
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&value, &mut bytes).unwrap();
+///
+/// let alg: Option<i32> = ciborium::extract(&bytes[..], "/header/alg").unwrap();
+/// assert_eq!(alg, Some(-7));
+///
+/// let second: Option<u8> = ciborium::extract(&bytes[..], "/payload/1").unwrap();
+/// assert_eq!(second, Some(2));
+///
+/// let missing: Option<u8> = ciborium::extract(&bytes[..], "/header/kid").unwrap();
+/// assert_eq!(missing, None);
+/// ```
+///
+/// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+#[inline]
+pub fn extract<T: de::DeserializeOwned, R: Read>(
+    reader: R,
+    pointer: &str,
+) -> Result<Option<T>, Error<R::Error>>
+where
+    R::Error: core::fmt::Debug,
+{
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::new(reader, &mut scratch, Options::new());
+
+    match reader.seek(pointer)? {
+        true => T::deserialize(&mut reader).map(Some),
+        false => Ok(None),
+    }
+}
+
+/// Deserializes as CBOR from a byte slice using a
+/// [`DeserializeSeed`](de::DeserializeSeed).
+///
+/// See [`from_reader_seed`] for details.
+#[inline]
+pub fn from_slice_seed<'de, S: de::DeserializeSeed<'de>>(
+    slice: &'de [u8],
+    seed: S,
+) -> Result<S::Value, Error<<&'de [u8] as Read>::Error>>
+where
+    <&'de [u8] as Read>::Error: core::fmt::Debug,
+{
+    let mut scratch = [0; 4096];
+    let mut reader = Deserializer::from_slice(slice, &mut scratch, Options::new());
+    seed.deserialize(&mut reader)
+}
+
 /// Deserializes as CBOR from a type with [`impl ciborium_io::Read`](ciborium_io::Read), with
 /// a specified maximum recursion limit.  Inputs that are nested beyond the specified limit
 /// will result in [`Error::RecursionLimitExceeded`] .
@@ -828,15 +2234,52 @@ pub fn from_reader_with_recursion_limit<T: de::DeserializeOwned, R: Read>(
 where
     R::Error: core::fmt::Debug,
 {
-    let mut scratch = [0; 4096];
+    let options = Options::new().recursion_limit(recurse_limit);
+    from_reader_with_options(reader, options)
+}
+
+/// Deserializes as CBOR from a type with [`impl
+/// ciborium_io::Read`](ciborium_io::Read) using the specified [`Options`].
+///
+/// Unless [`Options::scratch_size`] is set, a 4KB buffer on the stack is used
+/// as temporary scratch space.
+#[inline]
+pub fn from_reader_with_options<T: de::DeserializeOwned, R: Read>(
+    reader: R,
+    options: Options,
+) -> Result<T, Error<R::Error>>
+where
+    R::Error: core::fmt::Debug,
+{
+    match options.scratch_size {
+        Some(size) => {
+            let mut scratch = alloc::vec![0; size];
+            from_reader_with_buffer_and_options(reader, &mut scratch, options)
+        }
 
-    let mut reader = Deserializer {
-        decoder: reader.into(),
-        scratch: &mut scratch,
-        recurse: recurse_limit,
-    };
+        None => {
+            let mut scratch = [0; 4096];
+            from_reader_with_buffer_and_options(reader, &mut scratch, options)
+        }
+    }
+}
 
-    T::deserialize(&mut reader)
+/// Deserializes as CBOR from a type with [`impl
+/// ciborium_io::Read`](ciborium_io::Read) using the specified [`Options`] and
+/// a caller-specific buffer as a temporary scratch space.
+///
+/// The [`Options::scratch_size`] setting is ignored.
+#[inline]
+pub fn from_reader_with_buffer_and_options<T: de::DeserializeOwned, R: Read>(
+    reader: R,
+    scratch_buffer: &mut [u8],
+    options: Options,
+) -> Result<T, Error<R::Error>>
+where
+    R::Error: core::fmt::Debug,
+{
+    let mut reader = Deserializer::new(reader, scratch_buffer, options);
+    T::deserialize(&mut reader).map_err(|e| reader.attach(e))
 }
 
 /// Returns a deserializer with a specified scratch buffer
@@ -848,11 +2291,7 @@ pub fn deserializer_from_reader_with_buffer<R: Read>(
 where
     R::Error: core::fmt::Debug,
 {
-    Deserializer {
-        decoder: reader.into(),
-        scratch: scratch_buffer,
-        recurse: 256,
-    }
+    Deserializer::new(reader, scratch_buffer, Options::new())
 }
 
 /// Returns a deserializer with a specified scratch buffer
@@ -869,9 +2308,19 @@ pub fn deserializer_from_reader_with_buffer_and_recursion_limit<R: Read>(
 where
     R::Error: core::fmt::Debug,
 {
-    Deserializer {
-        decoder: reader.into(),
-        scratch: scratch_buffer,
-        recurse: recurse_limit,
-    }
+    let options = Options::new().recursion_limit(recurse_limit);
+    Deserializer::new(reader, scratch_buffer, options)
+}
+
+/// Returns a deserializer with a specified scratch buffer and [`Options`]
+#[inline]
+pub fn deserializer_from_reader_with_buffer_and_options<R: Read>(
+    reader: R,
+    scratch_buffer: &mut [u8],
+    options: Options,
+) -> Deserializer<'_, R>
+where
+    R::Error: core::fmt::Debug,
+{
+    Deserializer::new(reader, scratch_buffer, options)
 }
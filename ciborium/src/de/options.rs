@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::value::KeyOrder;
+
+/// Options which control how CBOR is deserialized
+///
+/// ```
+/// use ciborium::de::{from_reader_with_options, Options};
+///
+/// let options = Options::new().recursion_limit(16).max_size_hint(64);
+/// let value: Vec<u8> = from_reader_with_options(&[0x82, 0x01, 0x02][..], options).unwrap();
+/// assert_eq!(value, [1, 2]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub(crate) recursion_limit: usize,
+    pub(crate) max_size_hint: usize,
+    pub(crate) scratch_size: Option<usize>,
+    pub(crate) allocate: bool,
+    pub(crate) non_finite: bool,
+    pub(crate) floats: bool,
+    pub(crate) tag_limit: usize,
+    pub(crate) skip_tags: bool,
+    pub(crate) untagged_enums: bool,
+    pub(crate) unknown_simple_as_null: bool,
+    pub(crate) lossy_utf8: bool,
+    pub(crate) coerce_strings: bool,
+    pub(crate) coerce_numbers: bool,
+    pub(crate) error_context: bool,
+    pub(crate) key_order: Option<KeyOrder>,
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Options {
+    /// Returns the default options
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            recursion_limit: 256,
+            max_size_hint: 1024,
+            scratch_size: None,
+            allocate: true,
+            non_finite: true,
+            floats: true,
+            tag_limit: 256,
+            skip_tags: true,
+            untagged_enums: false,
+            unknown_simple_as_null: false,
+            lossy_utf8: false,
+            coerce_strings: false,
+            coerce_numbers: false,
+            error_context: false,
+            key_order: None,
+        }
+    }
+
+    /// Sets the maximum recursion limit
+    ///
+    /// Inputs that are nested beyond the specified limit will result in
+    /// [`Error::RecursionLimitExceeded`](super::Error::RecursionLimitExceeded).
+    /// Set a high recursion limit at your own risk (of stack exhaustion)!
+    ///
+    /// The default is 256.
+    #[inline]
+    pub const fn recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Sets the largest size hint reported for arrays and maps
+    ///
+    /// The length of an array or map is taken from its header, which is
+    /// controlled by the sender. A header may claim far more items than the
+    /// input actually contains. Since collections commonly preallocate from
+    /// the size hint, the hint is clamped to this bound. Larger collections
+    /// still decode; they simply grow as items arrive.
+    ///
+    /// The default is 1024.
+    #[inline]
+    pub const fn max_size_hint(mut self, max: usize) -> Self {
+        self.max_size_hint = max;
+        self
+    }
+
+    /// Sets the size of the scratch buffer used by
+    /// [`from_reader_with_options`](super::from_reader_with_options)
+    ///
+    /// Definite-length strings and byte strings no larger than the scratch
+    /// buffer are passed to the visitor without allocating; longer ones are
+    /// collected into an allocation first. When set, the scratch buffer is
+    /// allocated on the heap rather than the stack. This keeps large buffers
+    /// off the stack and lets callers with constrained stacks avoid the
+    /// default buffer entirely.
+    ///
+    /// By default, a 4KB buffer on the stack is used.
+    #[inline]
+    pub const fn scratch_size(mut self, size: usize) -> Self {
+        self.scratch_size = Some(size);
+        self
+    }
+
+    /// Sets whether the deserializer may allocate
+    ///
+    /// Some inputs can only be deserialized by first collecting them into a
+    /// heap allocation: segmented (indefinite-length) strings and byte
+    /// strings, strings longer than the scratch buffer, byte strings visited
+    /// as sequences and bignums wider than 128 bits. When allocation is
+    /// disabled, these inputs fail to deserialize instead.
+    ///
+    /// Together with [`from_slice_with_options`](super::from_slice_with_options),
+    /// this guarantees that the deserializer itself never touches the heap
+    /// when deserializing into types which borrow from the input or have a
+    /// fixed size.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub const fn allocate(mut self, enabled: bool) -> Self {
+        self.allocate = enabled;
+        self
+    }
+
+    /// Sets whether NaN and infinite floats are accepted
+    ///
+    /// Deterministic profiles such as dCBOR, and applications which must
+    /// never admit non-finite numbers, can disable this to reject such floats
+    /// with a semantic error wherever they appear in the input, including
+    /// inside a [`Value`](crate::value::Value).
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub const fn non_finite_floats(mut self, enabled: bool) -> Self {
+        self.non_finite = enabled;
+        self
+    }
+
+    /// Sets whether floats are accepted at all
+    ///
+    /// Constrained protocols which only carry integers can disable this, so
+    /// that any half, single or double precision float in the input is
+    /// rejected with a semantic error before it reaches a visitor, even when
+    /// deserializing float or dynamic types such as
+    /// [`Value`](crate::value::Value). Skipped items are not inspected.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub const fn floats(mut self, enabled: bool) -> Self {
+        self.floats = enabled;
+        self
+    }
+
+    /// Sets the maximum number of tags on a single item
+    ///
+    /// Tags which a type does not capture are skipped, and CBOR places no
+    /// bound on how many of them may precede an item. Once more than this
+    /// many consecutive tags are skipped, deserialization fails with a
+    /// semantic error instead of consuming an arbitrarily long tag chain.
+    /// Tags which are captured, such as by [`Value`](crate::value::Value),
+    /// nest and so count against the
+    /// [`recursion_limit`](Self::recursion_limit) instead.
+    ///
+    /// The default is 256.
+    #[inline]
+    pub const fn tag_limit(mut self, limit: usize) -> Self {
+        self.tag_limit = limit;
+        self
+    }
+
+    /// Sets whether tags which a type does not capture are skipped
+    ///
+    /// Most types have no notion of tags, so by default any tags on the
+    /// items they are deserialized from are skipped. Protocols which assign
+    /// meaning to tags can disable this, so that such tags fail with a
+    /// semantic error naming the tag instead of being silently dropped.
+    /// Tags are still accepted where they are captured, such as by
+    /// [`Value`](crate::value::Value) and the wrappers in
+    /// [`tag`](crate::tag), and on bignums deserialized as integers. Items
+    /// skipped as a whole, such as unknown fields, are not inspected.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub const fn skip_tags(mut self, enabled: bool) -> Self {
+        self.skip_tags = enabled;
+        self
+    }
+
+    /// Sets whether self-describing input is limited to what serde can buffer
+    ///
+    /// Serde buffers the input of `#[serde(untagged)]` and
+    /// `#[serde(tag = "...")]` enums, and of structs with `#[serde(flatten)]`
+    /// fields, before deciding how to deserialize it. Its buffer has no
+    /// representation for tags or 128-bit integers, so by default such input
+    /// fails to deserialize into these types. When this is enabled, items
+    /// deserialized without a type hint have their tags handled as by
+    /// [`skip_tags`](Self::skip_tags), and bignums which fit in 64 bits are
+    /// visited as 64-bit integers.
+    ///
+    /// This applies to every item deserialized without a type hint, so a
+    /// [`Value`](crate::value::Value) deserialized with this enabled loses
+    /// its tags.
+    ///
+    /// ```
+    /// use ciborium::de::{from_reader_with_options, Options};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// #[serde(untagged)]
+    /// enum Id {
+    ///     Number(u64),
+    ///     Name(String),
+    /// }
+    ///
+    /// // 1(5), a number with an epoch time tag
+    /// let bytes = [0xc1, 0x05];
+    /// assert!(ciborium::from_reader::<Id, _>(&bytes[..]).is_err());
+    ///
+    /// let options = Options::new().untagged_enums(true);
+    /// let id: Id = from_reader_with_options(&bytes[..], options).unwrap();
+    /// assert_eq!(id, Id::Number(5));
+    /// ```
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn untagged_enums(mut self, enabled: bool) -> Self {
+        self.untagged_enums = enabled;
+        self
+    }
+
+    /// Sets whether unassigned simple values are read as null
+    ///
+    /// Only `false`, `true`, `null` and `undefined` are assigned in the
+    /// simple values registry, and any other simple value fails to
+    /// deserialize. Consumers which should not break when new values are
+    /// registered can enable this, so that other simple values deserialize
+    /// as `null`: into [`Value::Null`](crate::value::Value::Null), `None`
+    /// or `()`.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn unknown_simple_as_null(mut self, enabled: bool) -> Self {
+        self.unknown_simple_as_null = enabled;
+        self
+    }
+
+    /// Sets whether text with invalid UTF-8 is accepted
+    ///
+    /// Text strings must be valid UTF-8, and by default any which are not
+    /// fail with [`Error::Syntax`](super::Error::Syntax). Consumers of
+    /// producers known to emit slightly broken text can enable this, so that
+    /// invalid sequences are replaced with U+FFFD REPLACEMENT CHARACTER as by
+    /// [`String::from_utf8_lossy()`]. Replacing requires an allocation, so
+    /// it fails with a semantic error when [`allocate`](Self::allocate) is
+    /// disabled. Characters deserialized as `char` are never replaced.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn lossy_utf8(mut self, enabled: bool) -> Self {
+        self.lossy_utf8 = enabled;
+        self
+    }
+
+    /// Sets whether text and byte strings may stand in for each other
+    ///
+    /// Peers do not always agree on whether a field is a text string or a
+    /// byte string. When this is enabled, a text string is accepted where
+    /// bytes are expected, such as by `Vec<u8>` or `serde_bytes::ByteBuf`, and a byte
+    /// string holding valid UTF-8 is accepted where text is expected, such
+    /// as by `String`. Dynamic types such as [`Value`](crate::value::Value)
+    /// still see the string type on the wire.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn coerce_strings(mut self, enabled: bool) -> Self {
+        self.coerce_strings = enabled;
+        self
+    }
+
+    /// Sets whether integers and floats may stand in for each other
+    ///
+    /// Data transcoded from JSON often carries `1.0` where an integer is
+    /// expected, or `1` where a float is. When this is enabled, an integer
+    /// is accepted where a float is expected if it converts exactly, and an
+    /// integral float is accepted where an integer is expected if it fits
+    /// the target type. Anything else fails with a semantic error. Dynamic
+    /// types such as [`Value`](crate::value::Value) still see the number
+    /// type on the wire.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn coerce_numbers(mut self, enabled: bool) -> Self {
+        self.coerce_numbers = enabled;
+        self
+    }
+
+    /// Sets whether errors record where in the input they occurred
+    ///
+    /// Errors from decoding input produced by a peer which cannot be
+    /// inspected are hard to place from an offset alone. When this is
+    /// enabled, errors are wrapped in
+    /// [`Error::Context`](super::Error::Context) along with the raw bytes
+    /// around the failure and the arrays, maps and tags enclosing it. An
+    /// error outside of any of these only has its context attached by
+    /// [`from_slice_with_options`](super::from_slice_with_options) and
+    /// [`from_reader_with_options`](super::from_reader_with_options).
+    /// The context is only written out when the error is formatted with
+    /// `{:#}`.
+    ///
+    /// ```
+    /// use ciborium::de::{from_slice_with_options, Options};
+    ///
+    /// // [1, [2, "x"]]
+    /// let bytes = [0x82, 0x01, 0x82, 0x02, 0x61, 0x78];
+    /// let options = Options::new().error_context(true);
+    ///
+    /// let error = from_slice_with_options::<(u8, (u8, u8))>(&bytes, options).unwrap_err();
+    /// let context = error.context().unwrap();
+    /// assert_eq!(context.stack.len(), 2);
+    /// assert_eq!(context.stack[1].offset, 2);
+    /// ```
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn error_context(mut self, enabled: bool) -> Self {
+        self.error_context = enabled;
+        self
+    }
+
+    /// Requires the keys of every map to be in the given order
+    ///
+    /// Protocols which hash or sign their input, or which must agree on it
+    /// byte for byte, only accept one encoding of each map. When this is
+    /// set, each key of a map being deserialized must sort strictly after
+    /// the one before it, or deserialization fails; this also rejects
+    /// repeated keys. Keys are compared as encoded with each header in its
+    /// shortest form.
+    ///
+    /// To be compared, each key is copied out of the input before it is
+    /// deserialized, so keys cannot borrow from the input.
+    ///
+    /// ```
+    /// use ciborium::de::{from_slice_with_options, Options};
+    /// use ciborium::value::{KeyOrder, Value};
+    ///
+    /// // {"a": 1, 1000: 2}
+    /// let bytes = [0xa2, 0x61, b'a', 0x01, 0x19, 0x03, 0xe8, 0x02];
+    ///
+    /// let options = Options::new().key_order(KeyOrder::LengthFirst);
+    /// let value: Value = from_slice_with_options(&bytes, options).unwrap();
+    /// assert_eq!(value.keys().count(), 2);
+    ///
+    /// let options = Options::new().key_order(KeyOrder::Lexical);
+    /// assert!(from_slice_with_options::<Value>(&bytes, options).is_err());
+    /// ```
+    ///
+    /// By default, keys may come in any order.
+    #[inline]
+    pub const fn key_order(mut self, order: KeyOrder) -> Self {
+        self.key_order = Some(order);
+        self
+    }
+}
@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Deserializer, Error, Options};
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Range;
+use std::io;
+
+use serde::de::DeserializeOwned;
+
+/// The self-describe tag (55799), which marks the start of an item
+const SELF_DESCRIBE: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+/// How many bytes are read from the reader at a time
+const CHUNK: usize = 4096;
+
+/// A stretch of a CBOR sequence skipped over by [`Recover`]
+#[derive(Debug)]
+pub struct Skipped {
+    /// The skipped bytes, as a range of offsets into the input
+    pub range: Range<u64>,
+
+    /// The error which caused the bytes to be skipped
+    ///
+    /// Its offset, if any, is relative to the start of the input.
+    pub error: Error<io::Error>,
+}
+
+/// An iterator over the items of a CBOR sequence which skips malformed items
+///
+/// Unlike decoding items one after another, iteration continues after an
+/// error. The bytes from the start of the failed item up to the next
+/// plausible item boundary are skipped and reported as a [`Skipped`] range.
+///
+/// By default, a boundary is the start of a self-describe tag
+/// (`0xd9d9f7`), which suits sequences whose writer prefixes every item
+/// with the tag as a sync marker. [`Recover::probe()`] also accepts offsets
+/// from which an item of type `T` deserializes.
+///
+/// ```
+/// use ciborium::de::Recover;
+/// use ciborium::Value;
+///
+/// // 55799(1), a damaged item, then 55799(2)
+/// let bytes = [0xd9, 0xd9, 0xf7, 0x01, 0x1c, 0xd9, 0xd9, 0xf7, 0x02];
+///
+/// let items: Vec<_> = Recover::<_, Value>::new(&bytes[..]).collect();
+/// assert_eq!(items.len(), 3);
+/// assert_eq!(items[1].as_ref().unwrap_err().range, 4..5);
+/// assert!(items[2].is_ok());
+/// ```
+///
+/// An error from the reader is reported as skipping the item being read,
+/// and ends the sequence.
+pub struct Recover<R, T> {
+    input: Input<R>,
+    probe: Option<usize>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<R: io::Read, T: DeserializeOwned> Recover<R, T> {
+    /// Creates an iterator over the items read from `reader`
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            input: Input {
+                reader,
+                buffer: Vec::new(),
+                offset: 0,
+                end: false,
+            },
+            probe: None,
+            item: PhantomData,
+        }
+    }
+
+    /// Also resumes where an item of type `T` deserializes
+    ///
+    /// After an error, each following offset is tried in turn, and decoding
+    /// resumes at the first from which an item of type `T` deserializes
+    /// within `window` bytes. The window bounds the work done at each
+    /// offset, so the scan is linear in the length of the damage; items
+    /// longer than the window are only found at self-describe tags.
+    ///
+    /// This is only a useful boundary when `T` is specific. A [`Value`]
+    /// deserializes from almost any byte, so with it, decoding resumes on
+    /// the byte after the damage starts, whatever follows.
+    ///
+    /// [`Value`]: crate::Value
+    #[inline]
+    pub fn probe(mut self, window: usize) -> Self {
+        self.probe = Some(window);
+        self
+    }
+
+    /// Deserializes the item `at` bytes into the buffer, returning it with
+    /// its length
+    ///
+    /// The item may extend at most `window` bytes.
+    fn decode(&mut self, at: usize, window: usize) -> Result<(T, usize), Error<io::Error>> {
+        let offset = self.input.offset + at as u64;
+        let reader = Window {
+            input: &mut self.input,
+            position: at,
+            end: at.saturating_add(window),
+        };
+
+        let mut scratch = [0; 4096];
+        let mut deserializer = Deserializer::new(reader, &mut scratch, Options::new());
+        let item = T::deserialize(&mut deserializer).map_err(|e| e.rebase(offset))?;
+        Ok((item, deserializer.into_inner().position - at))
+    }
+
+    /// Skips from the start of a failed item to the next plausible boundary
+    fn resync(&mut self) {
+        let mut at = 1;
+        loop {
+            // The scanned bytes are let go of in chunks, so that damage of
+            // any length is skipped in bounded memory.
+            if at >= CHUNK {
+                self.input.consume(at);
+                at = 0;
+            }
+
+            let filled = self.input.fill_to(at + SELF_DESCRIBE.len());
+            if !filled.unwrap_or(false) && at >= self.input.buffer.len() {
+                let len = self.input.buffer.len();
+                return self.input.consume(len);
+            }
+
+            if self.input.buffer[at..].starts_with(&SELF_DESCRIBE) {
+                return self.input.consume(at);
+            }
+
+            if let Some(window) = self.probe {
+                if self.decode(at, window).is_ok() {
+                    return self.input.consume(at);
+                }
+            }
+
+            at += 1;
+        }
+    }
+}
+
+impl<R: io::Read, T: DeserializeOwned> Iterator for Recover<R, T> {
+    type Item = Result<T, Skipped>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.input.offset;
+        match self.input.fill_to(1) {
+            Ok(true) => (),
+            Ok(false) => return None,
+            Err(error) => {
+                return Some(Err(Skipped {
+                    range: start..start,
+                    error: Error::Io(error),
+                }))
+            }
+        }
+
+        match self.decode(0, usize::MAX) {
+            Ok((item, len)) => {
+                self.input.consume(len);
+                Some(Ok(item))
+            }
+
+            Err(error) => {
+                self.resync();
+                Some(Err(Skipped {
+                    range: start..self.input.offset,
+                    error,
+                }))
+            }
+        }
+    }
+}
+
+/// The input of a [`Recover`], keeping the bytes read ahead of the next item
+struct Input<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    offset: u64,
+    end: bool,
+}
+
+impl<R: io::Read> Input<R> {
+    /// Reads more input onto the buffer, returning false at its end
+    ///
+    /// An error from the reader also ends the input.
+    fn more(&mut self) -> io::Result<bool> {
+        if self.end {
+            return Ok(false);
+        }
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK, 0);
+
+        let read = loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                read => break read,
+            }
+        };
+
+        self.buffer.truncate(len + *read.as_ref().unwrap_or(&0));
+        self.end = !matches!(read, Ok(x) if x > 0);
+        read.map(|x| x > 0)
+    }
+
+    /// Reads until the buffer holds `len` bytes, returning false if the
+    /// input ends first
+    fn fill_to(&mut self, len: usize) -> io::Result<bool> {
+        while self.buffer.len() < len {
+            if !self.more()? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Drops the first `len` bytes of the buffer
+    fn consume(&mut self, len: usize) {
+        self.buffer.drain(..len);
+        self.offset += len as u64;
+    }
+}
+
+/// A reader over the buffered input, from `position` up to `end`
+///
+/// The bytes read stay in the buffer, so that they can be decoded again.
+struct Window<'a, R> {
+    input: &'a mut Input<R>,
+    position: usize,
+    end: usize,
+}
+
+impl<R: io::Read> ciborium_io::Read for Window<'_, R> {
+    type Error = io::Error;
+
+    fn read_exact(&mut self, data: &mut [u8]) -> io::Result<()> {
+        let end = self.position.saturating_add(data.len());
+        if end > self.end || !self.input.fill_to(end)? {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        data.copy_from_slice(&self.input.buffer[self.position..end]);
+        self.position = end;
+        Ok(())
+    }
+
+    fn fill(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.input.buffer.len() {
+            self.input.more()?;
+        }
+
+        let end = self.input.buffer.len().min(self.end);
+        Ok(&self.input.buffer[self.position..end])
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        self.position += amount;
+    }
+}
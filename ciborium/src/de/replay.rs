@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::vec::Vec;
+
+use ciborium_io::Read;
+
+/// A reader which can return to earlier points in its input
+///
+/// Readers such as sockets cannot seek, so once an attempt to decode an
+/// item has consumed part of it, that input is gone. A `Replay` keeps a copy
+/// of everything read after a [`checkpoint()`](Self::checkpoint), so that
+/// a [`rollback()`](Self::rollback) can read it again. Only the input since
+/// the oldest outstanding checkpoint is kept; with none outstanding, reads
+/// pass straight through to the inner reader.
+///
+/// Checkpoints nest. Most callers need not manage them by hand:
+/// [`Deserializer::speculate()`](super::Deserializer::speculate) takes a
+/// checkpoint around an attempt to decode.
+#[derive(Debug)]
+pub struct Replay<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    position: usize,
+    checkpoints: Vec<usize>,
+}
+
+impl<R> Replay<R> {
+    /// Creates a new replaying reader over `reader`
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            position: 0,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Marks the current point in the input
+    #[inline]
+    pub fn checkpoint(&mut self) {
+        self.checkpoints.push(self.position);
+    }
+
+    /// Returns to the most recent checkpoint, releasing it
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no outstanding checkpoint.
+    pub fn rollback(&mut self) {
+        self.position = self.checkpoints.pop().expect("no checkpoint");
+    }
+
+    /// Releases the most recent checkpoint, keeping the current point
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no outstanding checkpoint.
+    pub fn commit(&mut self) {
+        self.checkpoints.pop().expect("no checkpoint");
+        self.trim();
+    }
+
+    /// Unwraps the inner reader
+    ///
+    /// Any input read from the inner reader but not yet replayed is lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Discards input which can no longer be replayed
+    #[inline]
+    fn trim(&mut self) {
+        if self.checkpoints.is_empty() {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+        }
+    }
+}
+
+impl<R: Read> Read for Replay<R> {
+    type Error = R::Error;
+
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        let buffered = &self.buffer[self.position..];
+        let (replayed, rest) = data.split_at_mut(buffered.len().min(data.len()));
+        replayed.copy_from_slice(&buffered[..replayed.len()]);
+        self.position += replayed.len();
+
+        if !rest.is_empty() {
+            self.reader.read_exact(rest)?;
+
+            if !self.checkpoints.is_empty() {
+                self.buffer.extend_from_slice(rest);
+                self.position += rest.len();
+            }
+        }
+
+        self.trim();
+        Ok(())
+    }
+
+    #[inline]
+    fn fill(&mut self) -> Result<&[u8], Self::Error> {
+        if self.position < self.buffer.len() {
+            return Ok(&self.buffer[self.position..]);
+        }
+
+        // Input taken from the inner buffer would have to be copied on
+        // `advance()`, so it is only exposed when nothing is being kept.
+        match self.checkpoints.is_empty() {
+            true => self.reader.fill(),
+            false => Ok(&[]),
+        }
+    }
+
+    #[inline]
+    fn advance(&mut self, amount: usize) {
+        match self.position < self.buffer.len() {
+            true => {
+                self.position += amount;
+                self.trim();
+            }
+
+            false => self.reader.advance(amount),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium_ll::Header;
+
+/// The shape of the items read from the input
+///
+/// This is returned by [`Deserializer::stats()`](super::Deserializer::stats).
+/// Items are counted by their CBOR major type, and an item nested inside a
+/// tag, array or map is counted alongside it. The chunks of an
+/// indefinite-length string are not counted as items of their own.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of unsigned integers
+    pub unsigned: usize,
+
+    /// The number of negative integers
+    pub negative: usize,
+
+    /// The number of byte strings
+    pub bytes: usize,
+
+    /// The number of text strings
+    pub text: usize,
+
+    /// The number of arrays
+    pub arrays: usize,
+
+    /// The number of maps
+    pub maps: usize,
+
+    /// The number of tags
+    pub tags: usize,
+
+    /// The number of floats and simple values, such as booleans and null
+    pub simple: usize,
+
+    /// The deepest nesting of items
+    ///
+    /// This is nesting as limited by
+    /// [`Options::recursion_limit()`](super::Options::recursion_limit). A
+    /// lone scalar has a depth of 1, and the items of an array or map are
+    /// one level deeper than the array or map itself. Tags add a level only
+    /// when they are visited, as they are when deserializing a
+    /// [`Value`](crate::Value).
+    pub max_depth: usize,
+
+    /// The total length of all definite-length strings and byte strings, in
+    /// bytes
+    pub string_bytes: u64,
+
+    /// The largest number of items in a definite-length array or entries in
+    /// a definite-length map
+    pub largest_collection: u64,
+}
+
+impl Stats {
+    /// Returns the total number of items
+    #[inline]
+    pub fn items(&self) -> usize {
+        self.unsigned
+            + self.negative
+            + self.bytes
+            + self.text
+            + self.arrays
+            + self.maps
+            + self.tags
+            + self.simple
+    }
+
+    /// Returns the counter for the item starting with `header`
+    #[inline]
+    fn counter(&mut self, header: Header) -> Option<&mut usize> {
+        match header {
+            Header::Positive(..) => Some(&mut self.unsigned),
+            Header::Negative(..) => Some(&mut self.negative),
+            Header::Float(..) | Header::Simple(..) => Some(&mut self.simple),
+            Header::Bytes(..) => Some(&mut self.bytes),
+            Header::Text(..) => Some(&mut self.text),
+            Header::Array(..) => Some(&mut self.arrays),
+            Header::Map(..) => Some(&mut self.maps),
+            Header::Tag(..) => Some(&mut self.tags),
+            Header::Break => None,
+        }
+    }
+
+    /// Counts the item starting with `header`, found at nesting `depth`
+    pub(crate) fn record(&mut self, header: Header, depth: usize) {
+        let counter = match self.counter(header) {
+            Some(counter) => counter,
+            None => return,
+        };
+
+        *counter += 1;
+        self.max_depth = self.max_depth.max(depth);
+
+        match header {
+            Header::Bytes(Some(len)) | Header::Text(Some(len)) => {
+                self.string_bytes = self.string_bytes.saturating_add(len);
+            }
+
+            Header::Array(Some(len)) | Header::Map(Some(len)) => {
+                self.largest_collection = self.largest_collection.max(len);
+            }
+
+            _ => (),
+        }
+    }
+
+    /// Takes back the count of a header which is to be read again
+    ///
+    /// The maxima are left alone, since reading the header again restores
+    /// them anyway.
+    pub(crate) fn unrecord(&mut self, header: Header) {
+        if let Some(counter) = self.counter(header) {
+            *counter -= 1;
+        }
+
+        if let Header::Bytes(Some(len)) | Header::Text(Some(len)) = header {
+            self.string_bytes = self.string_bytes.saturating_sub(len);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fixed-capacity collections from the `heapless` crate
+//!
+//! Enabling the `heapless` feature enables the `serde` support of the
+//! [`heapless`](::heapless) crate, so that `heapless::String<N>` and
+//! `heapless::Vec<T, N>` can be deserialized without an allocator. Since the
+//! length of a CBOR array is known from its header, the modules here go
+//! further: an array which would overflow the capacity is rejected before
+//! any of its elements are decoded, with an error naming the capacity.
+//!
+//! Use them with serde's `with` attribute:
+//!
+//! ```
+//! use ciborium::de::Error;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct Reading {
+//!     #[serde(with = "ciborium::heapless::vec")]
+//!     samples: heapless::Vec<u16, 4>,
+//!
+//!     #[serde(with = "ciborium::heapless::bytes")]
+//!     id: heapless::Vec<u8, 8>,
+//! }
+//!
+//! // {"samples": [1, 2, 3, 4, 5], "id": h'01'}
+//! let bytes = [
+//!     0xa2, 0x67, 0x73, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x73, 0x85, 0x01, 0x02, 0x03, 0x04,
+//!     0x05, 0x62, 0x69, 0x64, 0x41, 0x01,
+//! ];
+//!
+//! match ciborium::de::from_slice::<Reading>(&bytes) {
+//!     Err(Error::Semantic(None, msg)) => {
+//!         assert_eq!(msg, "invalid length 5, expected at most 4 elements")
+//!     }
+//!     _ => panic!("expected an overflow"),
+//! }
+//! ```
+//!
+//! `heapless::String<N>` needs no such helper: it already reports overflow
+//! as an invalid length.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+
+/// Reports the capacity of a collection when it overflows
+struct Capacity<const N: usize>(&'static str);
+
+impl<const N: usize> de::Expected for Capacity<N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "at most {} {}", N, self.0)
+    }
+}
+
+/// Collects a sequence, failing as soon as it is known not to fit
+fn collect<'de, T: de::Deserialize<'de>, A: SeqAccess<'de>, const N: usize>(
+    mut seq: A,
+    unit: &'static str,
+) -> Result<::heapless::Vec<T, N>, A::Error> {
+    let capacity = Capacity::<N>(unit);
+
+    if let Some(len) = seq.size_hint().filter(|len| *len > N) {
+        return Err(de::Error::invalid_length(len, &capacity));
+    }
+
+    let mut vec = ::heapless::Vec::new();
+    while let Some(item) = seq.next_element()? {
+        if vec.push(item).is_err() {
+            return Err(de::Error::invalid_length(N + 1, &capacity));
+        }
+    }
+
+    Ok(vec)
+}
+
+/// Serializes a `heapless::Vec<T, N>` as an array
+pub mod vec {
+    use super::*;
+
+    /// Serializes the vector as an array
+    #[inline]
+    pub fn serialize<T: serde::Serialize, S: Serializer, const N: usize>(
+        vec: &::heapless::Vec<T, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(vec)
+    }
+
+    /// Deserializes the vector from an array of at most `N` items
+    #[inline]
+    pub fn deserialize<'de, T: de::Deserialize<'de>, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<::heapless::Vec<T, N>, D::Error> {
+        struct Array<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T: de::Deserialize<'de>, const N: usize> Visitor<'de> for Array<T, N> {
+            type Value = ::heapless::Vec<T, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "an array of at most {} elements", N)
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                collect(seq, "elements")
+            }
+        }
+
+        deserializer.deserialize_seq(Array(PhantomData))
+    }
+}
+
+/// Serializes a `heapless::Vec<u8, N>` as a byte string
+///
+/// Byte strings are copied straight into the vector, so unlike the default
+/// implementation no allocation is needed. Arrays of bytes are also
+/// accepted.
+pub mod bytes {
+    use super::*;
+
+    /// Serializes the vector as a byte string
+    #[inline]
+    pub fn serialize<S: Serializer, const N: usize>(
+        vec: &::heapless::Vec<u8, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(vec)
+    }
+
+    /// Deserializes the vector from a byte string of at most `N` bytes
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<::heapless::Vec<u8, N>, D::Error> {
+        struct Bytes<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for Bytes<N> {
+            type Value = ::heapless::Vec<u8, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a byte string of at most {} bytes", N)
+            }
+
+            #[inline]
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                ::heapless::Vec::from_slice(v)
+                    .map_err(|_| E::invalid_length(v.len(), &Capacity::<N>("bytes")))
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                collect(seq, "bytes")
+            }
+        }
+
+        deserializer.deserialize_bytes(Bytes)
+    }
+}
@@ -11,8 +11,28 @@
 //! the main functions. Note that byte slices are also readers and writers and can be
 //! passed to these functions just as streams can.
 //!
+//! Users coming from `serde_json` will also find the familiar
+//! [`from_reader()`], [`from_slice()`], [`to_writer()`] and `to_vec()`
+//! (with the `std` feature) at the crate root.
+//!
 //! For dynamic CBOR value creation/inspection, see [`Value`](crate::value::Value).
 //!
+//! # Readers and Writers of Your Own
+//!
+//! With the `std` feature, which is on by default, every `std::io::Read`
+//! and `std::io::Write` type is a reader or writer through blanket
+//! implementations in [`ciborium_io`]. These rule out implementing
+//! [`ciborium_io::Read`] or [`ciborium_io::Write`] for your own types which
+//! also implement the `std::io` traits.
+//!
+//! The `std` feature of this crate always enables the blanket
+//! implementations, since the helpers it adds, such as
+//! `from_reader_exact()`, take `std::io` types as they are. To keep the blanket implementations out, disable
+//! `std` here and enable only the `std-adapters` feature of `ciborium-io`,
+//! whose `IoReader` and `IoWriter` wrap a single `std::io` type. This gives
+//! up the helpers which need `std`, and holds only as long as nothing else
+//! in the build enables the `std` feature of either crate.
+//!
 //! # Design Decisions
 //!
 //! ## Always Serialize Numeric Values to the Smallest Size
@@ -92,27 +112,68 @@
 
 extern crate alloc;
 
+pub mod adapters;
+#[cfg(feature = "bumpalo")]
+pub mod arena;
+pub mod cddl;
+#[cfg(feature = "compact")]
+pub mod compact;
 pub mod de;
+#[cfg(feature = "heapless")]
+pub mod heapless;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "yoke")]
+pub mod owned;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod patch;
 pub mod ser;
+pub mod stream;
 pub mod tag;
+pub mod validate;
 pub mod value;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export the [items recommended by serde](https://serde.rs/conventions.html).
 #[doc(inline)]
+pub use crate::de::extract;
+#[cfg(feature = "hex")]
+#[doc(inline)]
+pub use crate::de::from_hex_str;
+#[doc(inline)]
 pub use crate::de::from_reader;
 #[doc(inline)]
 pub use crate::de::from_reader_with_buffer;
+#[doc(inline)]
+pub use crate::de::from_slice;
+#[doc(inline)]
+pub use crate::de::Deserializer;
 
 #[doc(inline)]
 pub use crate::ser::into_writer;
+#[cfg(feature = "hex")]
+#[doc(inline)]
+pub use crate::ser::to_hex_string;
+#[doc(inline)]
+pub use crate::ser::to_writer;
+#[doc(inline)]
+pub use crate::ser::Serializer;
 
 #[cfg(feature = "std")]
 #[doc(inline)]
 pub use crate::ser::into_vec;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use crate::ser::to_vec;
 
 #[doc(inline)]
 pub use crate::value::Value;
 
+#[doc(inline)]
+pub use ciborium_ll::simple;
+
 /// Build a `Value` conveniently.
 ///
 /// The syntax should be intuitive if you are familiar with JSON. You can also
@@ -226,3 +287,32 @@ macro_rules! cbor {
         $crate::value::Value::serialized(&$val)
     }};
 }
+
+/// Asserts that two CBOR items are structurally equal
+///
+/// Each side is either a [`Value`] or a byte string holding an encoded item.
+/// On mismatch, the panic message names the path to the first differing
+/// item and shows both versions of it in diagnostic notation, rather than
+/// two opaque byte strings. When both sides are byte strings which decode
+/// to the same value, their encodings are compared as well.
+///
+/// ```should_panic
+/// use ciborium::{assert_cbor_eq, cbor};
+///
+/// let value = cbor!({ "id" => 7, "tags" => ["a", "b"] }).unwrap();
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&value, &mut bytes).unwrap();
+/// assert_cbor_eq!(value, bytes);
+///
+/// // Panics with:
+/// // assertion failed: CBOR differs at $["tags"][1]
+/// //   expected: "b"
+/// //     actual: "c"
+/// assert_cbor_eq!(bytes, cbor!({ "id" => 7, "tags" => ["a", "c"] }).unwrap());
+/// ```
+#[macro_export]
+macro_rules! assert_cbor_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        $crate::value::assert_eq(&$expected, &$actual)
+    };
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of CBOR files mapped into memory
+//!
+//! Large CBOR dumps are most efficiently decoded straight out of the page
+//! cache: [`Mapped`] maps a file into memory and decodes from the mapping as
+//! a byte slice, so no bytes are copied through an intermediate reader.
+//!
+//! ```no_run
+//! use ciborium::mmap::Mapped;
+//! use ciborium::Value;
+//!
+//! // SAFETY: the file is not modified while it is mapped.
+//! let file = unsafe { Mapped::open("records.cbor") }.unwrap();
+//!
+//! for record in file.iter::<Value>() {
+//!     let record = record.unwrap();
+//!     // ...
+//! }
+//! ```
+//!
+//! Logs and other long-lived sequences may contain damaged items; iterating
+//! with [`Mapped::recover`] skips over them and reports what was skipped
+//! instead of stopping at the first error.
+
+use crate::de::{from_reader, from_slice, Error, Recover};
+use crate::value::Value;
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+enum Backing {
+    Map(memmap2::Mmap),
+    Heap(Vec<u8>),
+}
+
+/// The contents of a file, mapped into memory for decoding
+///
+/// A `Mapped` dereferences to the bytes of the file. Files which cannot be
+/// mapped (such as empty files, pipes or character devices) are read into
+/// memory instead; [`Mapped::is_mapped`] reports which backing was used.
+pub struct Mapped(Backing);
+
+impl Mapped {
+    /// Maps the file at `path` into memory
+    ///
+    /// Falls back to reading the file into memory if it cannot be mapped.
+    ///
+    /// # Safety
+    ///
+    /// The mapping is only sound as long as the underlying file is not
+    /// modified or truncated, by this or any other process, while the
+    /// `Mapped` is alive. Doing so is undefined behavior: decoded values may
+    /// change underneath the decoder and truncation may raise `SIGBUS`.
+    ///
+    /// If this cannot be guaranteed, use [`Mapped::read`] instead.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        if file.metadata()?.len() > 0 {
+            if let Ok(map) = memmap2::Mmap::map(&file) {
+                return Ok(Self(Backing::Map(map)));
+            }
+        }
+
+        Self::load(file)
+    }
+
+    /// Reads the file at `path` into memory
+    ///
+    /// This is the safe counterpart of [`Mapped::open`]: the file is copied
+    /// into memory up front, so later modifications cannot be observed.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load(File::open(path)?)
+    }
+
+    fn load(mut file: File) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        Ok(Self(Backing::Heap(bytes)))
+    }
+
+    /// Whether the contents are mapped rather than read into memory
+    #[inline]
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.0, Backing::Map(..))
+    }
+
+    /// Deserializes the first item in the file
+    ///
+    /// Any bytes following the item are ignored.
+    #[inline]
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, Error<io::Error>> {
+        from_slice(self)
+    }
+
+    /// Deserializes the first item in the file as a [`Value`]
+    #[inline]
+    pub fn value(&self) -> Result<Value, Error<io::Error>> {
+        self.decode()
+    }
+
+    /// Deserializes a single item addressed by a JSON Pointer
+    ///
+    /// Only the addressed item is deserialized; everything else is skipped
+    /// at the decoder level. See [`extract`](crate::de::extract) for details.
+    #[inline]
+    pub fn extract<T: DeserializeOwned>(
+        &self,
+        pointer: &str,
+    ) -> Result<Option<T>, Error<io::Error>> {
+        crate::de::extract(ciborium_io::Buffered::new(&self[..]), pointer)
+    }
+
+    /// Lazily deserializes the file as a CBOR sequence ([RFC 8742])
+    ///
+    /// Each item is decoded only when the iterator is advanced. Iteration
+    /// stops after the first error.
+    ///
+    /// [RFC 8742]: https://www.rfc-editor.org/rfc/rfc8742
+    #[inline]
+    pub fn iter<T: DeserializeOwned>(&self) -> Iter<'_, T> {
+        Iter {
+            rest: self,
+            item: PhantomData,
+        }
+    }
+
+    /// Lazily deserializes the file as a CBOR sequence, skipping over
+    /// malformed items
+    ///
+    /// Unlike [`Mapped::iter`], iteration continues after an error; see
+    /// [`Recover`] for how decoding resumes. Each item is copied out of the
+    /// file as it is decoded, so that it can be decoded again.
+    ///
+    /// ```no_run
+    /// use ciborium::mmap::Mapped;
+    /// use ciborium::Value;
+    ///
+    /// let file = Mapped::read("records.cbor").unwrap();
+    ///
+    /// for record in file.recover::<Value>() {
+    ///     match record {
+    ///         Ok(record) => { /* ... */ }
+    ///         Err(skipped) => eprintln!("skipped bytes {:?}: {}", skipped.range, skipped.error),
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn recover<T: DeserializeOwned>(&self) -> Recover<&[u8], T> {
+        Recover::new(self)
+    }
+}
+
+impl Deref for Mapped {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match &self.0 {
+            Backing::Map(map) => map,
+            Backing::Heap(bytes) => bytes,
+        }
+    }
+}
+
+impl AsRef<[u8]> for Mapped {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// An iterator over the items of a CBOR sequence
+///
+/// This type is returned by [`Mapped::iter`].
+pub struct Iter<'a, T> {
+    rest: &'a [u8],
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Iterator for Iter<'_, T> {
+    type Item = Result<T, Error<io::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let item = from_reader(ciborium_io::Buffered::new(&mut self.rest));
+        if item.is_err() {
+            self.rest = &[];
+        }
+
+        Some(item)
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoded values which own the input they borrow from
+//!
+//! Decoding from a slice lets strings and byte strings borrow from the
+//! input, but the result cannot outlive the slice, which rules out storing
+//! it in a cache or moving it into a spawned task. An [`Owned`] keeps the
+//! input and the value borrowing from it together, using the [`yoke`]
+//! crate: the value's type is written with its lifetime erased to
+//! `'static`, and [`Owned::get()`] hands it back with a lifetime tied to
+//! the `Owned`.
+//!
+//! ```
+//! use ciborium::{cbor, owned::Owned, Value};
+//! use serde::Deserialize;
+//! use yoke::Yokeable;
+//!
+//! #[derive(Deserialize, Yokeable)]
+//! struct Message<'a> {
+//!     topic: &'a str,
+//!     #[serde(with = "serde_bytes")]
+//!     body: &'a [u8],
+//! }
+//!
+//! let mut bytes = Vec::new();
+//! let value = cbor!({ "topic" => "news", "body" => Value::Bytes(vec![1, 2, 3]) }).unwrap();
+//! ciborium::into_writer(&value, &mut bytes).unwrap();
+//!
+//! let owned = Owned::<Message<'static>>::from_bytes(bytes).unwrap();
+//!
+//! // The `Owned` is `'static`, so it can be sent to another thread.
+//! let topic = std::thread::spawn(move || owned.get().topic.to_owned());
+//! assert_eq!(topic.join().unwrap(), "news");
+//! ```
+
+use crate::de::{from_slice, Error};
+
+use alloc::boxed::Box;
+
+use ciborium_io::Read;
+use serde::de::Deserialize;
+use yoke::{Yoke, Yokeable};
+
+/// A decoded value along with the input it borrows from
+///
+/// `T` is the type of the value with its lifetime set to `'static`, such as
+/// `Message<'static>`; it must implement [`Yokeable`], which is usually
+/// derived.
+pub struct Owned<T: for<'a> Yokeable<'a>>(Yoke<T, Box<[u8]>>);
+
+impl<T: for<'a> Yokeable<'a>> Owned<T>
+where
+    for<'a> <T as Yokeable<'a>>::Output: Deserialize<'a>,
+{
+    /// Takes ownership of `bytes` and deserializes the first item in them
+    ///
+    /// Strings and byte strings are borrowed from `bytes`, as with
+    /// [`from_slice()`](crate::de::from_slice). Any bytes following the item
+    /// are kept but ignored.
+    pub fn from_bytes(
+        bytes: impl Into<Box<[u8]>>,
+    ) -> Result<Self, Error<<&'static [u8] as Read>::Error>> {
+        Yoke::try_attach_to_cart(bytes.into(), |bytes| from_slice(bytes)).map(Self)
+    }
+
+    /// Reads all of `reader` into memory and deserializes the first item
+    ///
+    /// This is for inputs which end with the message, such as a request
+    /// body or a length-delimited frame.
+    #[cfg(feature = "std")]
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self, Error<std::io::Error>> {
+        let mut bytes = alloc::vec::Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<T: for<'a> Yokeable<'a>> Owned<T> {
+    /// Returns the decoded value
+    #[inline]
+    pub fn get(&self) -> &<T as Yokeable<'_>>::Output {
+        self.0.get()
+    }
+
+    /// Returns the input the value was decoded from
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        self.0.backing_cart()
+    }
+
+    /// Unwraps the underlying [`Yoke`]
+    #[inline]
+    pub fn into_yoke(self) -> Yoke<T, Box<[u8]>> {
+        self.0
+    }
+}
+
+impl<T: for<'a> Yokeable<'a>> core::fmt::Debug for Owned<T>
+where
+    for<'a> <T as Yokeable<'a>>::Output: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Owned").field(self.get()).finish()
+    }
+}
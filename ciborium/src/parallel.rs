@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parallel decoding of CBOR sequences
+//!
+//! The items of a CBOR sequence ([RFC 8742]) are independent of each other.
+//! Once the boundaries between them are known, they can be decoded on
+//! separate threads. [`split`] finds those boundaries by skipping over each
+//! item at the decoder level, which is much cheaper than decoding it, and
+//! [`from_slice`] then decodes the items in parallel using [`rayon`].
+//!
+//! ```
+//! use ciborium::parallel;
+//!
+//! // The sequence 1, "two", [3]
+//! let bytes = [0x01, 0x63, b't', b'w', b'o', 0x81, 0x03];
+//!
+//! let items = parallel::split(&bytes).unwrap();
+//! assert_eq!(items, [&bytes[..1], &bytes[1..5], &bytes[5..]]);
+//!
+//! let values: Vec<ciborium::Value> = parallel::from_slice(&bytes).unwrap();
+//! assert_eq!(values[1], ciborium::Value::from("two"));
+//! ```
+//!
+//! [RFC 8742]: https://www.rfc-editor.org/rfc/rfc8742
+
+use crate::de::{self, Deserializer, Error, Options};
+
+use std::io;
+
+use rayon::prelude::*;
+
+/// Splits a CBOR sequence into its items
+///
+/// Each item is skipped without being deserialized; the contents of strings
+/// are not validated. Fails if the input ends in the middle of an item.
+pub fn split(slice: &[u8]) -> Result<Vec<&[u8]>, Error<io::Error>> {
+    let mut scratch = [0; 64];
+    let mut deserializer = Deserializer::from_slice(slice, &mut scratch, Options::new());
+
+    let mut items = Vec::new();
+    let mut start = 0;
+    while start < slice.len() {
+        deserializer.skip_item()?;
+        // Offsets into a slice always fit in `usize`.
+        let end = deserializer.offset() as usize;
+        items.push(&slice[start..end]);
+        start = end;
+    }
+
+    Ok(items)
+}
+
+/// Deserializes every item of a CBOR sequence in parallel
+///
+/// The sequence is first split with [`split`]. The offsets of errors are
+/// relative to the start of the whole sequence; if several items fail to
+/// deserialize, the error of the first one is returned.
+pub fn from_slice<'de, T: serde::de::Deserialize<'de> + Send>(
+    slice: &'de [u8],
+) -> Result<Vec<T>, Error<io::Error>> {
+    let items = split(slice)?;
+
+    let mut offsets = Vec::with_capacity(items.len());
+    let mut offset = 0;
+    for item in &items {
+        offsets.push(offset);
+        offset += item.len() as u64;
+    }
+
+    // Collecting straight into a `Result` would return whichever error a
+    // thread happened to hit first, so the results are kept in order.
+    let results: Vec<Result<T, _>> = items
+        .into_par_iter()
+        .zip(offsets)
+        .map(|(item, offset)| de::from_slice(item).map_err(|e| e.rebase(offset)))
+        .collect();
+
+    results.into_iter().collect()
+}
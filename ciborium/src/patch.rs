@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Patches describing the changes between two values
+//!
+//! A [`Patch`] is a list of operations which add, remove or replace items
+//! addressed by a [`Path`], in the spirit of JSON Patch (RFC 6902). Unlike a
+//! JSON Pointer, a path can address map entries with keys of any type; see
+//! [`Path::to_pointer()`] and [`Value::pointer_path()`] to convert between
+//! the two. Since a
+//! patch is usually much smaller than the value it changes, peers can keep
+//! their copies of a document in sync by exchanging patches rather than
+//! whole documents. Patches are themselves serializable, so they can be
+//! sent as CBOR:
+//!
+//! ```
+//! use ciborium::{cbor, patch};
+//!
+//! let old = cbor!({ "name" => "sensor", "readings" => [1, 2], "stale" => true }).unwrap();
+//! let new = cbor!({ "name" => "sensor", "readings" => [1, 2, 3] }).unwrap();
+//!
+//! let patch = patch::diff(&old, &new);
+//! assert_eq!(patch.operations().len(), 2);
+//!
+//! let mut bytes = Vec::new();
+//! ciborium::into_writer(&patch, &mut bytes).unwrap();
+//! let patch: patch::Patch = ciborium::from_reader(&bytes[..]).unwrap();
+//!
+//! let mut value = old.clone();
+//! patch::apply(&mut value, &patch).unwrap();
+//! assert_eq!(value, new);
+//! ```
+
+use crate::value::{self, get_mut, untagged_mut, Error, Path, Value};
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use serde::{de, ser, ser::SerializeMap, Deserialize, Serialize};
+
+/// A single change to a value
+///
+/// Operations serialize as a map with an `op` field naming the operation,
+/// along with its `path` and `value`, as in JSON Patch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    /// Adds an item
+    ///
+    /// In a map, the entry is appended, or replaced if the key exists. In
+    /// an array, the element is inserted at the index, which may be the
+    /// length of the array to append. At the root, the whole value is
+    /// replaced.
+    Add {
+        /// The location of the new item
+        path: Path,
+
+        /// The new item
+        value: Value,
+    },
+
+    /// Removes an existing item
+    Remove {
+        /// The location of the item
+        path: Path,
+    },
+
+    /// Replaces an existing item
+    Replace {
+        /// The location of the item
+        path: Path,
+
+        /// The new item
+        value: Value,
+    },
+}
+
+impl Operation {
+    /// Returns the location the operation applies to
+    #[inline]
+    pub fn path(&self) -> &Path {
+        match self {
+            Operation::Add { path, .. } => path,
+            Operation::Remove { path } => path,
+            Operation::Replace { path, .. } => path,
+        }
+    }
+}
+
+impl Serialize for Operation {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (op, path, value) = match self {
+            Operation::Add { path, value } => ("add", path, Some(value)),
+            Operation::Remove { path } => ("remove", path, None),
+            Operation::Replace { path, value } => ("replace", path, Some(value)),
+        };
+
+        let mut map = serializer.serialize_map(Some(2 + value.is_some() as usize))?;
+        map.serialize_entry("op", op)?;
+        map.serialize_entry("path", path)?;
+        if let Some(value) = value {
+            map.serialize_entry("value", value)?;
+        }
+        map.end()
+    }
+}
+
+// Deserializing by hand, rather than as an internally tagged enum, avoids
+// buffering the value, which would lose any tags within it.
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Operation;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a patch operation")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Operation, A::Error> {
+                let mut op: Option<String> = None;
+                let mut path = None;
+                let mut value = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "op" => op = Some(map.next_value()?),
+                        "path" => path = Some(map.next_value()?),
+                        "value" => value = Some(map.next_value()?),
+                        _ => drop(map.next_value::<de::IgnoredAny>()?),
+                    }
+                }
+
+                let path = path.ok_or_else(|| de::Error::missing_field("path"))?;
+                let value = || value.ok_or_else(|| de::Error::missing_field("value"));
+
+                match op.as_deref() {
+                    Some("add") => Ok(Operation::Add {
+                        path,
+                        value: value()?,
+                    }),
+                    Some("remove") => Ok(Operation::Remove { path }),
+                    Some("replace") => Ok(Operation::Replace {
+                        path,
+                        value: value()?,
+                    }),
+                    Some(op) => Err(de::Error::unknown_variant(
+                        op,
+                        &["add", "remove", "replace"],
+                    )),
+                    None => Err(de::Error::missing_field("op")),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+/// A sequence of operations, applied in order
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Patch(Vec<Operation>);
+
+impl Patch {
+    /// Returns the operations of the patch
+    #[inline]
+    pub fn operations(&self) -> &[Operation] {
+        &self.0
+    }
+
+    /// Returns whether the patch makes no changes
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Operation>> for Patch {
+    #[inline]
+    fn from(operations: Vec<Operation>) -> Self {
+        Self(operations)
+    }
+}
+
+impl From<Patch> for Vec<Operation> {
+    #[inline]
+    fn from(patch: Patch) -> Self {
+        patch.0
+    }
+}
+
+/// Returns a patch which transforms `from` into `to`
+///
+/// The patch descends into arrays, maps and tags with equal tag numbers,
+/// so that unchanged items are not repeated. Applying the patch to `from`
+/// always yields a value equal to `to`, including the order of map entries.
+pub fn diff(from: &Value, to: &Value) -> Patch {
+    let mut operations = Vec::new();
+    changes(&mut Path::new(), from, to, &mut operations);
+    Patch(operations)
+}
+
+fn find<'a>(map: &'a [(Value, Value)], key: &Value) -> Option<&'a Value> {
+    map.iter().find(|(k, ..)| k == key).map(|(.., v)| v)
+}
+
+// Arrays, maps and tags are compared by descending into them, so each item
+// is only compared once.
+fn changes(path: &mut Path, from: &Value, to: &Value, operations: &mut Vec<Operation>) {
+    match (from, to) {
+        (Value::Tag(x, a), Value::Tag(y, b)) if x == y => changes(path, a, b, operations),
+
+        (Value::Array(a), Value::Array(b)) => {
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                path.push(i as u64);
+                changes(path, x, y, operations);
+                path.pop();
+            }
+
+            for i in (b.len()..a.len()).rev() {
+                let path = path.join(i as u64);
+                operations.push(Operation::Remove { path });
+            }
+
+            for (i, value) in b.iter().enumerate().skip(a.len()) {
+                let path = path.join(i as u64);
+                let value = value.clone();
+                operations.push(Operation::Add { path, value });
+            }
+        }
+
+        // Added entries are appended, so the entries of `to` must be the
+        // kept entries of `from` in their original order, then the new ones.
+        (Value::Map(a), Value::Map(b))
+            if a.iter()
+                .filter(|(k, ..)| find(b, k).is_some())
+                .chain(b.iter().filter(|(k, ..)| find(a, k).is_none()))
+                .map(|(k, ..)| k)
+                .eq(b.iter().map(|(k, ..)| k)) =>
+        {
+            for (key, x) in a {
+                path.push(key.clone());
+                match find(b, key) {
+                    Some(y) => changes(path, x, y, operations),
+                    None => operations.push(Operation::Remove { path: path.clone() }),
+                }
+                path.pop();
+            }
+
+            for (key, value) in b.iter().filter(|(k, ..)| find(a, k).is_none()) {
+                let path = path.join(key.clone());
+                let value = value.clone();
+                operations.push(Operation::Add { path, value });
+            }
+        }
+
+        _ if value::diff(from, to).is_none() => (),
+
+        _ => {
+            let path = path.clone();
+            let value = to.clone();
+            operations.push(Operation::Replace { path, value });
+        }
+    }
+}
+
+/// Applies a patch to a value
+///
+/// Fails if an operation addresses an item which does not exist, or whose
+/// parent is neither an array nor a map. Operations before the failing one
+/// remain applied; clone the value first if the patch must be applied
+/// atomically.
+pub fn apply(value: &mut Value, patch: &Patch) -> Result<(), Error> {
+    patch.0.iter().try_for_each(|op| operation(value, op))
+}
+
+fn operation(root: &mut Value, operation: &Operation) -> Result<(), Error> {
+    let path = operation.path();
+    let missing = || Error::Custom(format!("no item at {}", path));
+
+    let (parent, last) = match path.segments().split_last() {
+        Some((last, parent)) => (get_mut(root, parent).ok_or_else(missing)?, last),
+        None => {
+            return match operation {
+                Operation::Add { value, .. } | Operation::Replace { value, .. } => {
+                    *root = value.clone();
+                    Ok(())
+                }
+                Operation::Remove { .. } => Err(Error::Custom("cannot remove the root".into())),
+            };
+        }
+    };
+
+    let index = |len: usize| {
+        last.as_integer()
+            .and_then(|i| usize::try_from(i).ok())
+            .filter(|i| *i < len)
+    };
+
+    match (untagged_mut(parent), operation) {
+        (Value::Array(x), Operation::Add { value, .. }) => match index(x.len() + 1) {
+            Some(i) => x.insert(i, value.clone()),
+            None => return Err(missing()),
+        },
+
+        (Value::Array(x), Operation::Remove { .. }) => match index(x.len()) {
+            Some(i) => drop(x.remove(i)),
+            None => return Err(missing()),
+        },
+
+        (Value::Array(x), Operation::Replace { value, .. }) => match index(x.len()) {
+            Some(i) => x[i] = value.clone(),
+            None => return Err(missing()),
+        },
+
+        (Value::Map(x), op) => match (x.iter().position(|(k, ..)| k == last), op) {
+            (Some(i), Operation::Remove { .. }) => drop(x.remove(i)),
+            (Some(i), Operation::Add { value, .. } | Operation::Replace { value, .. }) => {
+                x[i].1 = value.clone()
+            }
+            (None, Operation::Add { value, .. }) => x.push((last.clone(), value.clone())),
+            (None, ..) => return Err(missing()),
+        },
+
+        _ => return Err(missing()),
+    }
+
+    Ok(())
+}
@@ -3,28 +3,111 @@
 //! Serde serialization support for CBOR
 
 mod error;
+mod options;
 
 pub use error::Error;
+pub use options::Options;
 
-use alloc::string::ToString;
+use alloc::{format, string::ToString, vec::Vec};
 
+use crate::value::KeyOrder;
 use ciborium_io::Write;
 use ciborium_ll::*;
 use serde::{ser, Serialize as _};
 
-struct Serializer<W>(Encoder<W>);
+/// A serde serializer for CBOR
+///
+/// Most users should use [`into_writer`] instead. This type is exposed for
+/// libraries which compose with other serde tooling and therefore need to
+/// name the serializer.
+///
+/// ```
+/// use ciborium::ser::Serializer;
+/// use serde::Serialize;
+///
+/// let mut serializer = Serializer::new(Vec::new());
+/// [1u8, 2, 3].serialize(&mut serializer).unwrap();
+/// assert_eq!(serializer.into_inner(), [0x83, 0x01, 0x02, 0x03]);
+/// ```
+pub struct Serializer<W> {
+    encoder: Encoder<W>,
+    options: Options,
+
+    // The bytes left of a streamed byte string of known length. While this
+    // is set, serialized byte strings are its chunks and are written raw.
+    raw: Option<usize>,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Creates a new serializer writing to `writer`
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, Options::new())
+    }
+
+    /// Creates a new serializer writing to `writer` using the specified
+    /// [`Options`]
+    #[inline]
+    pub fn with_options(writer: W, options: Options) -> Self {
+        Self {
+            encoder: writer.into(),
+            options,
+            raw: None,
+        }
+    }
+
+    /// Returns the underlying encoder
+    ///
+    /// This allows mixing raw headers and pre-encoded items with serialized
+    /// values. Serialization of an item borrows the serializer until it
+    /// completes, so the encoder is always between items when reached here.
+    ///
+    /// ```
+    /// use ciborium::ser::Serializer;
+    /// use ciborium_ll::Header;
+    /// use serde::Serialize;
+    ///
+    /// let mut serializer = Serializer::new(Vec::new());
+    /// serializer.encoder_mut().push(Header::Tag(55799)).unwrap();
+    /// "hi".serialize(&mut serializer).unwrap();
+    /// assert_eq!(serializer.into_inner(), [0xd9, 0xd9, 0xf7, 0x62, 0x68, 0x69]);
+    /// ```
+    #[inline]
+    pub fn encoder_mut(&mut self) -> &mut Encoder<W> {
+        &mut self.encoder
+    }
+
+    /// Unwraps the writer, consuming the serializer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.encoder.into_inner()
+    }
+
+    /// Writes the name or index identifying an enum variant
+    #[inline]
+    fn variant(&mut self, index: u32, variant: &str) -> Result<(), Error<W::Error>> {
+        match self.options.variant_index {
+            false => Ok(self.encoder.text(variant, None)?),
+            true => Ok(self.encoder.push(Header::Positive(index.into()))?),
+        }
+    }
+}
 
 impl<W: Write> From<W> for Serializer<W> {
     #[inline]
     fn from(writer: W) -> Self {
-        Self(writer.into())
+        Self::new(writer)
     }
 }
 
 impl<W: Write> From<Encoder<W>> for Serializer<W> {
     #[inline]
     fn from(writer: Encoder<W>) -> Self {
-        Self(writer)
+        Self {
+            encoder: writer,
+            options: Options::new(),
+            raw: None,
+        }
     }
 }
 
@@ -45,7 +128,7 @@ where
 
     #[inline]
     fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
-        Ok(self.0.push(match v {
+        Ok(self.encoder.push(match v {
             false => Header::Simple(simple::FALSE),
             true => Header::Simple(simple::TRUE),
         })?)
@@ -68,7 +151,7 @@ where
 
     #[inline]
     fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
-        Ok(self.0.push(match v.is_negative() {
+        Ok(self.encoder.push(match v.is_negative() {
             false => Header::Positive(v as u64),
             true => Header::Negative(v as u64 ^ !0),
         })?)
@@ -82,8 +165,8 @@ where
         };
 
         match (tag, u64::try_from(raw)) {
-            (tag::BIGPOS, Ok(x)) => return Ok(self.0.push(Header::Positive(x))?),
-            (tag::BIGNEG, Ok(x)) => return Ok(self.0.push(Header::Negative(x))?),
+            (tag::BIGPOS, Ok(x)) => return Ok(self.encoder.push(Header::Positive(x))?),
+            (tag::BIGNEG, Ok(x)) => return Ok(self.encoder.push(Header::Negative(x))?),
             _ => {}
         }
 
@@ -95,9 +178,8 @@ where
             slice = &slice[1..];
         }
 
-        self.0.push(Header::Tag(tag))?;
-        self.0.push(Header::Bytes(Some(slice.len())))?;
-        Ok(self.0.write_all(slice)?)
+        self.encoder.push(Header::Tag(tag))?;
+        Ok(self.encoder.bytes(slice, None)?)
     }
 
     #[inline]
@@ -117,7 +199,7 @@ where
 
     #[inline]
     fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
-        Ok(self.0.push(Header::Positive(v))?)
+        Ok(self.encoder.push(Header::Positive(v))?)
     }
 
     #[inline]
@@ -134,9 +216,8 @@ where
             slice = &slice[1..];
         }
 
-        self.0.push(Header::Tag(tag::BIGPOS))?;
-        self.0.push(Header::Bytes(Some(slice.len())))?;
-        Ok(self.0.write_all(slice)?)
+        self.encoder.push(Header::Tag(tag::BIGPOS))?;
+        Ok(self.encoder.bytes(slice, None)?)
     }
 
     #[inline]
@@ -146,7 +227,11 @@ where
 
     #[inline]
     fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
-        Ok(self.0.push(Header::Float(v))?)
+        if !self.options.non_finite && !v.is_finite() {
+            return Err(Error::Value("non-finite float".into()));
+        }
+
+        Ok(self.encoder.push(Header::Float(v))?)
     }
 
     #[inline]
@@ -156,20 +241,26 @@ where
 
     #[inline]
     fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
-        let bytes = v.as_bytes();
-        self.0.push(Header::Text(bytes.len().into()))?;
-        Ok(self.0.write_all(bytes)?)
+        Ok(self.encoder.text(v, None)?)
     }
 
     #[inline]
     fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
-        self.0.push(Header::Bytes(v.len().into()))?;
-        Ok(self.0.write_all(v)?)
+        // Inside a streamed byte string of known length, chunks are raw data.
+        if let Some(left) = self.raw {
+            let left = left
+                .checked_sub(v.len())
+                .ok_or_else(|| Error::Value("streamed bytes exceed their length".into()))?;
+            self.raw = Some(left);
+            return Ok(self.encoder.write_all(v)?);
+        }
+
+        Ok(self.encoder.bytes(v, None)?)
     }
 
     #[inline]
     fn serialize_none(self) -> Result<(), Self::Error> {
-        Ok(self.0.push(Header::Simple(simple::NULL))?)
+        Ok(self.encoder.push(Header::Simple(simple::NULL))?)
     }
 
     #[inline]
@@ -191,10 +282,10 @@ where
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _index: u32,
+        index: u32,
         variant: &'static str,
     ) -> Result<(), Self::Error> {
-        self.serialize_str(variant)
+        self.variant(index, variant)
     }
 
     #[inline]
@@ -210,13 +301,13 @@ where
     fn serialize_newtype_variant<U: ?Sized + ser::Serialize>(
         self,
         name: &'static str,
-        _index: u32,
+        index: u32,
         variant: &'static str,
         value: &U,
     ) -> Result<(), Self::Error> {
         if name != "@@TAG@@" || variant != "@@UNTAGGED@@" {
-            self.0.push(Header::Map(Some(1)))?;
-            self.serialize_str(variant)?;
+            self.encoder.push(Header::Map(Some(1)))?;
+            self.variant(index, variant)?;
         }
 
         value.serialize(self)
@@ -224,11 +315,12 @@ where
 
     #[inline]
     fn serialize_seq(self, length: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        self.0.push(Header::Array(length))?;
+        self.encoder.push(Header::Array(length.map(|x| x as u64)))?;
         Ok(CollectionSerializer {
             encoder: self,
             ending: length.is_none(),
             tag: false,
+            sorted: None,
         })
     }
 
@@ -240,17 +332,33 @@ where
     #[inline]
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         length: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        self.serialize_seq(Some(length))
+        // A streamed byte string, whose fields are its chunks
+        let header = match name {
+            "@@STREAM@@" => Header::Bytes(None),
+            "@@STREAM_SIZED@@" => {
+                self.raw = Some(length);
+                Header::Bytes(Some(length as u64))
+            }
+            _ => return self.serialize_seq(Some(length)),
+        };
+
+        self.encoder.push(header)?;
+        Ok(CollectionSerializer {
+            encoder: self,
+            ending: header == Header::Bytes(None),
+            tag: false,
+            sorted: None,
+        })
     }
 
     #[inline]
     fn serialize_tuple_variant(
         self,
         name: &'static str,
-        _index: u32,
+        index: u32,
         variant: &'static str,
         length: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
@@ -259,16 +367,18 @@ where
                 encoder: self,
                 ending: false,
                 tag: true,
+                sorted: None,
             }),
 
             _ => {
-                self.0.push(Header::Map(Some(1)))?;
-                self.serialize_str(variant)?;
-                self.0.push(Header::Array(Some(length)))?;
+                self.encoder.push(Header::Map(Some(1)))?;
+                self.variant(index, variant)?;
+                self.encoder.push(Header::Array(Some(length as u64)))?;
                 Ok(CollectionSerializer {
                     encoder: self,
                     ending: false,
                     tag: false,
+                    sorted: None,
                 })
             }
         }
@@ -276,11 +386,16 @@ where
 
     #[inline]
     fn serialize_map(self, length: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        self.0.push(Header::Map(length))?;
+        if self.options.sort_maps {
+            return Ok(CollectionSerializer::sorted(self));
+        }
+
+        self.encoder.push(Header::Map(length.map(|x| x as u64)))?;
         Ok(CollectionSerializer {
             encoder: self,
             ending: length.is_none(),
             tag: false,
+            sorted: None,
         })
     }
 
@@ -290,11 +405,19 @@ where
         _name: &'static str,
         length: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        self.0.push(Header::Map(Some(length)))?;
+        if self.options.sort_maps && !self.options.struct_as_array {
+            return Ok(CollectionSerializer::sorted(self));
+        }
+
+        self.encoder.push(match self.options.struct_as_array {
+            false => Header::Map(Some(length as u64)),
+            true => Header::Array(Some(length as u64)),
+        })?;
         Ok(CollectionSerializer {
             encoder: self,
             ending: false,
             tag: false,
+            sorted: None,
         })
     }
 
@@ -302,17 +425,25 @@ where
     fn serialize_struct_variant(
         self,
         _name: &'static str,
-        _index: u32,
+        index: u32,
         variant: &'static str,
         length: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        self.0.push(Header::Map(Some(1)))?;
-        self.serialize_str(variant)?;
-        self.0.push(Header::Map(Some(length)))?;
+        self.encoder.push(Header::Map(Some(1)))?;
+        self.variant(index, variant)?;
+        if self.options.sort_maps && !self.options.struct_as_array {
+            return Ok(CollectionSerializer::sorted(self));
+        }
+
+        self.encoder.push(match self.options.struct_as_array {
+            false => Header::Map(Some(length as u64)),
+            true => Header::Array(Some(length as u64)),
+        })?;
         Ok(CollectionSerializer {
             encoder: self,
             ending: false,
             tag: false,
+            sorted: None,
         })
     }
 
@@ -326,8 +457,27 @@ macro_rules! end {
     () => {
         #[inline]
         fn end(self) -> Result<(), Self::Error> {
+            if let Some(mut entries) = self.sorted {
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                self.encoder
+                    .encoder
+                    .push(Header::Map(Some(entries.len() as u64)))?;
+                for (key, value) in entries {
+                    self.encoder.encoder.write_all(&key)?;
+                    self.encoder.encoder.write_all(&value)?;
+                }
+            }
+
             if self.ending {
-                self.encoder.0.push(Header::Break)?;
+                self.encoder.encoder.push(Header::Break)?;
+            }
+
+            if let Some(left) = self.encoder.raw.take() {
+                if left > 0 {
+                    return Err(Error::Value(
+                        "streamed bytes fall short of their length".into(),
+                    ));
+                }
             }
 
             Ok(())
@@ -335,10 +485,43 @@ macro_rules! end {
     };
 }
 
-struct CollectionSerializer<'a, W> {
+/// A serializer for the contents of arrays and maps
+///
+/// This type is returned by [`Serializer`] when serializing sequences,
+/// tuples, maps and structs.
+pub struct CollectionSerializer<'a, W> {
     encoder: &'a mut Serializer<W>,
     ending: bool,
     tag: bool,
+
+    // The encoded entries of a map to be written sorted by key
+    sorted: Option<Vec<(Vec<u8>, Vec<u8>)>>,
+}
+
+impl<'a, W: Write> CollectionSerializer<'a, W>
+where
+    W::Error: core::fmt::Debug,
+{
+    /// Returns a map serializer collecting its entries for sorting
+    #[inline]
+    fn sorted(encoder: &'a mut Serializer<W>) -> Self {
+        Self {
+            encoder,
+            ending: false,
+            tag: false,
+            sorted: Some(Vec::new()),
+        }
+    }
+}
+
+/// Encodes an item into memory for a sorted map
+fn encode<U: ?Sized + ser::Serialize, E>(item: &U, options: Options) -> Result<Vec<u8>, Error<E>> {
+    let mut bytes = Vec::new();
+    match into_writer_with_options(item, &mut bytes, options) {
+        Ok(()) => Ok(bytes),
+        Err(Error::Io(e)) => Err(Error::Value(format!("{:?}", e))),
+        Err(Error::Value(e)) => Err(Error::Value(e)),
+    }
 }
 
 impl<'a, W: Write> ser::SerializeSeq for CollectionSerializer<'a, W>
@@ -413,7 +596,7 @@ where
 
         self.tag = false;
         match value.serialize(crate::tag::Serializer) {
-            Ok(x) => Ok(self.encoder.0.push(Header::Tag(x))?),
+            Ok(x) => Ok(self.encoder.encoder.push(Header::Tag(x))?),
             _ => Err(Error::Value("expected tag".into())),
         }
     }
@@ -430,6 +613,11 @@ where
 
     #[inline]
     fn serialize_key<U: ?Sized + ser::Serialize>(&mut self, key: &U) -> Result<(), Self::Error> {
+        if let Some(entries) = &mut self.sorted {
+            entries.push((encode(key, self.encoder.options)?, Vec::new()));
+            return Ok(());
+        }
+
         key.serialize(&mut *self.encoder)
     }
 
@@ -438,6 +626,13 @@ where
         &mut self,
         value: &U,
     ) -> Result<(), Self::Error> {
+        if let Some(entries) = &mut self.sorted {
+            if let Some(entry) = entries.last_mut() {
+                entry.1 = encode(value, self.encoder.options)?;
+            }
+            return Ok(());
+        }
+
         value.serialize(&mut *self.encoder)
     }
 
@@ -457,7 +652,16 @@ where
         key: &'static str,
         value: &U,
     ) -> Result<(), Self::Error> {
-        key.serialize(&mut *self.encoder)?;
+        if let Some(entries) = &mut self.sorted {
+            let options = self.encoder.options;
+            entries.push((encode(key, options)?, encode(value, options)?));
+            return Ok(());
+        }
+
+        if !self.encoder.options.struct_as_array {
+            key.serialize(&mut *self.encoder)?;
+        }
+
         value.serialize(&mut *self.encoder)?;
         Ok(())
     }
@@ -478,7 +682,16 @@ where
         key: &'static str,
         value: &U,
     ) -> Result<(), Self::Error> {
-        key.serialize(&mut *self.encoder)?;
+        if let Some(entries) = &mut self.sorted {
+            let options = self.encoder.options;
+            entries.push((encode(key, options)?, encode(value, options)?));
+            return Ok(());
+        }
+
+        if !self.encoder.options.struct_as_array {
+            key.serialize(&mut *self.encoder)?;
+        }
+
         value.serialize(&mut *self.encoder)
     }
 
@@ -498,6 +711,223 @@ where
     value.serialize(&mut encoder)
 }
 
+/// Serializes as CBOR into a type with [`impl ciborium_io::Write`](ciborium_io::Write)
+/// using the specified [`Options`]
+#[inline]
+pub fn into_writer_with_options<T: ?Sized + ser::Serialize, W: Write>(
+    value: &T,
+    writer: W,
+    options: Options,
+) -> Result<(), Error<W::Error>>
+where
+    W::Error: core::fmt::Debug,
+{
+    let mut encoder = Serializer::with_options(writer, options);
+    value.serialize(&mut encoder)
+}
+
+/// Serializes as CBOR into a fixed buffer, returning the bytes written
+///
+/// Fails if the encoding does not fit, in which case the contents of the
+/// buffer are unspecified.
+///
+/// ```
+/// let mut buffer = [0u8; 16];
+/// let bytes = ciborium::ser::to_slice(&[1u8, 2, 3], &mut buffer).unwrap();
+/// assert_eq!(bytes, [0x83, 0x01, 0x02, 0x03]);
+///
+/// assert!(ciborium::ser::to_slice("too long", &mut buffer[..4]).is_err());
+/// ```
+#[inline]
+pub fn to_slice<'a, T: ?Sized + ser::Serialize>(
+    value: &T,
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8], Error<<&'a mut [u8] as Write>::Error>> {
+    let capacity = buffer.len();
+    let mut remaining = &mut buffer[..];
+    into_writer(value, &mut remaining)?;
+
+    let written = capacity - remaining.len();
+    Ok(&buffer[..written])
+}
+
+/// Serializes as CBOR into a type with [`impl
+/// ciborium_io::AsyncWrite`](ciborium_io::AsyncWrite)
+///
+/// The value is first encoded into `buffer`, and then written in one call,
+/// awaiting the writer. Fails if the encoding does not fit in the buffer.
+///
+/// This needs no runtime, so it can be used on embedded executors without
+/// `std`:
+///
+/// ```
+/// # embassy_futures::block_on(async {
+/// let mut output = Vec::new();
+/// let mut buffer = [0u8; 64];
+///
+/// ciborium::ser::into_async_writer(&[1u8, 2], &mut output, &mut buffer).await.unwrap();
+/// assert_eq!(output, [0x82, 0x01, 0x02]);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn into_async_writer<T: ?Sized + ser::Serialize, W: ciborium_io::AsyncWrite>(
+    value: &T,
+    writer: &mut W,
+    buffer: &mut [u8],
+) -> Result<(), Error<W::Error>> {
+    let bytes = to_slice(value, buffer).map_err(|e| match e {
+        Error::Io(..) => Error::Value("item does not fit in the buffer".into()),
+        Error::Value(msg) => Error::Value(msg),
+    })?;
+
+    Ok(writer.write_all(bytes).await?)
+}
+
+/// Serializes as CBOR into a type with [`impl ciborium_io::Write`](ciborium_io::Write)
+///
+/// This is [`into_writer()`] under the name used by `serde_json`.
+#[inline]
+pub fn to_writer<T: ?Sized + ser::Serialize, W: Write>(
+    value: &T,
+    writer: W,
+) -> Result<(), Error<W::Error>>
+where
+    W::Error: core::fmt::Debug,
+{
+    into_writer(value, writer)
+}
+
+/// Serializes as CBOR into a string of lowercase hex digits
+///
+/// ```
+/// let hex = ciborium::ser::to_hex_string(&(1, "a")).unwrap();
+/// assert_eq!(hex, "82016161");
+/// ```
+#[cfg(feature = "hex")]
+pub fn to_hex_string<T: ?Sized + ser::Serialize>(
+    value: &T,
+) -> Result<alloc::string::String, Error<<Vec<u8> as Write>::Error>> {
+    let mut bytes = Vec::new();
+    into_writer(value, &mut bytes)?;
+    Ok(hex::encode(bytes))
+}
+
+/// Serializes the items of an iterator as an indefinite-length array
+///
+/// Each item is written as soon as the iterator yields it, so that a long
+/// sequence never has to be collected first.
+///
+/// ```
+/// let mut bytes = Vec::new();
+/// ciborium::ser::into_writer_seq((1u8..=3).map(|x| x * 2), &mut bytes).unwrap();
+/// assert_eq!(bytes, [0x9f, 0x02, 0x04, 0x06, 0xff]);
+/// ```
+pub fn into_writer_seq<I, W>(iter: I, writer: W) -> Result<(), Error<W::Error>>
+where
+    I: IntoIterator,
+    I::Item: ser::Serialize,
+    W: Write,
+    W::Error: core::fmt::Debug,
+{
+    let mut serializer = Serializer::from(writer);
+    serializer.encoder.push(Header::Array(None))?;
+
+    for item in iter {
+        item.serialize(&mut serializer)?;
+    }
+
+    Ok(serializer.encoder.push(Header::Break)?)
+}
+
+/// Serializes the entries of an iterator as an indefinite-length map
+///
+/// Like [`into_writer_seq()`], each entry is written as soon as the
+/// iterator yields it. The keys are written in iteration order, and are
+/// not checked for duplicates.
+pub fn into_writer_map<I, K, V, W>(iter: I, writer: W) -> Result<(), Error<W::Error>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: ser::Serialize,
+    V: ser::Serialize,
+    W: Write,
+    W::Error: core::fmt::Debug,
+{
+    let mut serializer = Serializer::from(writer);
+    serializer.encoder.push(Header::Map(None))?;
+
+    for (key, value) in iter {
+        key.serialize(&mut serializer)?;
+        value.serialize(&mut serializer)?;
+    }
+
+    Ok(serializer.encoder.push(Header::Break)?)
+}
+
+/// Serializes the entries of an iterator as a map sorted by encoded key
+///
+/// The keys are encoded first and the entries then written to `serializer`
+/// in the given key `order`, as a definite-length map. This is the building
+/// block for deterministic encoding in custom `Serialize` implementations.
+/// Only the encoded keys are kept in memory: values are serialized straight
+/// to `serializer`. Repeated keys are rejected.
+///
+/// ```
+/// use ciborium::value::KeyOrder;
+/// use serde::{Serialize, Serializer};
+///
+/// struct Headers(Vec<(i64, &'static str)>);
+///
+/// impl Serialize for Headers {
+///     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+///         let entries = self.0.iter().map(|(k, v)| (k, v));
+///         ciborium::ser::serialize_sorted_map(entries, KeyOrder::Lexical, serializer)
+///     }
+/// }
+///
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&Headers(vec![(-1, "a"), (4, "b")]), &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xa2, 0x04, 0x61, 0x62, 0x20, 0x61, 0x61]);
+/// ```
+pub fn serialize_sorted_map<I, K, V, S>(
+    iter: I,
+    order: KeyOrder,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: ser::Serialize,
+    V: ser::Serialize,
+    S: ser::Serializer,
+{
+    use ser::{Error as _, SerializeMap};
+
+    let mut entries = Vec::new();
+    for (key, value) in iter {
+        let mut encoded = Vec::new();
+        match into_writer(&key, &mut encoded) {
+            Ok(()) => entries.push((encoded, key, value)),
+            Err(Error::Io(e)) => return Err(S::Error::custom(e)),
+            Err(Error::Value(e)) => return Err(S::Error::custom(e)),
+        }
+    }
+
+    entries.sort_by(|a, b| match order {
+        KeyOrder::LengthFirst => a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)),
+        KeyOrder::Lexical => a.0.cmp(&b.0),
+    });
+
+    if entries.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Err(S::Error::custom("duplicate map key"));
+    }
+
+    let mut map = serializer.serialize_map(Some(entries.len()))?;
+    for (_, key, value) in entries {
+        map.serialize_entry(&key, &value)?;
+    }
+
+    map.end()
+}
+
 #[cfg(feature = "std")]
 /// Serializes as CBOR into a new Vec<u8>
 #[inline]
@@ -509,3 +939,14 @@ pub fn into_vec<T: ?Sized + ser::Serialize>(
     value.serialize(&mut encoder)?;
     Ok(vector)
 }
+
+#[cfg(feature = "std")]
+/// Serializes as CBOR into a new Vec<u8>
+///
+/// This is [`into_vec()`] under the name used by `serde_json`.
+#[inline]
+pub fn to_vec<T: ?Sized + ser::Serialize>(
+    value: &T,
+) -> Result<Vec<u8>, Error<<Vec<u8> as ciborium_io::Write>::Error>> {
+    into_vec(value)
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// Options which control how CBOR is serialized
+///
+/// ```
+/// use ciborium::ser::{into_writer_with_options, Options};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: u8,
+///     y: u8,
+/// }
+///
+/// let mut bytes = Vec::new();
+/// let options = Options::new().struct_as_array(true);
+/// into_writer_with_options(&Point { x: 1, y: 2 }, &mut bytes, options).unwrap();
+/// assert_eq!(bytes, [0x82, 0x01, 0x02]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub(crate) struct_as_array: bool,
+    pub(crate) non_finite: bool,
+    pub(crate) variant_index: bool,
+    pub(crate) sort_maps: bool,
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Options {
+    /// Returns the default options
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            struct_as_array: false,
+            non_finite: true,
+            variant_index: false,
+            sort_maps: false,
+        }
+    }
+
+    /// Sets whether structs are encoded as arrays
+    ///
+    /// By default, structs are encoded as maps keyed by field name. When
+    /// enabled, structs (and struct variants) are instead encoded as
+    /// fixed-length arrays of their fields in declaration order. This is the
+    /// compact representation used by many constrained protocols.
+    ///
+    /// The deserializer accepts both representations. Note that fields
+    /// omitted with `#[serde(skip_serializing_if = "...")]` shift the
+    /// positions of all following fields, so such structs cannot round-trip
+    /// in this mode.
+    #[inline]
+    pub const fn struct_as_array(mut self, enabled: bool) -> Self {
+        self.struct_as_array = enabled;
+        self
+    }
+
+    /// Sets whether NaN and infinite floats may be serialized
+    ///
+    /// When disabled, serializing a non-finite float fails with
+    /// [`Error::Value`](super::Error::Value) instead of emitting it.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub const fn non_finite_floats(mut self, enabled: bool) -> Self {
+        self.non_finite = enabled;
+        self
+    }
+
+    /// Sets whether enum variants are identified by index
+    ///
+    /// By default, enum variants are identified by name: a unit variant is
+    /// encoded as its name, and any other variant as a single-entry map
+    /// keyed by its name. When enabled, the variant's zero-based index in
+    /// declaration order is used in place of the name, as in the compact
+    /// encodings of many C implementations. Reordering variants changes
+    /// their indices, so this is only suitable for enums whose order is
+    /// fixed.
+    ///
+    /// The deserializer accepts both representations for enum variants, but
+    /// struct fields are only ever matched by name.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn variant_index(mut self, enabled: bool) -> Self {
+        self.variant_index = enabled;
+        self
+    }
+
+    /// Sets whether map entries are sorted by key
+    ///
+    /// By default, the entries of maps and structs are written in the order
+    /// they are serialized, which for types like `HashMap` varies from run
+    /// to run. When enabled, they are instead sorted by the bytewise order
+    /// of their encoded keys, as in the core deterministic encoding of RFC
+    /// 8949 section 4.2.1, so that equal values always encode identically.
+    ///
+    /// Only maps are affected: each map's entries are encoded into memory
+    /// and written as a definite-length map once complete, while all other
+    /// items are still written as they are serialized. Unlike a full
+    /// canonical encoding, floats and lengths are left as they are.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub const fn sort_maps(mut self, enabled: bool) -> Self {
+        self.sort_maps = enabled;
+        self
+    }
+}
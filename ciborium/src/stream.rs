@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming large strings without holding them in memory
+//!
+//! A byte or text string may be far larger than the rest of a message, such
+//! as a file carried in a field of an upload request. Decoding it into a
+//! `Vec<u8>` holds all of it in memory at once; a [`BytesSink`] instead
+//! copies it into a writer as it is read, one scratch buffer at a time, and
+//! works with both definite and indefinite-length strings.
+//!
+//! ```
+//! use ciborium::{cbor, stream::BytesSink, Value};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Upload {
+//!     name: String,
+//!     body: BytesSink<Vec<u8>>,
+//! }
+//!
+//! let value = cbor!({ "name" => "a.txt", "body" => Value::Bytes(vec![7; 10000]) }).unwrap();
+//! let mut bytes = Vec::new();
+//! ciborium::into_writer(&value, &mut bytes).unwrap();
+//!
+//! let upload: Upload = ciborium::from_reader(&bytes[..]).unwrap();
+//! assert_eq!(upload.name, "a.txt");
+//! assert_eq!(upload.body.0, [7; 10000]);
+//! ```
+//!
+//! To stream into a writer which already exists, such as an open file, use
+//! a `BytesSink` as a [`DeserializeSeed`](serde::de::DeserializeSeed).
+//!
+//! In the other direction, a [`BytesSource`] serializes a byte string by
+//! reading it from a reader as it is written.
+
+use core::fmt;
+
+use ciborium_io::Write;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "std")]
+use serde::ser::{self, SerializeTupleStruct, Serializer};
+
+/// A byte or text string written into `W` as it is decoded
+///
+/// Under this crate's deserializer, the string is copied into the writer in
+/// chunks the size of the scratch buffer; text strings are validated as
+/// UTF-8 on the way. Other deserializers hand over the whole string at once,
+/// or an array of bytes, which is written all the same.
+///
+/// As a [`DeserializeSeed`], a `BytesSink` streams into the writer it wraps
+/// and returns it. Since `&mut W` is also a writer, the writer can be kept:
+///
+/// ```
+/// use ciborium::{de::from_reader_seed, stream::BytesSink};
+///
+/// let mut output = Vec::new();
+/// let input = [0x5f, 0x41, 1, 0x42, 2, 3, 0xff];
+/// from_reader_seed(&input[..], BytesSink(&mut output)).unwrap();
+/// assert_eq!(output, [1, 2, 3]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BytesSink<W>(pub W);
+
+impl<'de, W: Write + Default> Deserialize<'de> for BytesSink<W> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        BytesSink(W::default())
+            .deserialize(deserializer)
+            .map(BytesSink)
+    }
+}
+
+impl<'de, W: Write> DeserializeSeed<'de> for BytesSink<W> {
+    type Value = W;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<W, D::Error> {
+        deserializer.deserialize_newtype_struct("@@STREAM@@", self)
+    }
+}
+
+impl<'de, W: Write> Visitor<'de> for BytesSink<W> {
+    type Value = W;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        mut self,
+        deserializer: D,
+    ) -> Result<W, D::Error> {
+        deserializer.deserialize_byte_buf(Chunk(&mut self.0))?;
+        Ok(self.0)
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<W, A::Error> {
+        while seq.next_element_seed(Chunk(&mut self.0))?.is_some() {}
+        Ok(self.0)
+    }
+}
+
+/// Writes one chunk of a string, or one byte of an array, into the writer
+struct Chunk<'w, W>(&'w mut W);
+
+impl<W: Write> Chunk<'_, W> {
+    #[inline]
+    fn write<E: de::Error>(self, bytes: &[u8]) -> Result<(), E> {
+        self.0
+            .write_all(bytes)
+            .map_err(|_| E::custom("write failed"))
+    }
+}
+
+impl<'de, W: Write> DeserializeSeed<'de> for Chunk<'_, W> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, W: Write> Visitor<'de> for Chunk<'_, W> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<(), E> {
+        self.write(v)
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        self.write(v.as_bytes())
+    }
+
+    #[inline]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<(), E> {
+        match u8::try_from(v) {
+            Ok(byte) => self.write(&[byte]),
+            Err(..) => Err(E::invalid_value(de::Unexpected::Unsigned(v), &"a byte")),
+        }
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq.next_element_seed(Chunk(&mut *self.0))?.is_some() {}
+        Ok(())
+    }
+}
+
+/// A byte string read from `R` as it is encoded
+///
+/// Under this crate's serializer, a source made with
+/// [`BytesSource::new()`] is written as an indefinite-length byte string
+/// with a segment for each read, and one made with
+/// [`BytesSource::with_len()`] as a byte string of that length. Other
+/// serializers see a sequence of byte chunks, which [`BytesSink`] accepts.
+///
+/// Serializing reads the source to its end, so it can only be serialized
+/// once; the reader is shared through a [`RefCell`](core::cell::RefCell)
+/// since serialization only borrows the value.
+///
+/// ```
+/// use ciborium::stream::BytesSource;
+///
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&BytesSource::with_len(&[1, 2, 3][..], 3), &mut bytes).unwrap();
+/// assert_eq!(bytes, [0x43, 1, 2, 3]);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct BytesSource<R> {
+    reader: core::cell::RefCell<R>,
+    len: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> BytesSource<R> {
+    /// Streams all of `reader`, whose length is not known up front
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: reader.into(),
+            len: None,
+        }
+    }
+
+    /// Streams exactly `len` bytes of `reader`
+    ///
+    /// Serialization fails if the reader ends early.
+    #[inline]
+    pub fn with_len(reader: R, len: usize) -> Self {
+        Self {
+            reader: reader.into(),
+            len: Some(len),
+        }
+    }
+
+    /// Unwraps the reader
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ser::Serialize for BytesSource<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use ser::Error as _;
+
+        let mut reader = self
+            .reader
+            .try_borrow_mut()
+            .map_err(|_| S::Error::custom("source is already being read"))?;
+
+        let mut buffer = [0u8; 4096];
+        let mut left = self.len;
+        let mut seq = match self.len {
+            None => serializer.serialize_tuple_struct("@@STREAM@@", 0)?,
+            Some(len) => serializer.serialize_tuple_struct("@@STREAM_SIZED@@", len)?,
+        };
+
+        loop {
+            let size = left.map_or(buffer.len(), |left| left.min(buffer.len()));
+            if size == 0 {
+                break;
+            }
+
+            let read = match reader.read(&mut buffer[..size]) {
+                Ok(0) if left.is_some() => return Err(S::Error::custom("source ended early")),
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(S::Error::custom(format_args!("read failed: {}", e))),
+            };
+
+            seq.serialize_field(&Segment(&buffer[..read]))?;
+            left = left.map(|left| left - read);
+        }
+
+        seq.end()
+    }
+}
+
+/// One read of a [`BytesSource`], serialized as a byte string
+#[cfg(feature = "std")]
+struct Segment<'a>(&'a [u8]);
+
+#[cfg(feature = "std")]
+impl ser::Serialize for Segment<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
@@ -1,4 +1,10 @@
 //! Contains helper types for dealing with CBOR tags
+//!
+//! Other formats have no notion of tags. When used with a human-readable
+//! serializer or deserializer, such as JSON, these types are instead
+//! represented as a structure with `tag` and `value` fields.
+
+use core::marker::PhantomData;
 
 use serde::{de, de::Error as _, forward_to_deserialize_any, ser, Deserialize, Serialize};
 
@@ -12,6 +18,16 @@ enum Internal<T> {
     Tagged(u64, T),
 }
 
+/// The representation of a tagged item in human-readable formats
+///
+/// Formats other than CBOR do not understand the magic used by `Internal`.
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "Tag")]
+struct Plain<T> {
+    tag: Option<u64>,
+    value: T,
+}
+
 /// An optional CBOR tag and its data item
 ///
 /// No semantic evaluation of the tag is made.
@@ -21,6 +37,11 @@ pub struct Captured<V>(pub Option<u64>, pub V);
 impl<'de, V: Deserialize<'de>> Deserialize<'de> for Captured<V> {
     #[inline]
     fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let plain = Plain::deserialize(deserializer)?;
+            return Ok(Captured(plain.tag, plain.value));
+        }
+
         match Internal::deserialize(deserializer)? {
             Internal::Tagged(t, v) => Ok(Captured(Some(t), v)),
             Internal::Untagged(v) => Ok(Captured(None, v)),
@@ -31,6 +52,14 @@ impl<'de, V: Deserialize<'de>> Deserialize<'de> for Captured<V> {
 impl<V: Serialize> Serialize for Captured<V> {
     #[inline]
     fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let plain = Plain {
+                tag: self.0,
+                value: &self.1,
+            };
+            return plain.serialize(serializer);
+        }
+
         match self.0 {
             Some(tag) => Internal::Tagged(tag, &self.1).serialize(serializer),
             None => Internal::Untagged(&self.1).serialize(serializer),
@@ -49,6 +78,16 @@ pub struct Required<V, const TAG: u64>(pub V);
 impl<'de, V: Deserialize<'de>, const TAG: u64> Deserialize<'de> for Required<V, TAG> {
     #[inline]
     fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return match Plain::deserialize(deserializer)? {
+                Plain {
+                    tag: Some(t),
+                    value,
+                } if t == TAG => Ok(Required(value)),
+                _ => Err(de::Error::custom("required tag not found")),
+            };
+        }
+
         match Internal::deserialize(deserializer)? {
             Internal::Tagged(t, v) if t == TAG => Ok(Required(v)),
             _ => Err(de::Error::custom("required tag not found")),
@@ -59,6 +98,14 @@ impl<'de, V: Deserialize<'de>, const TAG: u64> Deserialize<'de> for Required<V,
 impl<V: Serialize, const TAG: u64> Serialize for Required<V, TAG> {
     #[inline]
     fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let plain = Plain {
+                tag: Some(TAG),
+                value: &self.0,
+            };
+            return plain.serialize(serializer);
+        }
+
         Internal::Tagged(TAG, &self.0).serialize(serializer)
     }
 }
@@ -74,6 +121,17 @@ pub struct Accepted<V, const TAG: u64>(pub V);
 impl<'de, V: Deserialize<'de>, const TAG: u64> Deserialize<'de> for Accepted<V, TAG> {
     #[inline]
     fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return match Plain::deserialize(deserializer)? {
+                Plain { tag: None, value } => Ok(Accepted(value)),
+                Plain {
+                    tag: Some(t),
+                    value,
+                } if t == TAG => Ok(Accepted(value)),
+                _ => Err(de::Error::custom("required tag not found")),
+            };
+        }
+
         match Internal::deserialize(deserializer)? {
             Internal::Tagged(t, v) if t == TAG => Ok(Accepted(v)),
             Internal::Untagged(v) => Ok(Accepted(v)),
@@ -85,10 +143,272 @@ impl<'de, V: Deserialize<'de>, const TAG: u64> Deserialize<'de> for Accepted<V,
 impl<V: Serialize, const TAG: u64> Serialize for Accepted<V, TAG> {
     #[inline]
     fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let plain = Plain {
+                tag: Some(TAG),
+                value: &self.0,
+            };
+            return plain.serialize(serializer);
+        }
+
         Internal::Tagged(TAG, &self.0).serialize(serializer)
     }
 }
 
+/// An optional CBOR tagged item with a default
+///
+/// This data type indicates that the specified tag, and **only** that tag,
+/// is accepted during deserialization, and that the item may be left out
+/// altogether. A null item, or a missing field marked `#[serde(default)]`,
+/// is deserialized as `V::default()`. During serialization, the tag is
+/// emitted unless the value equals the default, in which case null is
+/// emitted instead; a field marked
+/// `#[serde(skip_serializing_if = "Defaulted::is_default")]` is omitted.
+///
+/// ```
+/// use ciborium::tag::Defaulted;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Extension {
+///     id: u8,
+///     #[serde(default, skip_serializing_if = "Defaulted::is_default")]
+///     flags: Defaulted<u8, 42>,
+/// }
+///
+/// let plain = Extension { id: 1, flags: Defaulted(0) };
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&plain, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xa1, 0x62, b'i', b'd', 0x01]);
+/// assert_eq!(ciborium::from_reader::<Extension, _>(&bytes[..]).unwrap(), plain);
+///
+/// let flagged = Extension { id: 1, flags: Defaulted(7) };
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&flagged, &mut bytes).unwrap();
+/// assert_eq!(bytes[5..], [0x65, b'f', b'l', b'a', b'g', b's', 0xd8, 0x2a, 0x07]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Defaulted<V, const TAG: u64>(pub V);
+
+impl<V: Default + PartialEq, const TAG: u64> Defaulted<V, TAG> {
+    /// Returns whether the value equals the default
+    #[inline]
+    pub fn is_default(&self) -> bool {
+        self.0 == V::default()
+    }
+}
+
+impl<'de, V: Deserialize<'de> + Default, const TAG: u64> Deserialize<'de> for Defaulted<V, TAG> {
+    #[inline]
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<Required<V, TAG>>::deserialize(deserializer)? {
+            Some(Required(v)) => Ok(Defaulted(v)),
+            None => Ok(Defaulted(V::default())),
+        }
+    }
+}
+
+impl<V: Serialize + Default + PartialEq, const TAG: u64> Serialize for Defaulted<V, TAG> {
+    #[inline]
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.is_default() {
+            true => serializer.serialize_none(),
+            false => Required::<&V, TAG>(&self.0).serialize(serializer),
+        }
+    }
+}
+
+/// A set of CBOR tags
+///
+/// Implement this trait on a marker type to describe the tags accepted by
+/// [`RequiredOneOf`] and [`AcceptedOneOf`].
+///
+/// ```
+/// use ciborium::tag::{RequiredOneOf, TagSet};
+/// use ciborium::value::Value;
+///
+/// /// The standard date/time tags
+/// struct DateTime;
+///
+/// impl TagSet for DateTime {
+///     const TAGS: &'static [u64] = &[0, 1];
+/// }
+///
+/// let bytes = [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0];
+/// let date: RequiredOneOf<Value, DateTime> = ciborium::from_reader(&bytes[..]).unwrap();
+/// assert_eq!(date.tag(), 1);
+/// assert_eq!(date.value(), &Value::from(1363896240));
+/// ```
+pub trait TagSet {
+    /// The tags in the set
+    const TAGS: &'static [u64];
+}
+
+macro_rules! impltraits {
+    ($name:ident) => {
+        impl<V: Clone, S> Clone for $name<V, S> {
+            #[inline]
+            fn clone(&self) -> Self {
+                Self {
+                    tag: self.tag,
+                    value: self.value.clone(),
+                    set: PhantomData,
+                }
+            }
+        }
+
+        impl<V: Copy, S> Copy for $name<V, S> {}
+
+        impl<V: core::fmt::Debug, S> core::fmt::Debug for $name<V, S> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("tag", &self.tag)
+                    .field("value", &self.value)
+                    .finish()
+            }
+        }
+
+        impl<V: PartialEq, S> PartialEq for $name<V, S> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.tag == other.tag && self.value == other.value
+            }
+        }
+
+        impl<V: Eq, S> Eq for $name<V, S> {}
+
+        impl<V: core::hash::Hash, S> core::hash::Hash for $name<V, S> {
+            #[inline]
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.tag.hash(state);
+                self.value.hash(state);
+            }
+        }
+    };
+}
+
+/// A required CBOR tag from a set of tags
+///
+/// This data type indicates that one of the tags in `S` is required during
+/// deserialization. If the tag is missing or not in the set, deserialization
+/// will fail. The tag which was present is retained and emitted again during
+/// serialization.
+pub struct RequiredOneOf<V, S> {
+    tag: u64,
+    value: V,
+    set: PhantomData<S>,
+}
+
+impltraits!(RequiredOneOf);
+
+impl<V, S: TagSet> RequiredOneOf<V, S> {
+    /// Creates a tagged item, if `tag` is in the set
+    #[inline]
+    pub fn new(tag: u64, value: V) -> Option<Self> {
+        S::TAGS.contains(&tag).then_some(Self {
+            tag,
+            value,
+            set: PhantomData,
+        })
+    }
+
+    /// Returns the tag
+    #[inline]
+    pub fn tag(&self) -> u64 {
+        self.tag
+    }
+
+    /// Returns a reference to the data item
+    #[inline]
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Unwraps the data item
+    #[inline]
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<'de, V: Deserialize<'de>, S: TagSet> Deserialize<'de> for RequiredOneOf<V, S> {
+    #[inline]
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Captured::deserialize(deserializer)? {
+            Captured(Some(t), v) => Self::new(t, v),
+            Captured(None, ..) => None,
+        }
+        .ok_or_else(|| de::Error::custom("required tag not found"))
+    }
+}
+
+impl<V: Serialize, T: TagSet> Serialize for RequiredOneOf<V, T> {
+    #[inline]
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Captured(Some(self.tag), &self.value).serialize(serializer)
+    }
+}
+
+/// An optional CBOR tag from a set of tags
+///
+/// This data type indicates that one of the tags in `S` is accepted, but not
+/// required, during deserialization. The tag which was present, if any, is
+/// retained. During serialization, that tag is emitted again; if there was
+/// none, the first tag of the set is emitted.
+pub struct AcceptedOneOf<V, S> {
+    tag: Option<u64>,
+    value: V,
+    set: PhantomData<S>,
+}
+
+impltraits!(AcceptedOneOf);
+
+impl<V, S: TagSet> AcceptedOneOf<V, S> {
+    /// Creates an optionally tagged item, if `tag` is absent or in the set
+    #[inline]
+    pub fn new(tag: Option<u64>, value: V) -> Option<Self> {
+        tag.map_or(true, |t| S::TAGS.contains(&t)).then_some(Self {
+            tag,
+            value,
+            set: PhantomData,
+        })
+    }
+
+    /// Returns the tag, if one was present
+    #[inline]
+    pub fn tag(&self) -> Option<u64> {
+        self.tag
+    }
+
+    /// Returns a reference to the data item
+    #[inline]
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Unwraps the data item
+    #[inline]
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<'de, V: Deserialize<'de>, S: TagSet> Deserialize<'de> for AcceptedOneOf<V, S> {
+    #[inline]
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Captured(tag, value) = Captured::deserialize(deserializer)?;
+        Self::new(tag, value).ok_or_else(|| de::Error::custom("required tag not found"))
+    }
+}
+
+impl<V: Serialize, T: TagSet> Serialize for AcceptedOneOf<V, T> {
+    #[inline]
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tag = self.tag.or_else(|| T::TAGS.first().copied());
+        Captured(tag, &self.value).serialize(serializer)
+    }
+}
+
 pub(crate) struct TagAccess<D> {
     parent: Option<D>,
     state: usize,
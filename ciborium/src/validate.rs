@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validation of values against a schema built at runtime
+//!
+//! A [`Schema`] describes the expected structure of a value: its type, the
+//! keys of its maps, the items of its arrays and so on. Checking a value
+//! reports every place where it departs from the schema, each annotated
+//! with the [`Path`] to the offending item:
+//!
+//! ```
+//! use ciborium::{cbor, validate::Schema};
+//!
+//! let schema = Schema::map()
+//!     .required("alg", Schema::integer())
+//!     .required("kid", Schema::bytes())
+//!     .key("crit", Schema::array(Schema::text()));
+//!
+//! let value = cbor!({ "alg" => -7, "crit" => ["a", 1] }).unwrap();
+//! let violations = schema.validate(&value);
+//!
+//! let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+//! assert_eq!(messages, [
+//!     r#"$: missing required key "kid""#,
+//!     r#"$["crit"][1]: expected text, found integer"#,
+//! ]);
+//! ```
+//!
+//! This is a lightweight alternative to validating against a full CDDL
+//! specification; see also the [`cddl`](crate::cddl) module for exporting
+//! CDDL from Rust types.
+
+use crate::value::{Diagnostic, Path, Value};
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use ciborium_io::Read;
+use ciborium_ll::tag;
+
+/// A place where a value departs from a [`Schema`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    path: Path,
+    message: String,
+}
+
+impl Violation {
+    /// Returns the path to the offending item
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a description of the violation
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The expected structure of a value
+///
+/// Schemas are built from the constructors for each type, and maps are
+/// described further with [`key()`](Self::key) and
+/// [`required()`](Self::required). Tags are significant: a tagged item only
+/// matches a [`tagged()`](Self::tagged) schema, except that bignums also
+/// match [`integer()`](Self::integer).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema(Kind);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Any,
+    Bool,
+    Integer,
+    Float,
+    Bytes,
+    Text,
+    Null,
+    Exactly(Value),
+    Array(Box<Schema>),
+    Map {
+        keys: Vec<(Value, Schema, bool)>,
+        closed: bool,
+    },
+    Tagged(u64, Box<Schema>),
+    OneOf(Vec<Schema>),
+}
+
+impl Schema {
+    /// Returns a schema matching any value
+    #[inline]
+    pub fn any() -> Self {
+        Self(Kind::Any)
+    }
+
+    /// Returns a schema matching booleans
+    #[inline]
+    pub fn bool() -> Self {
+        Self(Kind::Bool)
+    }
+
+    /// Returns a schema matching integers, including bignums
+    #[inline]
+    pub fn integer() -> Self {
+        Self(Kind::Integer)
+    }
+
+    /// Returns a schema matching floats
+    #[inline]
+    pub fn float() -> Self {
+        Self(Kind::Float)
+    }
+
+    /// Returns a schema matching byte strings
+    #[inline]
+    pub fn bytes() -> Self {
+        Self(Kind::Bytes)
+    }
+
+    /// Returns a schema matching text strings
+    #[inline]
+    pub fn text() -> Self {
+        Self(Kind::Text)
+    }
+
+    /// Returns a schema matching null
+    #[inline]
+    pub fn null() -> Self {
+        Self(Kind::Null)
+    }
+
+    /// Returns a schema matching only values equal to `value`
+    #[inline]
+    pub fn exactly(value: impl Into<Value>) -> Self {
+        Self(Kind::Exactly(value.into()))
+    }
+
+    /// Returns a schema matching arrays whose items all match `items`
+    #[inline]
+    pub fn array(items: Schema) -> Self {
+        Self(Kind::Array(Box::new(items)))
+    }
+
+    /// Returns a schema matching maps
+    ///
+    /// Any keys are accepted until they are described with
+    /// [`key()`](Self::key) or [`required()`](Self::required).
+    #[inline]
+    pub fn map() -> Self {
+        Self(Kind::Map {
+            keys: Vec::new(),
+            closed: false,
+        })
+    }
+
+    /// Returns a schema matching items with tag `tag` wrapping an item
+    /// matching `inner`
+    #[inline]
+    pub fn tagged(tag: u64, inner: Schema) -> Self {
+        Self(Kind::Tagged(tag, Box::new(inner)))
+    }
+
+    /// Returns a schema matching values which match any of `alternatives`
+    #[inline]
+    pub fn one_of(alternatives: impl IntoIterator<Item = Schema>) -> Self {
+        Self(Kind::OneOf(alternatives.into_iter().collect()))
+    }
+
+    /// Describes an optional map entry
+    ///
+    /// If the map has an entry with key `key`, its value must match
+    /// `schema`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a [`map()`](Self::map) schema.
+    #[inline]
+    pub fn key(self, key: impl Into<Value>, schema: Schema) -> Self {
+        self.entry(key.into(), schema, false)
+    }
+
+    /// Describes a required map entry
+    ///
+    /// The map must have an entry with key `key`, and its value must match
+    /// `schema`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a [`map()`](Self::map) schema.
+    #[inline]
+    pub fn required(self, key: impl Into<Value>, schema: Schema) -> Self {
+        self.entry(key.into(), schema, true)
+    }
+
+    /// Rejects map entries whose keys are not described
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a [`map()`](Self::map) schema.
+    pub fn closed(mut self) -> Self {
+        match &mut self.0 {
+            Kind::Map { closed, .. } => *closed = true,
+            _ => panic!("closed() called on a schema which is not a map"),
+        }
+
+        self
+    }
+
+    fn entry(mut self, key: Value, schema: Schema, required: bool) -> Self {
+        match &mut self.0 {
+            Kind::Map { keys, .. } => keys.push((key, schema, required)),
+            _ => panic!("map entry described on a schema which is not a map"),
+        }
+
+        self
+    }
+
+    /// Returns every place where `value` departs from the schema
+    ///
+    /// The value is valid if none are returned.
+    pub fn validate(&self, value: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.check(value, &mut Path::new(), &mut violations);
+        violations
+    }
+
+    /// Decodes a value from `reader` and returns every place where it
+    /// departs from the schema
+    ///
+    /// The value is valid if none are returned.
+    pub fn validate_reader<R: Read>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<Violation>, crate::de::Error<R::Error>>
+    where
+        R::Error: fmt::Debug,
+    {
+        let value: Value = crate::de::from_reader(reader)?;
+        Ok(self.validate(&value))
+    }
+
+    fn check(&self, value: &Value, path: &mut Path, violations: &mut Vec<Violation>) {
+        let mut violation = |message: String| {
+            violations.push(Violation {
+                path: path.clone(),
+                message,
+            })
+        };
+
+        match (&self.0, value) {
+            (Kind::Any, ..)
+            | (Kind::Bool, Value::Bool(..))
+            | (Kind::Integer, Value::Integer(..))
+            | (Kind::Float, Value::Float(..))
+            | (Kind::Bytes, Value::Bytes(..))
+            | (Kind::Text, Value::Text(..))
+            | (Kind::Null, Value::Null) => (),
+
+            (Kind::Integer, Value::Tag(tag::BIGPOS | tag::BIGNEG, x)) if x.is_bytes() => (),
+
+            (Kind::Exactly(expected), value) => {
+                if value != expected {
+                    violation(format!("expected {}", Diagnostic(expected)));
+                }
+            }
+
+            (Kind::Array(items), Value::Array(x)) => {
+                for (index, item) in x.iter().enumerate() {
+                    path.push(index as u64);
+                    items.check(item, path, violations);
+                    path.pop();
+                }
+            }
+
+            (Kind::Map { keys, closed }, Value::Map(x)) => {
+                for (key, .., required) in keys {
+                    if *required && !x.iter().any(|(k, ..)| k == key) {
+                        violation(format!("missing required key {}", Diagnostic(key)));
+                    }
+                }
+
+                for (key, value) in x {
+                    path.push(key.clone());
+                    match keys.iter().find(|(k, ..)| k == key) {
+                        Some((.., schema, _)) => schema.check(value, path, violations),
+                        None if *closed => violations.push(Violation {
+                            path: path.clone(),
+                            message: "unexpected key".into(),
+                        }),
+                        None => (),
+                    }
+                    path.pop();
+                }
+            }
+
+            (Kind::Tagged(expected, inner), Value::Tag(tag, x)) if tag == expected => {
+                inner.check(x, path, violations)
+            }
+
+            (Kind::Tagged(expected, ..), value) => {
+                violation(format!("expected tag {}, found {}", expected, kind(value)))
+            }
+
+            (Kind::OneOf(alternatives), value) => {
+                if !alternatives.iter().any(|x| x.validate(value).is_empty()) {
+                    violation("matches none of the alternatives".into());
+                }
+            }
+
+            (expected, value) => {
+                let expected = match expected {
+                    Kind::Bool => "bool",
+                    Kind::Integer => "integer",
+                    Kind::Float => "float",
+                    Kind::Bytes => "bytes",
+                    Kind::Text => "text",
+                    Kind::Null => "null",
+                    Kind::Array(..) => "array",
+                    Kind::Map { .. } => "map",
+                    _ => unreachable!(),
+                };
+
+                violation(format!("expected {}, found {}", expected, kind(value)))
+            }
+        }
+    }
+}
+
+/// Returns the name of the type of `value`
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Integer(..) => "integer",
+        Value::Bytes(..) => "bytes",
+        Value::Float(..) => "float",
+        Value::Text(..) => "text",
+        Value::Bool(..) => "bool",
+        Value::Null => "null",
+        Value::Tag(..) => "tag",
+        Value::Array(..) => "array",
+        Value::Map(..) => "map",
+    }
+}
@@ -1,23 +1,108 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::value::Value;
-use alloc::vec::Vec;
+use ciborium_ll::{simple, Encoder, Header};
 use core::cmp::Ordering;
 use serde::{de, ser};
 
-/// Manually serialize values to compare them.
-fn serialized_canonical_cmp(v1: &Value, v2: &Value) -> Ordering {
-    // There is an optimization to be done here, but it would take a lot more code
-    // and using mixing keys, Arrays or Maps as CanonicalValue is probably not the
-    // best use of this type as it is meant mainly to be used as keys.
+/// Encodes the head of a value into `buffer`
+///
+/// Returns the encoded head along with the payload bytes which follow it.
+/// Scalars are encoded in full as their head, which is always a single
+/// header since integers never use the big integer encoding.
+fn head<'a>(value: &'a Value, buffer: &'a mut [u8; 9]) -> (&'a [u8], &'a [u8]) {
+    let header = match value {
+        Value::Bytes(x) => Some((Header::Bytes(Some(x.len() as u64)), &x[..])),
+        Value::Text(x) => Some((Header::Text(Some(x.len() as u64)), x.as_bytes())),
+        Value::Tag(t, ..) => Some((Header::Tag(*t), &[][..])),
+        Value::Array(x) => Some((Header::Array(Some(x.len() as u64)), &[][..])),
+        Value::Map(x) => Some((Header::Map(Some(x.len() as u64)), &[][..])),
+        Value::Float(x) => Some((Header::Float(*x), &[][..])),
+        Value::Bool(false) => Some((Header::Simple(simple::FALSE), &[][..])),
+        Value::Bool(true) => Some((Header::Simple(simple::TRUE), &[][..])),
+        Value::Null => Some((Header::Simple(simple::NULL), &[][..])),
+        _ => None,
+    };
+
+    let mut writer = &mut buffer[..];
+    let payload = match header {
+        Some((header, payload)) => {
+            let _ = Encoder::from(&mut writer).push(header);
+            payload
+        }
+
+        None => {
+            let _ = crate::ser::into_writer(value, &mut writer);
+            &[]
+        }
+    };
+
+    let len = 9 - writer.len();
+    (&buffer[..len], payload)
+}
+
+/// Computes the length of the encoded value without encoding it.
+fn encoded_len(value: &Value) -> usize {
+    let mut buffer = [0; 9];
+    let (head, payload) = head(value, &mut buffer);
+    let len = head.len() + payload.len();
+
+    match value {
+        Value::Tag(_, v) => len + encoded_len(v),
+        Value::Array(x) => len + x.iter().map(encoded_len).sum::<usize>(),
+        Value::Map(x) => {
+            len + x
+                .iter()
+                .map(|(k, v)| encoded_len(k) + encoded_len(v))
+                .sum::<usize>()
+        }
+        _ => len,
+    }
+}
+
+/// Compares the encoded bytes of two values without encoding them.
+///
+/// Since a CBOR item is never a prefix of another, the first differing head
+/// or payload decides the order of the whole encoding.
+pub(crate) fn encoded_bytes_cmp(v1: &Value, v2: &Value) -> Ordering {
+    let mut buffer1 = [0; 9];
+    let mut buffer2 = [0; 9];
+    let (h1, p1) = head(v1, &mut buffer1);
+    let (h2, p2) = head(v2, &mut buffer2);
+
+    // Equal heads imply equal types and lengths.
+    match h1.cmp(h2).then_with(|| p1.cmp(p2)) {
+        Ordering::Equal => (),
+        x => return x,
+    }
 
-    let mut bytes1 = Vec::new();
-    let _ = crate::ser::into_writer(v1, &mut bytes1);
-    let mut bytes2 = Vec::new();
-    let _ = crate::ser::into_writer(v2, &mut bytes2);
+    match (v1, v2) {
+        (Value::Tag(_, a), Value::Tag(_, b)) => encoded_bytes_cmp(a, b),
+        (Value::Array(a), Value::Array(b)) => encoded_items_cmp(a.iter(), b.iter()),
+        (Value::Map(a), Value::Map(b)) => {
+            let a = a.iter().flat_map(|(k, v)| [k, v]);
+            let b = b.iter().flat_map(|(k, v)| [k, v]);
+            encoded_items_cmp(a, b)
+        }
+        _ => Ordering::Equal,
+    }
+}
 
-    match bytes1.len().cmp(&bytes2.len()) {
-        Ordering::Equal => bytes1.cmp(&bytes2),
+/// Compares the concatenated encodings of two sequences of values.
+fn encoded_items_cmp<'a>(
+    i1: impl Iterator<Item = &'a Value>,
+    i2: impl Iterator<Item = &'a Value>,
+) -> Ordering {
+    i1.zip(i2)
+        .map(|(a, b)| encoded_bytes_cmp(a, b))
+        .find(|x| *x != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Compares two values as if they were serialized, without allocating.
+fn structural_canonical_cmp(v1: &Value, v2: &Value) -> Ordering {
+    match encoded_len(v1).cmp(&encoded_len(v2)) {
+        Ordering::Equal => encoded_bytes_cmp(v1, v2),
         x => x,
     }
 }
@@ -31,6 +116,13 @@ fn serialized_canonical_cmp(v1: &Value, v2: &Value) -> Ordering {
 ///     (byte-wise) lexical order sorts earlier.
 ///
 /// This specific comparison allows Maps and sorting that respect these two rules.
+///
+/// Floats are compared by their shortest lossless encoding, which is how
+/// they are serialized, so `1.5` (half precision) sorts before `0.1`
+/// (double precision). Keys of different types follow the same two rules:
+/// the major type sits in the first encoded byte, so among keys of equal
+/// length it decides the order. None of this requires encoding the values
+/// into memory.
 pub fn cmp_value(v1: &Value, v2: &Value) -> Ordering {
     use Value::*;
 
@@ -46,16 +138,17 @@ pub fn cmp_value(v1: &Value, v2: &Value) -> Ordering {
             Ordering::Equal => s.cmp(o),
             x => x,
         },
+        (Bytes(s), Bytes(o)) => s.len().cmp(&o.len()).then_with(|| s.cmp(o)),
+        (Float(..), Float(..)) => {
+            let mut buffer1 = [0; 9];
+            let mut buffer2 = [0; 9];
+            let (h1, ..) = head(v1, &mut buffer1);
+            let (h2, ..) = head(v2, &mut buffer2);
+            h1.len().cmp(&h2.len()).then_with(|| h1.cmp(h2))
+        }
         (Bool(s), Bool(o)) => s.cmp(o),
         (Null, Null) => Ordering::Equal,
-        (Tag(t, v), Tag(ot, ov)) => match Value::from(*t).partial_cmp(&Value::from(*ot)) {
-            Some(Ordering::Equal) | None => match v.partial_cmp(ov) {
-                Some(x) => x,
-                None => serialized_canonical_cmp(v1, v2),
-            },
-            Some(x) => x,
-        },
-        (_, _) => serialized_canonical_cmp(v1, v2),
+        (_, _) => structural_canonical_cmp(v1, v2),
     }
 }
 
@@ -5,10 +5,11 @@ use crate::tag::TagAccess;
 use super::{Error, Integer, Value};
 
 use alloc::{boxed::Box, string::String, vec::Vec};
+use core::borrow::Borrow;
 use core::iter::Peekable;
 
 use ciborium_ll::tag;
-use serde::de::{self, Deserializer as _};
+use serde::de::{self, Deserializer as _, IntoDeserializer};
 
 impl<'a> From<Integer> for de::Unexpected<'a> {
     #[inline]
@@ -177,7 +178,17 @@ impl<'de> de::Deserialize<'de> for Value {
 
 struct Deserializer<T>(T);
 
-impl<'a> Deserializer<&'a Value> {
+impl Deserializer<&Value> {
+    /// Deserializes the name of an enum variant, or its index
+    ///
+    /// Unlike struct fields, variants may be keyed by index.
+    fn variant<'de, V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Integer(..) => seed.deserialize(self.integer::<u64>("u64")?.into_deserializer()),
+            _ => seed.deserialize(self),
+        }
+    }
+
     fn integer<N>(&self, kind: &'static str) -> Result<N, Error>
     where
         N: TryFrom<u128>,
@@ -223,7 +234,7 @@ impl<'a> Deserializer<&'a Value> {
     }
 }
 
-impl<'a, 'de> de::Deserializer<'de> for Deserializer<&'a Value> {
+impl<'de> de::Deserializer<'de> for Deserializer<&Value> {
     type Error = Error;
 
     #[inline]
@@ -498,7 +509,7 @@ impl<'a, 'de> de::Deserializer<'de> for Deserializer<&'a Value> {
         match self.0 {
             Value::Tag(.., v) => Deserializer(v.as_ref()).deserialize_enum(name, variants, visitor),
             Value::Map(x) if x.len() == 1 => visitor.visit_enum(Deserializer(&x[0])),
-            x @ Value::Text(..) => visitor.visit_enum(Deserializer(x)),
+            x @ (Value::Text(..) | Value::Integer(..)) => visitor.visit_enum(Deserializer(x)),
             _ => Err(de::Error::invalid_type(self.0.into(), &"map")),
         }
     }
@@ -580,7 +591,7 @@ impl<'a, 'de> de::EnumAccess<'de> for Deserializer<&'a (Value, Value)> {
         self,
         seed: V,
     ) -> Result<(V::Value, Self::Variant), Self::Error> {
-        let k = seed.deserialize(Deserializer(&self.0 .0))?;
+        let k = Deserializer(&self.0 .0).variant(seed)?;
         Ok((k, Deserializer(&self.0 .1)))
     }
 }
@@ -594,12 +605,12 @@ impl<'a, 'de> de::EnumAccess<'de> for Deserializer<&'a Value> {
         self,
         seed: V,
     ) -> Result<(V::Value, Self::Variant), Self::Error> {
-        let k = seed.deserialize(self)?;
+        let k = self.variant(seed)?;
         Ok((k, Deserializer(&Value::Null)))
     }
 }
 
-impl<'a, 'de> de::VariantAccess<'de> for Deserializer<&'a Value> {
+impl<'de> de::VariantAccess<'de> for Deserializer<&Value> {
     type Error = Error;
 
     #[inline]
@@ -643,6 +654,138 @@ impl Value {
     pub fn deserialized<'de, T: de::Deserialize<'de>>(&self) -> Result<T, Error> {
         T::deserialize(Deserializer(self))
     }
+
+    /// Deserializes the `Value` using a [`DeserializeSeed`](de::DeserializeSeed)
+    ///
+    /// This is the counterpart of
+    /// [`from_reader_seed()`](crate::de::from_reader_seed) for values which
+    /// have already been decoded, so that stateful deserialization works
+    /// the same whatever the source:
+    ///
+    /// ```
+    /// use ciborium::{cbor, de::Interner};
+    ///
+    /// let value = cbor!({ "id" => 1, "ok" => true }).unwrap();
+    ///
+    /// let mut interner = Interner::new();
+    /// let map = value.deserialized_seed(interner.map::<ciborium::Value>()).unwrap();
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(interner.len(), 2);
+    /// ```
+    #[inline]
+    pub fn deserialized_seed<'de, S: de::DeserializeSeed<'de>>(
+        &self,
+        seed: S,
+    ) -> Result<S::Value, Error> {
+        seed.deserialize(Deserializer(self))
+    }
+
+    /// Decodes a value from a string of hex digits holding exactly one item
+    ///
+    /// See [`from_hex_str()`](crate::de::from_hex_str) for details.
+    #[cfg(feature = "hex")]
+    #[inline]
+    pub fn from_hex(
+        digits: &str,
+    ) -> Result<Self, crate::de::Error<<&'static [u8] as ciborium_io::Read>::Error>> {
+        crate::de::from_hex_str(digits)
+    }
+}
+
+/// A deserializer over a [`Value`] or a `&Value`
+///
+/// This is how a value is handed to serde wherever it expects a deserializer,
+/// and is what [`IntoDeserializer`](de::IntoDeserializer) returns for both.
+/// It deserializes just as [`Value::deserialized()`] does.
+///
+/// ```
+/// use ciborium::{cbor, value::Error};
+/// use serde::de::{Deserialize, IntoDeserializer};
+///
+/// let value = cbor!({ "x" => 1, "y" => 2 }).unwrap();
+///
+/// let deserializer = IntoDeserializer::<Error>::into_deserializer(&value);
+/// let map = <std::collections::BTreeMap<String, u8>>::deserialize(deserializer).unwrap();
+/// assert_eq!(map["y"], 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ValueDeserializer<V>(V);
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            #[inline]
+            fn $method<X: de::Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: X,
+            ) -> Result<X::Value, Self::Error> {
+                Deserializer(self.0.borrow()).$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Borrow<Value>> de::Deserializer<'de> for ValueDeserializer<V> {
+    type Error = Error;
+
+    forward! {
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_i128()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_u128()
+        deserialize_f32()
+        deserialize_f64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_option()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_map()
+        deserialize_struct(name: &'static str, fields: &'static [&'static str])
+        deserialize_enum(name: &'static str, variants: &'static [&'static str])
+        deserialize_identifier()
+        deserialize_ignored_any()
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        Deserializer(self.0.borrow()).is_human_readable()
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, Error> for Value {
+    type Deserializer = ValueDeserializer<Value>;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer(self)
+    }
+}
+
+impl<'de, 'a> de::IntoDeserializer<'de, Error> for &'a Value {
+    type Deserializer = ValueDeserializer<&'a Value>;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer(self)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Pretty, Value};
+
+use core::fmt::{self, Display, Formatter, Write};
+
+/// A [`Value`] formatted in CBOR diagnostic notation
+///
+/// Diagnostic notation is the human-readable text form of CBOR defined in
+/// RFC 8949 Section 8. It is returned by [`Value::diagnostic()`]. With the
+/// alternate flag (`{:#}`), it is written as an indented tree, as by
+/// [`Value::pretty()`].
+///
+/// ```
+/// use ciborium::cbor;
+///
+/// let value = cbor!({ "a" => [1, -2.5, null], 3 => ciborium::Value::Bytes(vec![0xde, 0xad]) }).unwrap();
+/// assert_eq!(value.diagnostic().to_string(), r#"{"a": [1, -2.5, null], 3: h'dead'}"#);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Diagnostic<'a>(pub(crate) &'a Value);
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return Display::fmt(&Pretty::new(self.0), f);
+        }
+
+        match self.0 {
+            Value::Integer(x) => write!(f, "{}", i128::from(*x)),
+
+            Value::Bytes(x) => {
+                f.write_str("h'")?;
+                for byte in x {
+                    write!(f, "{:02x}", byte)?;
+                }
+                f.write_char('\'')
+            }
+
+            Value::Float(x) if x.is_nan() => f.write_str("NaN"),
+            Value::Float(x) if x.is_infinite() && *x > 0.0 => f.write_str("Infinity"),
+            Value::Float(x) if x.is_infinite() => f.write_str("-Infinity"),
+            Value::Float(x) => write!(f, "{:?}", x),
+
+            Value::Text(x) => {
+                f.write_char('"')?;
+                for c in x.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
+
+            Value::Bool(x) => write!(f, "{}", x),
+            Value::Null => f.write_str("null"),
+            Value::Tag(t, v) => write!(f, "{}({})", t, Diagnostic(v)),
+
+            Value::Array(x) => {
+                f.write_char('[')?;
+                for (i, v) in x.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", Diagnostic(v))?;
+                }
+                f.write_char(']')
+            }
+
+            Value::Map(x) => {
+                f.write_char('{')?;
+                for (i, (k, v)) in x.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", Diagnostic(k), Diagnostic(v))?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Diagnostic, Value};
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter, Write};
+
+/// The first place where two values differ
+///
+/// This is returned by [`diff()`], and is what
+/// [`assert_cbor_eq!`](crate::assert_cbor_eq) reports on failure. When
+/// displayed, it shows the path to the differing item along with both
+/// versions of it in diagnostic notation:
+///
+/// ```
+/// use ciborium::{cbor, value::diff};
+///
+/// let expected = cbor!({ "a" => [1, 2] }).unwrap();
+/// let actual = cbor!({ "a" => [1, 3] }).unwrap();
+///
+/// let difference = diff(&expected, &actual).unwrap();
+/// assert_eq!(difference.path(), r#"$["a"][1]"#);
+/// assert_eq!(
+///     difference.to_string(),
+///     "CBOR differs at $[\"a\"][1]\n  expected: 2\n    actual: 3",
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference<'a> {
+    path: String,
+    expected: Option<&'a Value>,
+    actual: Option<&'a Value>,
+    note: Option<&'static str>,
+}
+
+impl<'a> Difference<'a> {
+    /// Returns the path to the differing item
+    ///
+    /// The path starts at `$`, and descends into arrays by index and into
+    /// maps by key, both written in brackets. Keys are written in diagnostic
+    /// notation.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the expected item, or `None` if it is missing
+    pub fn expected(&self) -> Option<&'a Value> {
+        self.expected
+    }
+
+    /// Returns the actual item, or `None` if it is missing
+    pub fn actual(&self) -> Option<&'a Value> {
+        self.actual
+    }
+}
+
+impl Display for Difference<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "CBOR differs at {}", self.path)?;
+        if let Some(note) = self.note {
+            write!(f, " ({})", note)?;
+        }
+
+        for (name, value) in [("expected", self.expected), ("actual", self.actual)] {
+            match value {
+                Some(value) => write!(f, "\n  {:>8}: {}", name, Diagnostic(value))?,
+                None => write!(f, "\n  {:>8}: <missing>", name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the first difference between two values, if any
+///
+/// Unlike `==`, floats are compared by their bits, so that identical NaNs
+/// compare equal while `0.0` and `-0.0` do not, as their encodings differ.
+/// Maps are compared by key, and a map which has the same entries in a
+/// different order is reported as such.
+pub fn diff<'a>(expected: &'a Value, actual: &'a Value) -> Option<Difference<'a>> {
+    let mut path = String::from("$");
+    difference(&mut path, expected, actual)
+}
+
+fn difference<'a>(path: &mut String, e: &'a Value, a: &'a Value) -> Option<Difference<'a>> {
+    let mismatch = |path: &mut String, note| {
+        Some(Difference {
+            path: path.clone(),
+            expected: Some(e),
+            actual: Some(a),
+            note,
+        })
+    };
+
+    match (e, a) {
+        (Value::Float(x), Value::Float(y)) if x.to_bits() == y.to_bits() => None,
+        (Value::Float(..), Value::Float(..)) => mismatch(path, None),
+
+        (Value::Tag(x, ..), Value::Tag(y, ..)) if x != y => mismatch(path, None),
+        (Value::Tag(_, x), Value::Tag(_, y)) => difference(path, x, y),
+
+        (Value::Array(x), Value::Array(y)) => {
+            for i in 0..x.len().max(y.len()) {
+                let len = path.len();
+                let _ = write!(path, "[{}]", i);
+                let found = match (x.get(i), y.get(i)) {
+                    (Some(x), Some(y)) => difference(path, x, y),
+                    (x, y) => Some(Difference {
+                        path: path.clone(),
+                        expected: x,
+                        actual: y,
+                        note: None,
+                    }),
+                };
+                if found.is_some() {
+                    return found;
+                }
+                path.truncate(len);
+            }
+
+            None
+        }
+
+        (Value::Map(x), Value::Map(y)) => {
+            let find = |map: &'a Vec<(Value, Value)>, key: &Value| {
+                map.iter()
+                    .find(|(k, ..)| diff(k, key).is_none())
+                    .map(|(.., v)| v)
+            };
+
+            let missing = x.iter().map(|(k, v)| (k, Some(v), find(y, k)));
+            let extra = y
+                .iter()
+                .filter(|(k, ..)| find(x, k).is_none())
+                .map(|(k, v)| (k, None, Some(v)));
+
+            for (k, x, y) in missing.chain(extra) {
+                let len = path.len();
+                let _ = write!(path, "[{}]", Diagnostic(k));
+                let found = match (x, y) {
+                    (Some(x), Some(y)) => difference(path, x, y),
+                    (x, y) => Some(Difference {
+                        path: path.clone(),
+                        expected: x,
+                        actual: y,
+                        note: None,
+                    }),
+                };
+                if found.is_some() {
+                    return found;
+                }
+                path.truncate(len);
+            }
+
+            let reordered = x
+                .iter()
+                .zip(y)
+                .any(|((a, ..), (b, ..))| diff(a, b).is_some());
+            match x.len() == y.len() && reordered {
+                true => mismatch(path, Some("keys in a different order")),
+                false => None,
+            }
+        }
+
+        (x, y) if x == y => None,
+        _ => mismatch(path, None),
+    }
+}
+
+/// A value which `assert_cbor_eq!` can compare
+#[doc(hidden)]
+pub trait Compare {
+    /// Returns the value along with its encoding, if it was given encoded
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>);
+}
+
+impl Compare for Value {
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>) {
+        (Cow::Borrowed(self), None)
+    }
+}
+
+impl Compare for [u8] {
+    #[track_caller]
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>) {
+        match crate::de::from_reader(self) {
+            Ok(value) => (Cow::Owned(value), Some(self)),
+            Err(e) => panic!("invalid CBOR ({:?}): {}", e, Hex(self)),
+        }
+    }
+}
+
+impl<const N: usize> Compare for [u8; N] {
+    #[track_caller]
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>) {
+        self[..].decode()
+    }
+}
+
+impl Compare for Vec<u8> {
+    #[track_caller]
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>) {
+        self[..].decode()
+    }
+}
+
+impl<T: Compare + ?Sized> Compare for &T {
+    #[track_caller]
+    fn decode(&self) -> (Cow<'_, Value>, Option<&[u8]>) {
+        (**self).decode()
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_eq<E: Compare + ?Sized, A: Compare + ?Sized>(expected: &E, actual: &A) {
+    let (e, ebytes) = expected.decode();
+    let (a, abytes) = actual.decode();
+
+    if let Some(difference) = diff(&e, &a) {
+        panic!("assertion failed: {}", difference);
+    }
+
+    if let (Some(e), Some(a)) = (ebytes, abytes) {
+        if e != a {
+            panic!(
+                "assertion failed: CBOR encodes the same value differently\n  expected: {}\n    actual: {}",
+                Hex(e),
+                Hex(a),
+            );
+        }
+    }
+}
@@ -1,5 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
-use core::cmp::Ordering;
+use core::{cmp::Ordering, fmt, num::ParseIntError, str::FromStr};
 
 macro_rules! implfrom {
     ($( $(#[$($attr:meta)+])? $t:ident)+) => {
@@ -35,6 +35,71 @@ macro_rules! implfrom {
 pub struct Integer(i128);
 
 impl Integer {
+    /// The smallest integer, -2<sup>64</sup>
+    pub const MIN: Integer = Integer(-1 - u64::MAX as i128);
+
+    /// The largest integer, 2<sup>64</sup> - 1
+    pub const MAX: Integer = Integer(u64::MAX as i128);
+
+    /// Returns whether the integer is less than zero
+    #[inline]
+    pub fn is_negative(self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Returns whether the integer is greater than zero
+    #[inline]
+    pub fn is_positive(self) -> bool {
+        self.0.is_positive()
+    }
+
+    /// Adds two integers, returning `None` if the result is out of range
+    ///
+    /// ```
+    /// use ciborium::value::Integer;
+    ///
+    /// assert_eq!(Integer::from(1).checked_add(2.into()), Some(3.into()));
+    /// assert_eq!(Integer::MAX.checked_add(1.into()), None);
+    /// ```
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::try_from(self.0 + rhs.0).ok()
+    }
+
+    /// Subtracts two integers, returning `None` if the result is out of range
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::try_from(self.0 - rhs.0).ok()
+    }
+
+    /// Multiplies two integers, returning `None` if the result is out of range
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::try_from(self.0.checked_mul(rhs.0)?).ok()
+    }
+
+    /// Negates the integer, returning `None` if the result is out of range
+    ///
+    /// Only [`Integer::MIN`] has no negation.
+    #[inline]
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::try_from(-self.0).ok()
+    }
+
+    /// Returns the absolute value, or `None` if it is out of range
+    ///
+    /// Only [`Integer::MIN`] has no absolute value.
+    #[inline]
+    pub fn checked_abs(self) -> Option<Self> {
+        Self::try_from(self.0.abs()).ok()
+    }
+
+    /// Returns the absolute value, which is always representable as a `u128`
+    #[inline]
+    pub fn unsigned_abs(self) -> u128 {
+        self.0.unsigned_abs()
+    }
+
     /// Returns the canonical length this integer will have when serialized to bytes.
     /// This is called `canonical` as it is only used for canonically comparing two
     /// values. It shouldn't be used in any other context.
@@ -92,6 +157,56 @@ impl Integer {
     }
 }
 
+impl fmt::Display for Integer {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The error when parsing an [`Integer`] from a string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIntegerError(Option<ParseIntError>);
+
+impl fmt::Display for ParseIntegerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(e) => fmt::Display::fmt(e, f),
+            None => f.write_str("number too large to fit in an integer"),
+        }
+    }
+}
+
+impl serde::de::StdError for ParseIntegerError {}
+
+impl FromStr for Integer {
+    type Err = ParseIntegerError;
+
+    /// Parses a decimal integer, as with `i128::from_str()`
+    ///
+    /// ```
+    /// use ciborium::value::Integer;
+    ///
+    /// let integer: Integer = "-18446744073709551616".parse().unwrap();
+    /// assert_eq!(i128::from(integer), -(1 << 64));
+    /// assert!("18446744073709551616".parse::<Integer>().is_err());
+    /// ```
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = i128::from_str(s).map_err(|e| ParseIntegerError(Some(e)))?;
+        Integer::try_from(value).map_err(|_| ParseIntegerError(None))
+    }
+}
+
+impl TryFrom<&str> for Integer {
+    type Error = ParseIntegerError;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 implfrom! {
     u8 u16 u32 u64
     i8 i16 i32 i64
@@ -122,7 +237,7 @@ impl TryFrom<u128> for Integer {
 
     #[inline]
     fn try_from(value: u128) -> Result<Self, Self::Error> {
-        Ok(Self(u64::try_from(value)?.into()))
+        Ok(u64::try_from(value)?.into())
     }
 }
 
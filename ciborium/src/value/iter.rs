@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{untagged, untagged_mut, Value};
+
+use alloc::vec::{self, Vec};
+use core::iter::FusedIterator;
+use core::slice;
+
+macro_rules! iterator {
+    ($(#[$meta:meta])* $name:ident<$a:lifetime>: $inner:ty => $item:ty, |$x:ident| $map:expr) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct $name<$a>($inner);
+
+        impl<$a> Iterator for $name<$a> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map(|$x| $map)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<$a> DoubleEndedIterator for $name<$a> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back().map(|$x| $map)
+            }
+        }
+
+        impl<$a> ExactSizeIterator for $name<$a> {}
+        impl<$a> FusedIterator for $name<$a> {}
+    };
+}
+
+iterator! {
+    /// An iterator over the items of an array, returned by [`Value::iter()`]
+    Iter<'a>: slice::Iter<'a, Value> => &'a Value, |x| x
+}
+
+iterator! {
+    /// A mutable iterator over the items of an array, returned by
+    /// [`Value::iter_mut()`]
+    IterMut<'a>: slice::IterMut<'a, Value> => &'a mut Value, |x| x
+}
+
+iterator! {
+    /// An iterator over the keys of a map, returned by [`Value::keys()`]
+    Keys<'a>: slice::Iter<'a, (Value, Value)> => &'a Value, |x| &x.0
+}
+
+iterator! {
+    /// An iterator over the values of a map, returned by [`Value::values()`]
+    Values<'a>: slice::Iter<'a, (Value, Value)> => &'a Value, |x| &x.1
+}
+
+iterator! {
+    /// A mutable iterator over the values of a map, returned by
+    /// [`Value::values_mut()`]
+    ValuesMut<'a>: slice::IterMut<'a, (Value, Value)> => &'a mut Value, |x| &mut x.1
+}
+
+iterator! {
+    /// An iterator over the entries of a map, returned by [`Value::entries()`]
+    Entries<'a>: slice::Iter<'a, (Value, Value)> => (&'a Value, &'a Value), |x| (&x.0, &x.1)
+}
+
+iterator! {
+    /// An iterator over the entries of a map with mutable values, returned by
+    /// [`Value::entries_mut()`]
+    EntriesMut<'a>: slice::IterMut<'a, (Value, Value)> => (&'a Value, &'a mut Value), |x| (&x.0, &mut x.1)
+}
+
+/// An iterator over the items of an array, returned by
+/// [`Value::into_iter()`](IntoIterator::into_iter)
+#[derive(Debug)]
+pub struct IntoIter(vec::IntoIter<Value>);
+
+impl Iterator for IntoIter {
+    type Item = Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Value> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Value> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+impl FusedIterator for IntoIter {}
+
+/// Returns the items of an array, or nothing for other values
+fn items(value: &Value) -> &[Value] {
+    match untagged(value) {
+        Value::Array(x) => x,
+        _ => &[],
+    }
+}
+
+/// Returns the items of an array mutably, or nothing for other values
+fn items_mut(value: &mut Value) -> &mut [Value] {
+    match untagged_mut(value) {
+        Value::Array(x) => x,
+        _ => &mut [],
+    }
+}
+
+/// Returns the entries of a map, or nothing for other values
+fn entries(value: &Value) -> &[(Value, Value)] {
+    match untagged(value) {
+        Value::Map(x) => x,
+        _ => &[],
+    }
+}
+
+/// Returns the entries of a map mutably, or nothing for other values
+fn entries_mut(value: &mut Value) -> &mut [(Value, Value)] {
+    match untagged_mut(value) {
+        Value::Map(x) => x,
+        _ => &mut [],
+    }
+}
+
+impl Value {
+    /// Returns an iterator over the items of an array
+    ///
+    /// Tags around the array are looked through. Values which are not arrays
+    /// have no items.
+    ///
+    /// ```
+    /// # use ciborium::cbor;
+    /// #
+    /// let value = cbor!([1, 2, 3]).unwrap();
+    ///
+    /// let sum: i128 = value.iter().map(|x| i128::from(x.as_integer().unwrap())).sum();
+    /// assert_eq!(sum, 6);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(items(self).iter())
+    }
+
+    /// Returns a mutable iterator over the items of an array
+    ///
+    /// See [`Value::iter()`] for which values have items.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut(items_mut(self).iter_mut())
+    }
+
+    /// Returns an iterator over the keys of a map
+    ///
+    /// Tags around the map are looked through. Values which are not maps have
+    /// no entries.
+    ///
+    /// ```
+    /// # use ciborium::cbor;
+    /// #
+    /// let value = cbor!({ "a" => 1, "b" => 2 }).unwrap();
+    ///
+    /// let keys: Vec<_> = value.keys().filter_map(|k| k.as_text()).collect();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_> {
+        Keys(entries(self).iter())
+    }
+
+    /// Returns an iterator over the values of a map
+    ///
+    /// See [`Value::keys()`] for which values have entries.
+    #[inline]
+    pub fn values(&self) -> Values<'_> {
+        Values(entries(self).iter())
+    }
+
+    /// Returns a mutable iterator over the values of a map
+    ///
+    /// See [`Value::keys()`] for which values have entries.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_> {
+        ValuesMut(entries_mut(self).iter_mut())
+    }
+
+    /// Returns an iterator over the keys and values of a map
+    ///
+    /// See [`Value::keys()`] for which values have entries.
+    #[inline]
+    pub fn entries(&self) -> Entries<'_> {
+        Entries(entries(self).iter())
+    }
+
+    /// Returns an iterator over the keys and mutable values of a map
+    ///
+    /// Keys cannot be changed through the iterator, since that could leave
+    /// the map with duplicate keys. See [`Value::keys()`] for which values
+    /// have entries.
+    ///
+    /// ```
+    /// # use ciborium::{cbor, Value};
+    /// #
+    /// let mut value = cbor!({ "a" => 1, "b" => 2 }).unwrap();
+    ///
+    /// for (key, value) in value.entries_mut() {
+    ///     if *key == "b" {
+    ///         *value = Value::Null;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(value, cbor!({ "a" => 1, "b" => null }).unwrap());
+    /// ```
+    #[inline]
+    pub fn entries_mut(&mut self) -> EntriesMut<'_> {
+        EntriesMut(entries_mut(self).iter_mut())
+    }
+}
+
+impl IntoIterator for Value {
+    type Item = Value;
+    type IntoIter = IntoIter;
+
+    /// Returns an iterator over the items of an array
+    ///
+    /// See [`Value::iter()`] for which values have items.
+    #[inline]
+    fn into_iter(self) -> IntoIter {
+        let items = match self {
+            Value::Tag(_, value) => return value.into_iter(),
+            Value::Array(x) => x,
+            _ => Vec::new(),
+        };
+
+        IntoIter(items.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a Value {
+    type Item = &'a Value;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Value {
+    type Item = &'a mut Value;
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a> {
+        self.iter_mut()
+    }
+}
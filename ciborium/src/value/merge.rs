@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::Value;
+
+use alloc::vec::Vec;
+
+impl Value {
+    /// Applies a merge patch to this value
+    ///
+    /// This implements the algorithm of [RFC 7396] (JSON Merge Patch) over
+    /// CBOR maps, which may have keys of any type. If `patch` is a map, each
+    /// of its entries updates the entry with an equal key: null removes the
+    /// entry, a map is merged into the existing value recursively and any
+    /// other value replaces it. If this value is not a map, it is replaced
+    /// by an empty map before merging. If `patch` is not a map, it replaces
+    /// this value entirely.
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// let mut value = cbor!({ "title" => "Goodbye!", "author" => { "given" => "John", "family" => "Doe" } }).unwrap();
+    /// let patch = cbor!({ "title" => "Hello!", "author" => { "family" => null } }).unwrap();
+    ///
+    /// value.apply_merge_patch(&patch);
+    /// assert_eq!(value, cbor!({ "title" => "Hello!", "author" => { "given" => "John" } }).unwrap());
+    /// ```
+    ///
+    /// [RFC 7396]: https://www.rfc-editor.org/rfc/rfc7396
+    pub fn apply_merge_patch(&mut self, patch: &Value) {
+        let entries = match patch {
+            Value::Map(entries) => entries,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+
+        if !self.is_map() {
+            *self = Value::Map(Vec::new());
+        }
+
+        if let Value::Map(map) = self {
+            for (key, value) in entries {
+                let index = map.iter().position(|(k, ..)| k == key);
+
+                match (value, index) {
+                    (Value::Null, Some(index)) => {
+                        map.remove(index);
+                    }
+                    (Value::Null, None) => (),
+                    (value, Some(index)) => map[index].1.apply_merge_patch(value),
+                    (value, None) => {
+                        let mut new = Value::Null;
+                        new.apply_merge_patch(value);
+                        map.push((key.clone(), new));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,12 +6,36 @@ mod canonical;
 mod integer;
 
 mod de;
+mod diag;
+mod diff;
 mod error;
+mod iter;
+mod merge;
+mod path;
+mod pointer;
+mod pretty;
+mod query;
 mod ser;
+mod sort;
+mod walk;
 
 pub use canonical::CanonicalValue;
+pub use de::ValueDeserializer;
+pub use diag::Diagnostic;
+pub use diff::{diff, Difference};
 pub use error::Error;
-pub use integer::Integer;
+pub use integer::{Integer, ParseIntegerError};
+pub use iter::{Entries, EntriesMut, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+pub use path::Path;
+pub use pretty::{ByteFormat, Pretty};
+pub use query::Query;
+pub use sort::{Duplicates, KeyOrder};
+
+#[doc(hidden)]
+pub use diff::{assert_eq, Compare};
+
+pub(crate) use path::{get_mut, untagged, untagged_mut};
+pub(crate) use pointer::{array_index, integer_key};
 
 use alloc::{boxed::Box, string::String, vec::Vec};
 
@@ -215,6 +239,92 @@ impl Value {
         }
     }
 
+    /// Returns the value as a `u64` if it is an `Integer` or an integral
+    /// `Float` within range. Returns None otherwise.
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// assert_eq!(Value::Integer(17.into()).as_u64(), Some(17));
+    /// assert_eq!(Value::Float(17.0).as_u64(), Some(17));
+    /// assert_eq!(Value::Float(17.5).as_u64(), None);
+    /// assert_eq!(Value::Integer((-1).into()).as_u64(), None);
+    /// ```
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Integer(int) => u64::try_from(int).ok(),
+            Value::Float(f) if (0.0..18446744073709551616.0).contains(&f) => {
+                let x = f as u64;
+                (x as f64 == f).then_some(x)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `i64` if it is an `Integer` or an integral
+    /// `Float` within range. Returns None otherwise.
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// assert_eq!(Value::Integer((-17).into()).as_i64(), Some(-17));
+    /// assert_eq!(Value::Float(-17.0).as_i64(), Some(-17));
+    /// assert_eq!(Value::Float(f64::NAN).as_i64(), None);
+    /// assert_eq!(Value::Integer(u64::MAX.into()).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Integer(int) => i64::try_from(int).ok(),
+            Value::Float(f) if (-9223372036854775808.0..9223372036854775808.0).contains(&f) => {
+                let x = f as i64;
+                (x as f64 == f).then_some(x)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `f64` if it is a `Float` or an `Integer` which
+    /// an `f64` represents exactly. Returns None otherwise.
+    ///
+    /// See [`Value::as_f64_lossy`] to round large integers instead.
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// assert_eq!(Value::Float(1.5).as_f64(), Some(1.5));
+    /// assert_eq!(Value::Integer(17.into()).as_f64(), Some(17.0));
+    /// assert_eq!(Value::Integer(u64::MAX.into()).as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(f) => Some(f),
+            Value::Integer(int) => {
+                let x = i128::from(int);
+                let f = x as f64;
+                (f as i128 == x).then_some(f)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `f64` if it is a `Float` or an `Integer`,
+    /// rounding integers to the nearest representable `f64`. Returns None
+    /// otherwise.
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// assert_eq!(Value::Integer(u64::MAX.into()).as_f64_lossy(), Some(18446744073709551615.0));
+    /// assert_eq!(Value::Text("1.5".into()).as_f64_lossy(), None);
+    /// ```
+    pub fn as_f64_lossy(&self) -> Option<f64> {
+        match *self {
+            Value::Float(f) => Some(f),
+            Value::Integer(int) => Some(i128::from(int) as f64),
+            _ => None,
+        }
+    }
+
     /// Returns true if the `Value` is a `Text`. Returns false otherwise.
     ///
     /// ```
@@ -594,6 +704,34 @@ impl Value {
             other => Err(other),
         }
     }
+
+    /// Returns the value formatted in CBOR diagnostic notation
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// let value = Value::Tag(1, Box::from(Value::from(1363896240)));
+    ///
+    /// assert_eq!(value.diagnostic().to_string(), "1(1363896240)");
+    /// ```
+    pub fn diagnostic(&self) -> Diagnostic<'_> {
+        Diagnostic(self)
+    }
+
+    /// Returns the value formatted as an indented tree
+    ///
+    /// See [`Pretty`] for how it is written and the ways it can be cut down.
+    ///
+    /// ```
+    /// # use ciborium::Value;
+    /// #
+    /// let value = Value::Array(vec![Value::from(1), Value::Array(vec![])]);
+    ///
+    /// assert_eq!(value.pretty().to_string(), "[\n  1,\n  []\n]");
+    /// ```
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty::new(self)
+    }
 }
 
 macro_rules! implfrom {
@@ -683,3 +821,89 @@ impl From<char> for Value {
         Value::Text(v)
     }
 }
+
+impl<K: Into<Value>, V: Into<Value>> From<alloc::collections::BTreeMap<K, V>> for Value {
+    #[inline]
+    fn from(value: alloc::collections::BTreeMap<K, V>) -> Self {
+        Value::Map(
+            value
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Note that the entries are stored in the iteration order of the `HashMap`,
+/// which is unspecified.
+#[cfg(feature = "std")]
+impl<K: Into<Value>, V: Into<Value>, S> From<std::collections::HashMap<K, V, S>> for Value {
+    #[inline]
+    fn from(value: std::collections::HashMap<K, V, S>) -> Self {
+        Value::Map(
+            value
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(x) => x.into(),
+            None => Value::Null,
+        }
+    }
+}
+
+/// Collects items into a `Value::Array`
+///
+/// A blanket `From<Vec<T>>` would conflict with `From<Vec<u8>>`, which
+/// produces `Value::Bytes`. Collect a vector of other items instead:
+///
+/// ```
+/// # use ciborium::Value;
+/// #
+/// let value: Value = vec!["a", "b"].into_iter().collect();
+/// assert_eq!(value, Value::Array(vec!["a".into(), "b".into()]));
+/// ```
+impl<T: Into<Value>> FromIterator<T> for Value {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Value::Array(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+macro_rules! impleq {
+    ($($t:ty => |$v:ident, $o:ident| $e:expr),+ $(,)?) => {
+        $(
+            impl PartialEq<$t> for Value {
+                #[inline]
+                fn eq(&self, other: &$t) -> bool {
+                    let ($v, $o) = (self, other);
+                    $e
+                }
+            }
+
+            impl PartialEq<Value> for $t {
+                #[inline]
+                fn eq(&self, other: &Value) -> bool {
+                    other == self
+                }
+            }
+        )+
+    };
+}
+
+impleq! {
+    str => |v, o| v.as_text() == Some(o),
+    &str => |v, o| v.as_text() == Some(*o),
+    String => |v, o| v.as_text() == Some(o.as_str()),
+    bool => |v, o| v.as_bool() == Some(*o),
+    i64 => |v, o| v.as_integer().and_then(|x| i64::try_from(x).ok()) == Some(*o),
+    u64 => |v, o| v.as_integer().and_then(|x| u64::try_from(x).ok()) == Some(*o),
+    f64 => |v, o| v.as_float() == Some(*o),
+}
@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Diagnostic, Value};
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter, Write};
+
+use serde::{Deserialize, Serialize};
+
+/// The location of an item nested within a [`Value`]
+///
+/// A path is a sequence of segments, each of which selects a child of the
+/// current item: an integer selects an element of an array by index, while
+/// any value selects the entry of a map with an equal key. Tags are
+/// transparent, so a segment applied to a tagged item selects a child of
+/// the item it wraps. The empty path refers to the root.
+///
+/// ```
+/// use ciborium::{cbor, value::Path};
+///
+/// let value = cbor!({ "items" => [{ 1 => "one" }] }).unwrap();
+/// let path = Path::from_iter([cbor!("items").unwrap(), 0.into(), 1.into()]);
+///
+/// assert_eq!(value.get_path(&path).unwrap(), &cbor!("one").unwrap());
+/// assert_eq!(path.to_string(), r#"$["items"][0][1]"#);
+/// ```
+///
+/// Paths serialize as an array of their segments. A path whose segments
+/// are all text or integers converts to a JSON Pointer with
+/// [`Path::to_pointer()`], and [`Value::pointer_path()`] resolves a pointer
+/// into a path.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Path(Vec<Value>);
+
+impl Path {
+    /// Returns the empty path, which refers to the root
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the segments of the path
+    #[inline]
+    pub fn segments(&self) -> &[Value] {
+        &self.0
+    }
+
+    /// Appends a segment to the path
+    #[inline]
+    pub fn push(&mut self, segment: impl Into<Value>) {
+        self.0.push(segment.into())
+    }
+
+    /// Removes the last segment of the path and returns it
+    #[inline]
+    pub fn pop(&mut self) -> Option<Value> {
+        self.0.pop()
+    }
+
+    /// Returns a copy of the path with `segment` appended
+    #[inline]
+    pub fn join(&self, segment: impl Into<Value>) -> Self {
+        let mut path = self.clone();
+        path.push(segment);
+        path
+    }
+
+    /// Returns the JSON Pointer (RFC 6901) to the item at this path
+    ///
+    /// Text segments become tokens as they are, escaped as the RFC requires,
+    /// and integer segments are written in decimal. Returns `None` if any
+    /// other segment is found, since a pointer cannot address it. A token
+    /// selects a text key or an integer key spelled the same way, so in a
+    /// map with both, the pointer selects whichever comes first.
+    ///
+    /// ```
+    /// use ciborium::{cbor, value::Path};
+    ///
+    /// let path = Path::from_iter([cbor!("a/b").unwrap(), 1.into()]);
+    /// assert_eq!(path.to_pointer().unwrap(), "/a~1b/1");
+    ///
+    /// let path = Path::from_iter([cbor!([1]).unwrap()]);
+    /// assert_eq!(path.to_pointer(), None);
+    /// ```
+    pub fn to_pointer(&self) -> Option<String> {
+        let mut pointer = String::new();
+        for segment in &self.0 {
+            pointer.push('/');
+            match segment {
+                Value::Text(x) => pointer.push_str(&x.replace('~', "~0").replace('/', "~1")),
+                Value::Integer(x) => {
+                    let _ = write!(pointer, "{}", i128::from(*x));
+                }
+                _ => return None,
+            }
+        }
+
+        Some(pointer)
+    }
+}
+
+impl From<Vec<Value>> for Path {
+    #[inline]
+    fn from(segments: Vec<Value>) -> Self {
+        Self(segments)
+    }
+}
+
+impl<T: Into<Value>> FromIterator<T> for Path {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Formats the path starting from `$`, with each segment in brackets in
+/// diagnostic notation
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("$")?;
+        for segment in &self.0 {
+            write!(f, "[{}]", Diagnostic(segment))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the value inside any tags
+pub(crate) fn untagged(value: &Value) -> &Value {
+    match value {
+        Value::Tag(_, value) => untagged(value),
+        value => value,
+    }
+}
+
+/// Returns the value inside any tags mutably
+pub(crate) fn untagged_mut(value: &mut Value) -> &mut Value {
+    match value {
+        Value::Tag(_, value) => untagged_mut(value),
+        value => value,
+    }
+}
+
+/// Returns the child of an array or map selected by `segment`
+fn child<'a>(value: &'a Value, segment: &Value) -> Option<&'a Value> {
+    match untagged(value) {
+        Value::Array(x) => x.get(usize::try_from(segment.as_integer()?).ok()?),
+        Value::Map(x) => x.iter().find(|(k, ..)| k == segment).map(|(.., v)| v),
+        _ => None,
+    }
+}
+
+/// Returns the child of an array or map selected by `segment` mutably
+fn child_mut<'a>(value: &'a mut Value, segment: &Value) -> Option<&'a mut Value> {
+    match untagged_mut(value) {
+        Value::Array(x) => x.get_mut(usize::try_from(segment.as_integer()?).ok()?),
+        Value::Map(x) => x.iter_mut().find(|(k, ..)| k == segment).map(|(.., v)| v),
+        _ => None,
+    }
+}
+
+/// Returns the item at the end of `segments` mutably
+pub(crate) fn get_mut<'a>(value: &'a mut Value, segments: &[Value]) -> Option<&'a mut Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| child_mut(value, segment))
+}
+
+impl Value {
+    /// Returns the item at `path`, if there is one
+    pub fn get_path(&self, path: &Path) -> Option<&Value> {
+        path.0
+            .iter()
+            .try_fold(self, |value, segment| child(value, segment))
+    }
+
+    /// Returns the item at `path` mutably, if there is one
+    pub fn get_path_mut(&mut self, path: &Path) -> Option<&mut Value> {
+        get_mut(self, &path.0)
+    }
+}
@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{untagged, untagged_mut, Error, Path, Value};
+
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+
+/// Splits a JSON Pointer into its unescaped tokens
+fn tokens(pointer: &str) -> Result<impl Iterator<Item = Cow<'_, str>>, Error> {
+    let tokens = match pointer {
+        "" => None,
+        _ => match pointer.strip_prefix('/') {
+            Some(pointer) => Some(pointer.split('/')),
+            None => {
+                return Err(Error::Custom(
+                    "pointer must be empty or start with '/'".into(),
+                ))
+            }
+        },
+    };
+
+    Ok(tokens
+        .into_iter()
+        .flatten()
+        .map(|token| match token.contains('~') {
+            true => Cow::Owned(token.replace("~1", "/").replace("~0", "~")),
+            false => Cow::Borrowed(token),
+        }))
+}
+
+/// Returns whether a map key is matched by a pointer token
+fn matches(key: &Value, token: &str) -> bool {
+    match key {
+        Value::Text(x) => x == token,
+        Value::Integer(x) => integer_key(token) == Some(i128::from(*x)),
+        _ => false,
+    }
+}
+
+/// Returns whether `digits` are written in decimal without a sign or
+/// leading zeros
+fn canonical(digits: &str) -> bool {
+    match digits.as_bytes() {
+        [b'0'] => true,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    }
+}
+
+/// Parses an array index, which RFC 6901 writes in decimal without a sign
+/// or leading zeros
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    match canonical(token) {
+        true => token.parse().ok(),
+        false => None,
+    }
+}
+
+/// Parses an integer map key, written like an array index but for an
+/// optional leading `-`
+pub(crate) fn integer_key(token: &str) -> Option<i128> {
+    match token.strip_prefix('-') {
+        Some("0") => None,
+        Some(digits) if canonical(digits) => token.parse().ok(),
+        None if canonical(token) => token.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the index of the child of an array or map selected by `token`
+fn index(value: &Value, token: &str) -> Option<usize> {
+    match untagged(value) {
+        Value::Array(x) => array_index(token).filter(|i| *i < x.len()),
+        Value::Map(x) => x.iter().position(|(k, ..)| matches(k, token)),
+        _ => None,
+    }
+}
+
+/// Returns the child at `index` of an array or map
+fn child_mut(value: &mut Value, index: usize) -> &mut Value {
+    match untagged_mut(value) {
+        Value::Array(x) => &mut x[index],
+        Value::Map(x) => &mut x[index].1,
+        _ => unreachable!(),
+    }
+}
+
+impl Value {
+    /// Returns the item addressed by a JSON Pointer, if there is one
+    ///
+    /// Pointers are resolved as by [`extract()`](crate::de::extract): each
+    /// token selects either the value of a map entry whose text or integer
+    /// key matches it, or an array element by index. Tags along the way are
+    /// ignored. Indices and integer keys are written in decimal without
+    /// leading zeros or a `+`. Returns `None` if the pointer is malformed.
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// let value = cbor!({ "a" => { 1 => [10, 20] } }).unwrap();
+    /// assert_eq!(value.pointer("/a/1/1"), Some(&cbor!(20).unwrap()));
+    /// assert_eq!(value.pointer("/a/2"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        tokens(pointer).ok()?.try_fold(self, |value, token| {
+            let index = index(value, &token)?;
+            match untagged(value) {
+                Value::Array(x) => Some(&x[index]),
+                Value::Map(x) => Some(&x[index].1),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the [`Path`] to the item addressed by a JSON Pointer, if there
+    /// is one
+    ///
+    /// A token may select an array element, a text key or an integer key,
+    /// so a pointer only becomes a path against the value it addresses. Each
+    /// segment of the path is the index or the key which the token selected.
+    ///
+    /// ```
+    /// use ciborium::{cbor, value::Path};
+    ///
+    /// let value = cbor!({ "a" => { 1 => [10, 20] } }).unwrap();
+    /// let path = value.pointer_path("/a/1/0").unwrap();
+    /// assert_eq!(path, Path::from_iter([cbor!("a").unwrap(), 1.into(), 0.into()]));
+    /// assert_eq!(path.to_pointer().unwrap(), "/a/1/0");
+    /// ```
+    pub fn pointer_path(&self, pointer: &str) -> Option<Path> {
+        let mut path = Path::new();
+        tokens(pointer).ok()?.try_fold(self, |value, token| {
+            let index = index(value, &token)?;
+            match untagged(value) {
+                Value::Array(x) => {
+                    path.push(index as u64);
+                    Some(&x[index])
+                }
+                Value::Map(x) => {
+                    path.push(x[index].0.clone());
+                    Some(&x[index].1)
+                }
+                _ => None,
+            }
+        })?;
+
+        Some(path)
+    }
+
+    /// Returns the item addressed by a JSON Pointer mutably, if there is one
+    ///
+    /// See [`Value::pointer()`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        tokens(pointer).ok()?.try_fold(self, |value, token| {
+            let index = index(value, &token)?;
+            Some(child_mut(value, index))
+        })
+    }
+
+    /// Sets the item addressed by a JSON Pointer, returning the old item
+    ///
+    /// Tokens are resolved as by [`Value::pointer()`]. If the last token does
+    /// not match an existing item, a new one is added: a map gains an entry
+    /// with the token as its text key, and an array is extended if the token
+    /// is its length or `-`. In that case, `None` is returned.
+    ///
+    /// Intermediate items must exist, unless `create` is set. Then, missing
+    /// items are created as empty arrays if the next token is `0` or `-` and
+    /// as empty maps otherwise, and nulls are replaced in the same way.
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// let mut value = cbor!({ "server" => { "port" => 80 } }).unwrap();
+    ///
+    /// let old = value.pointer_set("/server/port", 8080.into(), false).unwrap();
+    /// assert_eq!(old, Some(cbor!(80).unwrap()));
+    ///
+    /// value.pointer_set("/server/hosts/-", "localhost".into(), true).unwrap();
+    /// assert_eq!(
+    ///     value,
+    ///     cbor!({ "server" => { "port" => 8080, "hosts" => ["localhost"] } }).unwrap()
+    /// );
+    ///
+    /// assert!(value.pointer_set("/client/port", 80.into(), false).is_err());
+    /// ```
+    pub fn pointer_set(
+        &mut self,
+        pointer: &str,
+        value: Value,
+        create: bool,
+    ) -> Result<Option<Value>, Error> {
+        let tokens: Vec<_> = tokens(pointer)?.collect();
+        let (last, parents) = match tokens.split_last() {
+            Some(split) => split,
+            None => return Ok(Some(core::mem::replace(self, value))),
+        };
+
+        // The pointer to the item selected by the first `n` tokens
+        let prefix = |n: usize| match pointer.match_indices('/').nth(n) {
+            Some((i, ..)) => &pointer[..i],
+            None => pointer,
+        };
+
+        let mut item = self;
+        for (n, token) in parents.iter().enumerate() {
+            let index = match index(prepare(item, token, create), token) {
+                Some(index) => index,
+                None if create => add(item, token, Value::Null).ok_or_else(|| {
+                    Error::Custom(format!("cannot add \"{}\" to \"{}\"", token, prefix(n)))
+                })?,
+                None => {
+                    let missing = prefix(n + 1);
+                    return Err(Error::Custom(format!("\"{}\" does not exist", missing)));
+                }
+            };
+
+            item = child_mut(item, index);
+        }
+
+        match index(prepare(item, last, create), last) {
+            Some(index) => Ok(Some(core::mem::replace(child_mut(item, index), value))),
+            None => match add(item, last, value) {
+                Some(..) => Ok(None),
+                None => Err(Error::Custom(format!(
+                    "cannot add \"{}\" to \"{}\"",
+                    last,
+                    prefix(parents.len())
+                ))),
+            },
+        }
+    }
+}
+
+/// Replaces a null with a container which `token` can address, if creating
+fn prepare<'a>(item: &'a mut Value, token: &str, create: bool) -> &'a mut Value {
+    if create && item.is_null() {
+        *item = match token {
+            "0" | "-" => Value::Array(Vec::new()),
+            _ => Value::Map(Vec::new()),
+        };
+    }
+
+    item
+}
+
+/// Adds a new child to an array or map, returning its index
+///
+/// Fails unless `token` addresses the end of an array, or `item` is a map.
+fn add(item: &mut Value, token: &str, value: Value) -> Option<usize> {
+    match untagged_mut(item) {
+        Value::Array(x) if token == "-" || array_index(token) == Some(x.len()) => {
+            x.push(value);
+            Some(x.len() - 1)
+        }
+
+        Value::Map(x) => {
+            x.push((Value::Text(String::from(token)), value));
+            Some(x.len() - 1)
+        }
+
+        _ => None,
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Diagnostic, Value};
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+/// How byte strings are written by [`Pretty`]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ByteFormat {
+    /// In hex, as in diagnostic notation: `h'6869ff'`
+    Hex,
+
+    /// As text, with bytes outside of printable ASCII escaped: `'hi\xff'`
+    Text,
+
+    /// Only as their length: `<3 bytes>`
+    Length,
+}
+
+/// A [`Value`] formatted as an indented tree
+///
+/// This is diagnostic notation, as written by [`Diagnostic`], but with each
+/// item of an array or map on a line of its own. It is returned by
+/// [`Value::pretty()`], and is also how a [`Diagnostic`] is written with the
+/// alternate flag (`{:#}`). Since it formats the same way with `Debug`, it
+/// can stand in for a value in assertion messages.
+///
+/// ```
+/// use ciborium::cbor;
+///
+/// let value = cbor!({ "a" => [1, 2], "b" => {} }).unwrap();
+/// assert_eq!(
+///     value.pretty().to_string(),
+///     "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}",
+/// );
+/// ```
+///
+/// Large values can be cut down by writing long byte strings by their
+/// length, and by summarizing arrays and maps nested too deeply:
+///
+/// ```
+/// use ciborium::{cbor, value::ByteFormat, Value};
+///
+/// let value = cbor!({ "body" => Value::Bytes(vec![0; 1024]), "list" => [[1, 2]] }).unwrap();
+/// let pretty = value.pretty().bytes(ByteFormat::Length).depth(2);
+/// assert_eq!(
+///     pretty.to_string(),
+///     "{\n  \"body\": <1024 bytes>,\n  \"list\": [\n    [<2 items>]\n  ]\n}",
+/// );
+/// ```
+#[derive(Copy, Clone)]
+pub struct Pretty<'a> {
+    value: &'a Value,
+    bytes: ByteFormat,
+    depth: Option<usize>,
+}
+
+impl<'a> Pretty<'a> {
+    #[inline]
+    pub(crate) fn new(value: &'a Value) -> Self {
+        Self {
+            value,
+            bytes: ByteFormat::Hex,
+            depth: None,
+        }
+    }
+
+    /// Sets how byte strings are written
+    ///
+    /// The default is [`ByteFormat::Hex`].
+    #[inline]
+    pub fn bytes(mut self, format: ByteFormat) -> Self {
+        self.bytes = format;
+        self
+    }
+
+    /// Sets how deeply arrays and maps are written in full
+    ///
+    /// Arrays and maps nested within `depth` others are written only as how
+    /// many items they hold. The default is to write everything.
+    #[inline]
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    fn write(&self, f: &mut Formatter<'_>, value: &Value, depth: usize) -> fmt::Result {
+        let truncated = matches!(self.depth, Some(limit) if depth >= limit);
+
+        match value {
+            Value::Bytes(x) => match self.bytes {
+                ByteFormat::Hex => write!(f, "{}", Diagnostic(value)),
+                ByteFormat::Length => write!(f, "<{} byte{}>", x.len(), plural(x.len())),
+                ByteFormat::Text => {
+                    f.write_char('\'')?;
+                    for byte in x {
+                        match byte {
+                            b'\'' => f.write_str("\\'")?,
+                            b'\\' => f.write_str("\\\\")?,
+                            b' '..=b'~' => f.write_char(char::from(*byte))?,
+                            _ => write!(f, "\\x{:02x}", byte)?,
+                        }
+                    }
+                    f.write_char('\'')
+                }
+            },
+
+            Value::Tag(t, v) => {
+                write!(f, "{}(", t)?;
+                self.write(f, v, depth)?;
+                f.write_char(')')
+            }
+
+            Value::Array(x) if x.is_empty() => f.write_str("[]"),
+            Value::Array(x) if truncated => write!(f, "[<{} item{}>]", x.len(), plural(x.len())),
+            Value::Array(x) => {
+                f.write_char('[')?;
+                for (i, v) in x.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    indent(f, depth + 1)?;
+                    self.write(f, v, depth + 1)?;
+                }
+                indent(f, depth)?;
+                f.write_char(']')
+            }
+
+            Value::Map(x) if x.is_empty() => f.write_str("{}"),
+            Value::Map(x) if truncated => write!(f, "{{<{} pair{}>}}", x.len(), plural(x.len())),
+            Value::Map(x) => {
+                f.write_char('{')?;
+                for (i, (k, v)) in x.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    indent(f, depth + 1)?;
+                    self.write(f, k, depth + 1)?;
+                    f.write_str(": ")?;
+                    self.write(f, v, depth + 1)?;
+                }
+                indent(f, depth)?;
+                f.write_char('}')
+            }
+
+            value => write!(f, "{}", Diagnostic(value)),
+        }
+    }
+}
+
+/// Returns the ending of a noun counting `n` things
+fn plural(n: usize) -> &'static str {
+    match n {
+        1 => "",
+        _ => "s",
+    }
+}
+
+/// Starts a new line indented for `depth`
+fn indent(f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+    f.write_char('\n')?;
+    (0..depth).try_for_each(|_| f.write_str("  "))
+}
+
+impl Display for Pretty<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write(f, self.value, 0)
+    }
+}
+
+impl Debug for Pretty<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
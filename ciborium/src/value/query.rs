@@ -0,0 +1,428 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{untagged, Error, Path, Value};
+
+use alloc::{format, string::String, vec::Vec};
+use core::{cmp::Ordering, str::FromStr};
+
+/// A query selecting items within a [`Value`], in the style of JSONPath
+///
+/// A query starts at the root `$` and applies a sequence of selectors, each
+/// of which maps every item selected so far to some of its children:
+///
+/// | Selector            | Selects                                            |
+/// |---------------------|----------------------------------------------------|
+/// | `.name`, `["name"]` | the entry of a map with the text key `name`        |
+/// | `[1]`, `[-1]`       | an array element by index (negative from the end), |
+/// |                     | or the entry of a map with the integer key         |
+/// | `.*`, `[*]`         | every array element or map entry                   |
+/// | `[?(@.a > 1)]`      | every element or entry matching a filter           |
+/// | `..` + selector     | the selector applied to the item and all of its    |
+/// |                     | descendants                                        |
+///
+/// A filter compares the item at a relative path, which starts at `@`, with
+/// a literal using `==`, `!=`, `<`, `<=`, `>` or `>=`. Literals are
+/// integers, floats, quoted strings, `true`, `false` and `null`. Without a
+/// comparison, the filter matches when the relative path exists. Numbers
+/// compare by value regardless of type and strings compare lexically;
+/// ordering anything else never matches. As with [`Path`], tags are
+/// transparent.
+///
+/// ```
+/// use ciborium::{cbor, value::Query};
+///
+/// let value = cbor!({
+///     "routes" => [
+///         { "path" => "/a", "weight" => 1 },
+///         { "path" => "/b", "weight" => 5 },
+///     ]
+/// }).unwrap();
+///
+/// let query: Query = "$.routes[?(@.weight > 2)].path".parse().unwrap();
+/// let matches = query.select(&value);
+///
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].0.to_string(), r#"$["routes"][1]["path"]"#);
+/// assert_eq!(matches[0].1, &cbor!("/b").unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query(Vec<Step>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    Child(Selector),
+    Descendants(Selector),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Selector {
+    Key(Value),
+    Wildcard,
+    Filter(Path, Option<(Operator, Value)>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Query {
+    /// Returns the items selected by the query, with their paths
+    ///
+    /// Items are returned in the order they appear within `value`.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<(Path, &'a Value)> {
+        let mut selected = Vec::from([(Path::new(), value)]);
+
+        for step in &self.0 {
+            let mut next = Vec::new();
+
+            for (path, value) in &selected {
+                match step {
+                    Step::Child(selector) => selector.apply(path, value, &mut next),
+                    Step::Descendants(selector) => {
+                        let mut stack = Vec::from([(path.clone(), *value)]);
+                        while let Some((path, value)) = stack.pop() {
+                            selector.apply(&path, value, &mut next);
+
+                            let mut children = Vec::new();
+                            Selector::Wildcard.apply(&path, value, &mut children);
+                            stack.extend(children.into_iter().rev());
+                        }
+                    }
+                }
+            }
+
+            selected = next;
+        }
+
+        selected
+    }
+}
+
+impl Selector {
+    /// Appends the children of `value` that are selected
+    fn apply<'a>(&self, path: &Path, value: &'a Value, out: &mut Vec<(Path, &'a Value)>) {
+        match (self, untagged(value)) {
+            (Selector::Key(key), Value::Array(x)) => {
+                let index = match key.as_integer().map(i128::from) {
+                    Some(i) if i < 0 => x.len() as i128 + i,
+                    Some(i) => i,
+                    None => return,
+                };
+
+                if let Some(item) = usize::try_from(index).ok().and_then(|i| x.get(i)) {
+                    out.push((path.join(index as u64), item));
+                }
+            }
+
+            (Selector::Key(key), Value::Map(x)) => {
+                for (k, v) in x.iter().filter(|(k, ..)| k == key) {
+                    out.push((path.join(k.clone()), v));
+                }
+            }
+
+            (Selector::Wildcard | Selector::Filter(..), Value::Array(x)) => {
+                for (i, v) in x.iter().enumerate() {
+                    if self.matches(v) {
+                        out.push((path.join(i as u64), v));
+                    }
+                }
+            }
+
+            (Selector::Wildcard | Selector::Filter(..), Value::Map(x)) => {
+                for (k, v) in x {
+                    if self.matches(v) {
+                        out.push((path.join(k.clone()), v));
+                    }
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    /// Returns whether a child passes the filter, if this is one
+    fn matches(&self, value: &Value) -> bool {
+        let (path, comparison) = match self {
+            Selector::Filter(path, comparison) => (path, comparison),
+            _ => return true,
+        };
+
+        let item = match value.get_path(path) {
+            Some(item) => untagged(item),
+            None => return false,
+        };
+
+        let (operator, literal) = match comparison {
+            Some((operator, literal)) => (operator, literal),
+            None => return true,
+        };
+
+        let ordering = compare(item, literal);
+        match operator {
+            Operator::Eq => ordering == Some(Ordering::Equal),
+            Operator::Ne => ordering != Some(Ordering::Equal),
+            Operator::Lt => ordering == Some(Ordering::Less),
+            Operator::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Operator::Gt => ordering == Some(Ordering::Greater),
+            Operator::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+/// Compares an item with a filter literal
+fn compare(value: &Value, literal: &Value) -> Option<Ordering> {
+    let float = |x: &Value| match x {
+        Value::Integer(x) => Some(i128::from(*x) as f64),
+        Value::Float(x) => Some(*x),
+        _ => None,
+    };
+
+    match (value, literal) {
+        (Value::Integer(a), Value::Integer(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+        (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+        (a, b) => match (float(a), float(b)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ if a == b => Some(Ordering::Equal),
+            _ => None,
+        },
+    }
+}
+
+/// A cursor over the text of a query
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> Error {
+        Error::Custom(format!("invalid query at {}: {}", self.offset, message))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.text[self.offset..].starts_with(prefix);
+        if found {
+            self.offset += prefix.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, prefix: &str) -> Result<(), Error> {
+        match self.eat(prefix) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected '{}'", prefix))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.offset += c.len_utf8();
+        }
+    }
+
+    /// Parses the selector following `.` or `..`
+    fn dotted(&mut self) -> Result<Selector, Error> {
+        if self.eat("*") {
+            return Ok(Selector::Wildcard);
+        }
+
+        if self.peek() == Some('[') {
+            return self.bracketed();
+        }
+
+        let rest = &self.text[self.offset..];
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+
+        match len {
+            0 => Err(self.error("expected a name")),
+            _ => {
+                self.offset += len;
+                Ok(Selector::Key(rest[..len].into()))
+            }
+        }
+    }
+
+    /// Parses a selector in brackets
+    fn bracketed(&mut self) -> Result<Selector, Error> {
+        self.expect("[")?;
+        self.skip_whitespace();
+
+        let selector = if self.eat("*") {
+            Selector::Wildcard
+        } else if self.eat("?") {
+            self.skip_whitespace();
+            self.expect("(")?;
+            let filter = self.filter()?;
+            self.expect(")")?;
+            filter
+        } else {
+            match self.literal()? {
+                key @ (Value::Text(..) | Value::Integer(..)) => Selector::Key(key),
+                _ => return Err(self.error("expected a string or an integer")),
+            }
+        };
+
+        self.skip_whitespace();
+        self.expect("]")?;
+        Ok(selector)
+    }
+
+    /// Parses a filter expression inside `?(...)`
+    fn filter(&mut self) -> Result<Selector, Error> {
+        self.skip_whitespace();
+        self.expect("@")?;
+
+        let mut path = Path::new();
+        loop {
+            if self.eat(".") {
+                match self.dotted()? {
+                    Selector::Key(key) => path.push(key),
+                    _ => return Err(self.error("expected a name")),
+                }
+            } else if self.peek() == Some('[') {
+                match self.bracketed()? {
+                    Selector::Key(key) => path.push(key),
+                    _ => return Err(self.error("expected a string or an integer")),
+                }
+            } else {
+                break;
+            }
+        }
+
+        self.skip_whitespace();
+        let operator = [
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            ("<=", Operator::Le),
+            (">=", Operator::Ge),
+            ("<", Operator::Lt),
+            (">", Operator::Gt),
+        ]
+        .into_iter()
+        .find(|(text, ..)| self.eat(text));
+
+        let comparison = match operator {
+            None => None,
+            Some((.., operator)) => Some((operator, self.literal()?)),
+        };
+
+        self.skip_whitespace();
+        Ok(Selector::Filter(path, comparison))
+    }
+
+    /// Parses a literal value
+    fn literal(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+
+        for (keyword, value) in [
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+            ("null", Value::Null),
+        ] {
+            if self.eat(keyword) {
+                return Ok(value);
+            }
+        }
+
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.offset += 1;
+                let mut text = String::new();
+                let mut chars = self.text[self.offset..].char_indices();
+
+                loop {
+                    match chars.next() {
+                        Some((i, c)) if c == quote => {
+                            self.offset += i + 1;
+                            return Ok(Value::Text(text));
+                        }
+                        Some((.., '\\')) => match chars.next() {
+                            Some((.., c)) => text.push(c),
+                            None => break,
+                        },
+                        Some((.., c)) => text.push(c),
+                        None => break,
+                    }
+                }
+
+                self.offset = self.text.len();
+                Err(self.error("unterminated string"))
+            }
+
+            _ => {
+                let rest = &self.text[self.offset..];
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+                    .unwrap_or(rest.len());
+
+                let number = &rest[..len];
+                let value = match number.parse::<i64>() {
+                    Ok(x) => Value::from(x),
+                    Err(..) => match number.parse::<f64>() {
+                        Ok(x) => Value::Float(x),
+                        Err(..) => return Err(self.error("expected a literal")),
+                    },
+                };
+
+                self.offset += len;
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        let mut parser = Parser { text, offset: 0 };
+        let mut steps = Vec::new();
+
+        parser.skip_whitespace();
+        parser.expect("$")?;
+
+        loop {
+            parser.skip_whitespace();
+
+            if parser.eat("..") {
+                steps.push(Step::Descendants(parser.dotted()?));
+            } else if parser.eat(".") {
+                steps.push(Step::Child(parser.dotted()?));
+            } else if parser.peek() == Some('[') {
+                steps.push(Step::Child(parser.bracketed()?));
+            } else if parser.peek().is_none() {
+                return Ok(Query(steps));
+            } else {
+                return Err(parser.error("expected a selector"));
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Returns the items selected by a [`Query`], with their paths
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// let value = cbor!({ "a" => { "id" => 1 }, "b" => [{ "id" => 2 }] }).unwrap();
+    /// let ids: Vec<_> = value.query("$..id").unwrap().into_iter().map(|(_, v)| v).collect();
+    ///
+    /// assert_eq!(ids, [&cbor!(1).unwrap(), &cbor!(2).unwrap()]);
+    /// ```
+    pub fn query(&self, query: &str) -> Result<Vec<(Path, &Value)>, Error> {
+        Ok(query.parse::<Query>()?.select(self))
+    }
+}
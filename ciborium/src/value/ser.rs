@@ -433,4 +433,20 @@ impl Value {
     pub fn serialized<T: ?Sized + ser::Serialize>(value: &T) -> Result<Self, Error> {
         value.serialize(Serializer(()))
     }
+
+    /// Encodes the value as a string of lowercase hex digits
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// assert_eq!(cbor!({ "a" => [1] }).unwrap().to_hex(), "a161618101");
+    /// ```
+    #[cfg(feature = "hex")]
+    pub fn to_hex(&self) -> alloc::string::String {
+        let mut bytes = Vec::new();
+        match crate::ser::into_writer(self, &mut bytes) {
+            Ok(()) => hex::encode(bytes),
+            Err(e) => unreachable!("values always encode: {:?}", e),
+        }
+    }
 }
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::canonical::{cmp_value, encoded_bytes_cmp};
+use super::{Error, Value};
+
+use alloc::{format, vec, vec::Vec};
+use core::cmp::Ordering;
+
+/// The order in which [`Value::sort_keys()`] sorts map entries
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// Shorter encoded keys first, then bytewise lexical order
+    ///
+    /// This is the canonical order of RFC 7049 Section 3.9, as used by
+    /// [`CanonicalValue`](super::CanonicalValue).
+    LengthFirst,
+
+    /// Bytewise lexical order of the encoded keys
+    ///
+    /// This is the deterministic order of RFC 8949 Section 4.2.1.
+    Lexical,
+}
+
+impl KeyOrder {
+    fn cmp(self, v1: &Value, v2: &Value) -> Ordering {
+        match self {
+            KeyOrder::LengthFirst => cmp_value(v1, v2),
+            KeyOrder::Lexical => encoded_bytes_cmp(v1, v2),
+        }
+    }
+
+    /// Compares two encoded keys
+    pub(crate) fn cmp_encoded(self, k1: &[u8], k2: &[u8]) -> Ordering {
+        match self {
+            KeyOrder::LengthFirst => k1.len().cmp(&k2.len()).then_with(|| k1.cmp(k2)),
+            KeyOrder::Lexical => k1.cmp(k2),
+        }
+    }
+}
+
+/// Which entry [`Value::dedup_keys()`] keeps when a map key is repeated
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Keep the first entry with the key
+    First,
+
+    /// Keep the last entry with the key
+    Last,
+
+    /// Fail on the first repeated key
+    Reject,
+}
+
+impl Value {
+    /// Sorts the entries of a map by their keys
+    ///
+    /// Entries with equal keys keep their relative order. If `recursive`
+    /// is set, the maps nested anywhere inside this value, including inside
+    /// map keys, are sorted too. Otherwise values other than maps are left
+    /// untouched.
+    ///
+    /// ```
+    /// use ciborium::{cbor, value::KeyOrder};
+    ///
+    /// let mut value = cbor!({ "aa" => 1, "b" => { 100 => 2, -1 => 3 } }).unwrap();
+    ///
+    /// value.sort_keys(KeyOrder::LengthFirst, true);
+    /// assert_eq!(value, cbor!({ "b" => { -1 => 3, 100 => 2 }, "aa" => 1 }).unwrap());
+    /// ```
+    pub fn sort_keys(&mut self, order: KeyOrder, recursive: bool) {
+        if recursive {
+            match self {
+                Value::Tag(_, v) => v.sort_keys(order, true),
+                Value::Array(x) => x.iter_mut().for_each(|v| v.sort_keys(order, true)),
+                Value::Map(x) => {
+                    for (k, v) in x.iter_mut() {
+                        k.sort_keys(order, true);
+                        v.sort_keys(order, true);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if let Value::Map(entries) = self {
+            entries.sort_by(|a, b| order.cmp(&a.0, &b.0));
+        }
+    }
+
+    /// Looks up a key in a map sorted by [`Value::sort_keys()`]
+    ///
+    /// This performs a binary search, which takes logarithmic rather than
+    /// linear time in the size of the map. The map must be sorted in
+    /// `order`: otherwise an entry may not be found even though it exists.
+    /// Returns `None` if this value is not a map.
+    ///
+    /// ```
+    /// use ciborium::{cbor, value::KeyOrder};
+    ///
+    /// let value = cbor!({ 1 => "a", -1 => "b", "c" => "d" }).unwrap();
+    ///
+    /// assert_eq!(value.get_sorted(&"c".into(), KeyOrder::LengthFirst), Some(&"d".into()));
+    /// assert_eq!(value.get_sorted(&2.into(), KeyOrder::LengthFirst), None);
+    /// ```
+    pub fn get_sorted(&self, key: &Value, order: KeyOrder) -> Option<&Value> {
+        let entries = self.as_map()?;
+        let index = entries.binary_search_by(|(k, _)| order.cmp(k, key)).ok()?;
+        Some(&entries[index].1)
+    }
+
+    /// Looks up a key in a map sorted by [`Value::sort_keys()`], mutably
+    ///
+    /// See [`Value::get_sorted()`].
+    pub fn get_sorted_mut(&mut self, key: &Value, order: KeyOrder) -> Option<&mut Value> {
+        let entries = self.as_map_mut()?;
+        let index = entries.binary_search_by(|(k, _)| order.cmp(k, key)).ok()?;
+        Some(&mut entries[index].1)
+    }
+
+    /// Removes the entries of a map whose key is repeated
+    ///
+    /// Two keys are equal when they have the same encoding. The entries
+    /// kept according to `policy` stay in place. If `recursive` is set, the
+    /// maps nested anywhere inside this value are deduplicated too.
+    ///
+    /// ```
+    /// use ciborium::{cbor, value::Duplicates};
+    ///
+    /// let mut value = cbor!({ "a" => 1, "b" => 2, "a" => 3 }).unwrap();
+    /// assert!(value.clone().dedup_keys(Duplicates::Reject, false).is_err());
+    ///
+    /// value.dedup_keys(Duplicates::Last, false).unwrap();
+    /// assert_eq!(value, cbor!({ "b" => 2, "a" => 3 }).unwrap());
+    /// ```
+    pub fn dedup_keys(&mut self, policy: Duplicates, recursive: bool) -> Result<(), Error> {
+        if recursive {
+            match self {
+                Value::Tag(_, v) => v.dedup_keys(policy, true)?,
+                Value::Array(x) => {
+                    for v in x.iter_mut() {
+                        v.dedup_keys(policy, true)?;
+                    }
+                }
+                Value::Map(x) => {
+                    for (k, v) in x.iter_mut() {
+                        k.dedup_keys(policy, true)?;
+                        v.dedup_keys(policy, true)?;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let entries = match self {
+            Value::Map(entries) => entries,
+            _ => return Ok(()),
+        };
+
+        // Sorting the indices is stable, so equal keys stay in map order.
+        let mut indices: Vec<usize> = (0..entries.len()).collect();
+        indices.sort_by(|a, b| cmp_value(&entries[*a].0, &entries[*b].0));
+
+        let mut keep = vec![true; entries.len()];
+        for pair in indices.windows(2) {
+            if cmp_value(&entries[pair[0]].0, &entries[pair[1]].0) != Ordering::Equal {
+                continue;
+            }
+
+            match policy {
+                Duplicates::First => keep[pair[1]] = false,
+                Duplicates::Last => keep[pair[0]] = false,
+                Duplicates::Reject => {
+                    let key = entries[pair[1]].0.diagnostic();
+                    return Err(Error::Custom(format!("duplicate map key {}", key)));
+                }
+            }
+        }
+
+        let mut keep = keep.into_iter();
+        entries.retain(|_| keep.next().unwrap_or(true));
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Path, Value};
+
+use alloc::{boxed::Box, vec::Vec};
+
+impl Value {
+    /// Visits this value and every item nested within it
+    ///
+    /// Items are visited in order, each before its children, along with
+    /// the [`Path`] which addresses it. Both a tagged item and the item
+    /// inside the tag are visited, at the same path. Map keys are part of
+    /// the path and are not visited themselves.
+    ///
+    /// ```
+    /// use ciborium::cbor;
+    ///
+    /// let value = cbor!({ "a" => [1, 2], "b" => { "c" => 3 } }).unwrap();
+    ///
+    /// let mut sum = 0;
+    /// let mut paths = Vec::new();
+    /// value.walk(&mut |path, item| {
+    ///     if let Some(x) = item.as_i64() {
+    ///         sum += x;
+    ///         paths.push(path.to_string());
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(sum, 6);
+    /// assert_eq!(paths, [r#"$["a"][0]"#, r#"$["a"][1]"#, r#"$["b"]["c"]"#]);
+    /// ```
+    pub fn walk<F: FnMut(&Path, &Value)>(&self, visit: &mut F) {
+        self.walk_at(&mut Path::new(), visit)
+    }
+
+    fn walk_at<F: FnMut(&Path, &Value)>(&self, path: &mut Path, visit: &mut F) {
+        visit(path, self);
+
+        match self {
+            Value::Tag(_, value) => value.walk_at(path, visit),
+
+            Value::Array(x) => {
+                for (i, value) in x.iter().enumerate() {
+                    path.push(i as u64);
+                    value.walk_at(path, visit);
+                    path.pop();
+                }
+            }
+
+            Value::Map(x) => {
+                for (key, value) in x {
+                    path.push(key.clone());
+                    value.walk_at(path, visit);
+                    path.pop();
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    /// Transforms this value and every item nested within it
+    ///
+    /// Each item is passed to `transform` along with its [`Path`] and is
+    /// replaced by the result. Items are transformed after their children,
+    /// so `transform` sees the transformed children of arrays, maps and
+    /// tags. Map keys are left unchanged.
+    ///
+    /// ```
+    /// use ciborium::{cbor, Value};
+    ///
+    /// let value = cbor!({ "user" => "alice", "password" => "hunter2", "retries" => [1, 2] }).unwrap();
+    ///
+    /// let redacted = value.map_values(&mut |path, item| match path.segments().last() {
+    ///     Some(key) if key == "password" => Value::Null,
+    ///     _ => item,
+    /// });
+    ///
+    /// assert_eq!(redacted, cbor!({ "user" => "alice", "password" => null, "retries" => [1, 2] }).unwrap());
+    /// ```
+    pub fn map_values<F: FnMut(&Path, Value) -> Value>(self, transform: &mut F) -> Value {
+        self.map_values_at(&mut Path::new(), transform)
+    }
+
+    fn map_values_at<F: FnMut(&Path, Value) -> Value>(
+        self,
+        path: &mut Path,
+        transform: &mut F,
+    ) -> Value {
+        let value = match self {
+            Value::Tag(tag, value) => {
+                Value::Tag(tag, Box::new(value.map_values_at(path, transform)))
+            }
+
+            Value::Array(x) => {
+                let mut array = Vec::with_capacity(x.len());
+                for (i, value) in x.into_iter().enumerate() {
+                    path.push(i as u64);
+                    array.push(value.map_values_at(path, transform));
+                    path.pop();
+                }
+                Value::Array(array)
+            }
+
+            Value::Map(x) => {
+                let mut map = Vec::with_capacity(x.len());
+                for (key, value) in x {
+                    path.push(key);
+                    let value = value.map_values_at(path, transform);
+                    map.push((path.pop().unwrap(), value));
+                }
+                Value::Map(map)
+            }
+
+            value => value,
+        };
+
+        transform(path, value)
+    }
+}
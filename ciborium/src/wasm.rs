@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions between [`Value`] and JavaScript values
+//!
+//! With the `wasm` feature, a [`Value`] converts directly into a
+//! [`JsValue`], so that Rust code running in a browser can hand decoded
+//! CBOR to JavaScript without a round trip through JSON:
+//!
+//! ```no_run
+//! use ciborium::Value;
+//! use wasm_bindgen::JsValue;
+//!
+//! let value: Value = ciborium::from_reader(&[0x82, 0x01, 0x42, 0x01, 0x02][..]).unwrap();
+//! let js = JsValue::from(&value);
+//!
+//! assert_eq!(Value::try_from(&js).unwrap(), value);
+//! ```
+//!
+//! Values map onto JavaScript types as follows:
+//!
+//! | CBOR       | JavaScript                                             |
+//! |------------|--------------------------------------------------------|
+//! | integer    | `Number` if it is a safe integer, otherwise `BigInt`   |
+//! | float      | `Number`                                               |
+//! | bytes      | `Uint8Array`                                           |
+//! | text       | `string`                                               |
+//! | bool       | `boolean`                                              |
+//! | null       | `null`                                                 |
+//! | array      | `Array`                                                |
+//! | map        | `Map`                                                  |
+//!
+//! Tags have no JavaScript equivalent, so a tagged value converts as the
+//! value it wraps. In the other direction, `undefined` converts to null,
+//! any `Number` which is a safe integer converts to an integer and plain
+//! objects convert to maps with text keys.
+
+use crate::value::{Error, Integer, Value};
+
+use alloc::vec::Vec;
+
+use js_sys::{Array, Map, Number, Object, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The deepest nesting converted from JavaScript, which guards against
+/// cyclic objects
+const DEPTH_LIMIT: usize = 256;
+
+/// The largest integer which a `Number` represents exactly
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+impl From<&Value> for JsValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Integer(x) => match i128::from(*x) {
+                x if x.abs() <= MAX_SAFE_INTEGER => JsValue::from_f64(x as f64),
+                x => JsValue::from(x),
+            },
+
+            Value::Bytes(x) => Uint8Array::from(&x[..]).into(),
+            Value::Float(x) => JsValue::from_f64(*x),
+            Value::Text(x) => JsValue::from_str(x),
+            Value::Bool(x) => JsValue::from_bool(*x),
+            Value::Null => JsValue::NULL,
+            Value::Tag(_, x) => JsValue::from(&**x),
+            Value::Array(x) => x.iter().map(JsValue::from).collect::<Array>().into(),
+
+            Value::Map(x) => {
+                let map = Map::new();
+                for (k, v) in x {
+                    map.set(&k.into(), &v.into());
+                }
+                map.into()
+            }
+        }
+    }
+}
+
+impl From<Value> for JsValue {
+    #[inline]
+    fn from(value: Value) -> Self {
+        JsValue::from(&value)
+    }
+}
+
+impl TryFrom<&JsValue> for Value {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        convert(value, DEPTH_LIMIT)
+    }
+}
+
+impl TryFrom<JsValue> for Value {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        convert(&value, DEPTH_LIMIT)
+    }
+}
+
+fn convert(value: &JsValue, depth: usize) -> Result<Value, Error> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(Value::Null);
+    }
+
+    if let Some(x) = value.as_bool() {
+        return Ok(Value::Bool(x));
+    }
+
+    if let Some(x) = value.as_string() {
+        return Ok(Value::Text(x));
+    }
+
+    if let Some(x) = value.as_f64() {
+        return Ok(match Number::is_safe_integer(value) {
+            true => Value::Integer((x as i64).into()),
+            false => Value::Float(x),
+        });
+    }
+
+    if value.is_bigint() {
+        return i128::try_from(value.clone())
+            .ok()
+            .and_then(|x| Integer::try_from(x).ok())
+            .map(Value::Integer)
+            .ok_or_else(|| Error::Custom("integer too large".into()));
+    }
+
+    if let Some(x) = value.dyn_ref::<Uint8Array>() {
+        return Ok(Value::Bytes(x.to_vec()));
+    }
+
+    let depth = depth
+        .checked_sub(1)
+        .ok_or_else(|| Error::Custom("nesting too deep".into()))?;
+
+    if let Some(x) = value.dyn_ref::<Array>() {
+        let array = x.iter().map(|v| convert(&v, depth));
+        return array.collect::<Result<_, _>>().map(Value::Array);
+    }
+
+    if let Some(x) = value.dyn_ref::<Map>() {
+        let mut entries = Vec::new();
+        x.for_each(&mut |v, k| entries.push((k, v)));
+
+        let map = entries
+            .iter()
+            .map(|(k, v)| Ok((convert(k, depth)?, convert(v, depth)?)));
+        return map.collect::<Result<_, _>>().map(Value::Map);
+    }
+
+    if let Some(x) = value.dyn_ref::<Object>() {
+        let entries = Object::entries(x);
+        let map = entries.iter().map(|entry| {
+            let entry: Array = entry.unchecked_into();
+            Ok((
+                convert(&entry.get(0), depth)?,
+                convert(&entry.get(1), depth)?,
+            ))
+        });
+        return map.collect::<Result<_, _>>().map(Value::Map);
+    }
+
+    Err(Error::Custom("unsupported JavaScript value".into()))
+}
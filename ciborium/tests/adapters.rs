@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use ciborium::{adapters::Extensible, cbor, Value};
+use rstest::rstest;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Bytes(#[serde(with = "ciborium::adapters::bytes")] Vec<u8>);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Hex(#[serde(with = "ciborium::adapters::hex")] Vec<u8>);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Text(#[serde(with = "ciborium::adapters::text")] i128);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Pairs(#[serde(with = "ciborium::adapters::pairs")] BTreeMap<Vec<u8>, bool>);
+
+fn check<T: Debug + PartialEq + Serialize + DeserializeOwned>(value: T, encoded: Value) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<Value, _>(&bytes[..]).unwrap(),
+        encoded
+    );
+    assert_eq!(ciborium::from_reader::<T, _>(&bytes[..]).unwrap(), value);
+}
+
+#[test]
+fn bytes() {
+    check(Bytes(vec![0, 255]), Value::Bytes(vec![0, 255]));
+
+    let array = cbor!([0, 255]).unwrap();
+    assert_eq!(array.deserialized::<Bytes>().unwrap(), Bytes(vec![0, 255]));
+}
+
+#[test]
+fn hex() {
+    check(Hex(vec![0x0a, 0xff]), cbor!("0aff").unwrap());
+
+    let upper = cbor!("0AFF").unwrap();
+    assert_eq!(upper.deserialized::<Hex>().unwrap(), Hex(vec![0x0a, 0xff]));
+
+    assert!(cbor!("0af").unwrap().deserialized::<Hex>().is_err());
+    assert!(cbor!("0g").unwrap().deserialized::<Hex>().is_err());
+}
+
+#[test]
+fn text() {
+    check(Text(i128::MIN), cbor!(i128::MIN.to_string()).unwrap());
+    assert!(cbor!("1.5").unwrap().deserialized::<Text>().is_err());
+}
+
+#[test]
+fn pairs() {
+    let map = [(vec![1], true), (vec![2, 3], false)].into();
+    check(Pairs(map), cbor!([[[1], true], [[2, 3], false]]).unwrap());
+}
+
+#[cfg(feature = "std")]
+mod timestamp {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Timestamp(#[serde(with = "ciborium::adapters::timestamp")] std::time::SystemTime);
+
+    fn time(secs: f64) -> Timestamp {
+        let duration = std::time::Duration::from_secs_f64(secs.abs());
+        Timestamp(match secs < 0.0 {
+            false => std::time::UNIX_EPOCH + duration,
+            true => std::time::UNIX_EPOCH - duration,
+        })
+    }
+
+    #[rstest]
+    #[case(time(1363896240.0), Value::Tag(1, Box::new(1363896240.into())))]
+    #[case(time(1363896240.5), Value::Tag(1, Box::new(1363896240.5.into())))]
+    #[case(time(-2.0), Value::Tag(1, Box::new((-2).into())))]
+    #[case(time(-0.5), Value::Tag(1, Box::new((-0.5).into())))]
+    fn timestamp(#[case] time: Timestamp, #[case] encoded: Value) {
+        check(time, encoded);
+    }
+
+    #[test]
+    fn timestamp_untagged() {
+        let value = cbor!(1363896240).unwrap();
+        assert_eq!(
+            value.deserialized::<Timestamp>().unwrap(),
+            time(1363896240.0)
+        );
+
+        let value = Value::Tag(0, Box::new(1363896240.into()));
+        assert!(value.deserialized::<Timestamp>().is_err());
+
+        let value = cbor!(f64::INFINITY).unwrap();
+        assert!(value.deserialized::<Timestamp>().is_err());
+    }
+}
+
+#[test]
+fn pair_list() {
+    use ciborium::adapters::PairList;
+
+    let pairs = PairList(vec![
+        (cbor!([1]).unwrap(), 1),
+        (Value::Null, 2),
+        (cbor!([1]).unwrap(), 3),
+    ]);
+    let map = Value::Map(
+        pairs
+            .0
+            .iter()
+            .map(|(k, v)| (k.clone(), (*v).into()))
+            .collect(),
+    );
+    check(pairs, map);
+
+    let pairs: PairList<u8, u8> = [(2, 0), (1, 0)].into_iter().collect();
+    check(pairs, cbor!({ 2 => 0, 1 => 0 }).unwrap());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle(u8),
+    Rect { w: u8, h: u8 },
+}
+
+#[rstest]
+#[case(Extensible::Known(Shape::Point), cbor!("Point"))]
+#[case(Extensible::Known(Shape::Circle(3)), cbor!({ "Circle" => 3 }))]
+#[case(Extensible::Known(Shape::Rect { w: 1, h: 2 }), cbor!({ "Rect" => { "w" => 1, "h" => 2 } }))]
+#[case(Extensible::Unknown { variant: "Line".into(), content: None }, cbor!("Line"))]
+#[case(
+    Extensible::Unknown { variant: "Star".into(), content: Some(cbor!([5, 2]).unwrap()) },
+    cbor!({ "Star" => [5, 2] })
+)]
+#[case(
+    Extensible::Unknown { variant: 7.into(), content: Some(Value::Null) },
+    cbor!({ 7 => null })
+)]
+fn extensible(
+    #[case] shape: Extensible<Shape>,
+    #[case] encoded: Result<Value, ciborium::value::Error>,
+) {
+    check(shape, encoded.unwrap());
+}
+
+#[test]
+fn extensible_invalid() {
+    // Known variants with invalid payloads are not captured.
+    let value = cbor!({ "Circle" => "big" }).unwrap();
+    assert!(value.deserialized::<Extensible<Shape>>().is_err());
+
+    // Types other than enums are simply passed through.
+    let value = cbor!(5).unwrap();
+    let number: Extensible<u8> = value.deserialized().unwrap();
+    assert_eq!(number, Extensible::Known(5));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Number(u8),
+    Text(String),
+}
+
+#[test]
+fn extensible_untagged() {
+    // An untagged enum reveals no variant names, so every input is taken
+    // for a known variant and an unrecognized one is not captured.
+    let value = cbor!("x").unwrap();
+    let known: Extensible<Untagged> = value.deserialized().unwrap();
+    assert_eq!(known, Extensible::Known(Untagged::Text("x".into())));
+
+    let value = cbor!({ "Star" => [5, 2] }).unwrap();
+    assert!(value.deserialized::<Extensible<Untagged>>().is_err());
+}
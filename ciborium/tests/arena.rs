@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "bumpalo")]
+
+use bumpalo::Bump;
+use ciborium::{arena, cbor, Value};
+use rstest::rstest;
+
+#[rstest]
+#[case(cbor!(null).unwrap())]
+#[case(cbor!(-1).unwrap())]
+#[case(cbor!(u64::MAX).unwrap())]
+#[case(cbor!(1.5).unwrap())]
+#[case(cbor!("text").unwrap())]
+#[case(Value::Bytes(vec![1, 2, 3]))]
+#[case(Value::Tag(32, Box::new(cbor!("http://x").unwrap())))]
+#[case(cbor!([1, [2, "three"], { "a" => { 1 => [true, false] } }]).unwrap())]
+fn roundtrip(#[case] value: Value) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let bump = Bump::new();
+    let decoded = arena::from_reader(&bytes[..], &bump).unwrap();
+    assert_eq!(Value::from(decoded), value);
+    assert_eq!(arena::from_slice(&bytes, &bump).unwrap(), decoded);
+
+    let mut encoded = Vec::new();
+    ciborium::into_writer(&decoded, &mut encoded).unwrap();
+    assert_eq!(encoded, bytes);
+}
+
+#[test]
+fn bignum() {
+    // A bignum too large for an integer remains a tagged byte string.
+    let bytes = hex::decode("c2510100000000000000000000000000000000").unwrap();
+
+    let bump = Bump::new();
+    match arena::from_slice(&bytes, &bump).unwrap() {
+        arena::Value::Tag(2, arena::Value::Bytes(x)) => assert_eq!(x.len(), 17),
+        value => panic!("unexpected value: {:?}", value),
+    }
+}
+
+#[test]
+fn seed() {
+    let bytes = hex::decode("826161f5").unwrap();
+
+    let bump = Bump::new();
+    let value = ciborium::de::from_reader_seed(&bytes[..], arena::Seed(&bump)).unwrap();
+    assert_eq!(
+        value.as_array().unwrap(),
+        [arena::Value::Text("a"), arena::Value::Bool(true)]
+    );
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "async")]
+
+use ciborium::de::{from_async_reader, Error};
+use ciborium::ser::into_async_writer;
+use ciborium::{cbor, Value};
+use ciborium_io::AsyncRead;
+
+use embassy_futures::{block_on, yield_now};
+use rstest::rstest;
+use serde::Deserialize;
+
+/// Yields to the executor before every read
+struct Yielding<'a>(&'a [u8]);
+
+impl AsyncRead for Yielding<'_> {
+    type Error = ();
+
+    async fn read_exact(&mut self, data: &mut [u8]) -> Result<(), ()> {
+        yield_now().await;
+
+        if data.len() > self.0.len() {
+            return Err(());
+        }
+
+        let (head, tail) = self.0.split_at(data.len());
+        data.copy_from_slice(head);
+        self.0 = tail;
+        Ok(())
+    }
+}
+
+#[rstest]
+#[case("01")]
+#[case("20")]
+#[case("f93c00")]
+#[case("fb3ff199999999999a")]
+#[case("40")]
+#[case("4401020304")]
+#[case("6161")]
+#[case("80")]
+#[case("a0")]
+#[case("8301820203820405")]
+#[case("a201020304")]
+#[case("c24101")]
+#[case("d818d81801")]
+#[case("5f4101420203ff")]
+#[case("7f61616162ff")]
+#[case("9f01d8189f02ffff")]
+#[case("bf6161f5ff")]
+#[case("9fbf6161819f40ffffff")]
+fn items(#[case] item: &str) {
+    let mut bytes = hex::decode(item).unwrap();
+    bytes.extend_from_slice(&[0xf6]);
+
+    let mut reader = Yielding(&bytes);
+    let mut buffer = [0u8; 64];
+    let value: Value = block_on(from_async_reader(&mut reader, &mut buffer)).unwrap();
+    assert_eq!(
+        value,
+        ciborium::from_reader::<Value, _>(&bytes[..]).unwrap()
+    );
+
+    // Nothing past the item is read.
+    assert_eq!(reader.0, [0xf6]);
+}
+
+#[rstest]
+#[case("ff", 64, Error::Syntax(0))]
+#[case("1c", 64, Error::Syntax(0))]
+#[case("82ff", 64, Error::Syntax(1))]
+#[case("1b00", 64, Error::Io(()))]
+#[case("6261", 64, Error::Io(()))]
+#[case("4401020304", 4, Error::Semantic(Some(1), "".into()))]
+fn invalid(#[case] item: &str, #[case] size: usize, #[case] expected: Error<()>) {
+    let bytes = hex::decode(item).unwrap();
+    let mut buffer = vec![0u8; size];
+    let result: Result<Value, _> = block_on(from_async_reader(&mut Yielding(&bytes), &mut buffer));
+
+    match (result.unwrap_err(), expected) {
+        (Error::Io(()), Error::Io(())) => (),
+        (Error::Syntax(x), Error::Syntax(y)) => assert_eq!(x, y),
+        (Error::Semantic(x, _), Error::Semantic(y, _)) => assert_eq!(x, y),
+        (e, _) => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn depth() {
+    let mut buffer = [0u8; 64];
+
+    let bytes = [[0x81; 31].as_slice(), &[0x01]].concat();
+    let value: Result<Value, _> = block_on(from_async_reader(&mut &bytes[..], &mut buffer));
+    assert!(value.is_ok());
+
+    let bytes = [[0x81; 33].as_slice(), &[0x01]].concat();
+    let value: Result<Value, _> = block_on(from_async_reader(&mut &bytes[..], &mut buffer));
+    assert!(matches!(value, Err(Error::RecursionLimitExceeded)));
+}
+
+#[test]
+fn borrowed() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Message<'a> {
+        topic: &'a str,
+        seq: u32,
+    }
+
+    let mut bytes = Vec::new();
+    let mut buffer = [0u8; 32];
+    for seq in 0..3 {
+        let message = cbor!({ "topic" => "news", "seq" => seq }).unwrap();
+        block_on(into_async_writer(&message, &mut bytes, &mut buffer)).unwrap();
+    }
+
+    let mut reader = Yielding(&bytes);
+    for seq in 0..3 {
+        let message: Message = block_on(from_async_reader(&mut reader, &mut buffer)).unwrap();
+        assert_eq!(message, Message { topic: "news", seq });
+    }
+
+    assert!(reader.0.is_empty());
+}
+
+#[test]
+fn too_large() {
+    let mut buffer = [0u8; 4];
+    let result = block_on(into_async_writer("too long", &mut Vec::new(), &mut buffer));
+    assert!(matches!(result, Err(ciborium::ser::Error::Value(..))));
+}
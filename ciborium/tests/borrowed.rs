@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{from_slice, from_slice_with_options, Options};
+use ciborium::Value;
+use rstest::rstest;
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Record<'a> {
+    name: &'a str,
+    data: &'a [u8],
+    #[serde(with = "serde_bytes")]
+    more: &'a [u8],
+    id: [u8; 4],
+    n: i32,
+}
+
+// {"name": "abc", "data": h'0102', "more": h'03', "id": [1, 2, 3, 4], "n": -2}
+const RECORD: &str = "a5646e616d65636162636464617461420102646d6f726541036269648401020304616e21";
+
+#[test]
+fn borrow() {
+    let bytes = hex::decode(RECORD).unwrap();
+    let record: Record<'_> = from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        record,
+        Record {
+            name: "abc",
+            data: &[1, 2],
+            more: &[3],
+            id: [1, 2, 3, 4],
+            n: -2,
+        }
+    );
+
+    // The strings point into the input.
+    assert!(bytes.as_ptr_range().contains(&record.name.as_ptr()));
+    assert!(bytes.as_ptr_range().contains(&record.data.as_ptr()));
+}
+
+#[test]
+fn borrow_without_allocating() {
+    let bytes = hex::decode(RECORD).unwrap();
+    let options = Options::new().allocate(false);
+    let record: Record<'_> = from_slice_with_options(&bytes, options).unwrap();
+    assert_eq!(record.name, "abc");
+}
+
+#[test]
+fn borrow_invalid_utf8() {
+    assert!(from_slice::<&str>(&[0x62, 0xc3, 0x28]).is_err());
+}
+
+#[test]
+fn borrow_segmented() {
+    // Segmented strings cannot be borrowed, but can still be copied.
+    let bytes = hex::decode("7f61616162ff").unwrap();
+    assert!(from_slice::<&str>(&bytes).is_err());
+    assert_eq!(from_slice::<String>(&bytes).unwrap(), "ab");
+}
+
+#[test]
+fn allocation_required() {
+    let options = Options::new().allocate(false);
+
+    let text = hex::decode("7f61616162ff").unwrap();
+    assert!(from_slice_with_options::<String>(&text, options).is_err());
+    assert!(from_slice::<String>(&text).is_ok());
+
+    let bytes = hex::decode("5f41014102ff").unwrap();
+    assert!(from_slice_with_options::<ByteBuf>(&bytes, options).is_err());
+    assert!(from_slice::<ByteBuf>(&bytes).is_ok());
+
+    // Byte strings visited as sequences are collected first.
+    let bytes = hex::decode("43010203").unwrap();
+    assert!(from_slice_with_options::<Vec<u8>>(&bytes, options).is_err());
+    assert!(from_slice::<Vec<u8>>(&bytes).is_ok());
+}
+
+#[rstest]
+#[case::small("c2420100", true)]
+#[case::large("c2510100000000000000000000000000000000", false)]
+fn bignum(#[case] bytes: &str, #[case] ok: bool) {
+    let bytes = hex::decode(bytes).unwrap();
+    let options = Options::new().allocate(false);
+
+    let result = from_slice_with_options::<Value>(&bytes, options);
+    assert_eq!(result.is_ok(), ok);
+    assert!(from_slice::<Value>(&bytes).is_ok());
+}
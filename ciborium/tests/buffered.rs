@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "std")]
+
+use std::io::BufReader;
+
+use ciborium::de::{deserializer_from_reader_with_buffer, from_reader, from_slice, Error};
+use ciborium::value::Value;
+use ciborium_io::Buffered;
+use rstest::rstest;
+use serde::Deserialize;
+
+#[rstest(capacity, case(1), case(2), case(3), case(5), case(9), case(64))]
+fn straddle(capacity: usize) {
+    // Headers of every width, so that some straddle the buffer boundary.
+    let bytes = hex::decode(concat!(
+        "a3",
+        "6161",
+        "1818",
+        "6162",
+        "8319010019ffff1a00010000",
+        "6163",
+        "c25b00000000000000080102030405060708",
+    ))
+    .unwrap();
+
+    let expected: Value = from_reader(&bytes[..]).unwrap();
+    let reader = Buffered::new(BufReader::with_capacity(capacity, &bytes[..]));
+    let actual: Value = from_reader(reader).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn trailing() {
+    let bytes = hex::decode("0102").unwrap();
+    let mut reader = BufReader::new(&bytes[..]);
+
+    let first: u8 = from_reader(Buffered::new(&mut reader)).unwrap();
+    let second: u8 = from_reader(Buffered::new(&mut reader)).unwrap();
+    assert_eq!((first, second), (1, 2));
+}
+
+#[test]
+fn syntax() {
+    let bytes = hex::decode("821c").unwrap();
+    match from_reader::<Value, _>(Buffered::new(&bytes[..])).unwrap_err() {
+        Error::Syntax(1) => (),
+        e => panic!("incorrect error: {:?}", e),
+    }
+}
+
+#[test]
+fn no_scratch() {
+    let bytes = hex::decode("a2616b4401020304626b6b6576616c7565").unwrap();
+    let expected: Value = from_reader(&bytes[..]).unwrap();
+
+    let mut scratch = [0u8; 0];
+    let reader = Buffered::new(&bytes[..]);
+    let mut deserializer = deserializer_from_reader_with_buffer(reader, &mut scratch);
+    assert_eq!(Value::deserialize(&mut deserializer).unwrap(), expected);
+}
+
+#[test]
+fn slice_larger_than_scratch() {
+    let text = "x".repeat(5000);
+    let mut bytes = hex::decode("791388").unwrap();
+    bytes.extend_from_slice(text.as_bytes());
+
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value, Value::Text(text.clone()));
+
+    let value: String = from_slice(&bytes).unwrap();
+    assert_eq!(value, text);
+}
@@ -4,7 +4,7 @@ extern crate std;
 
 use ciborium::cbor;
 use ciborium::tag::Required;
-use ciborium::value::CanonicalValue;
+use ciborium::value::{CanonicalValue, Duplicates, KeyOrder, Value};
 use rand::prelude::*;
 use std::collections::BTreeMap;
 
@@ -109,3 +109,199 @@ fn tagged_option() {
     let output = ciborium::de::from_reader(&bytes[..]).unwrap();
     assert_eq!(opt, output);
 }
+
+#[test]
+fn matches_encoding() {
+    use std::cmp::Ordering;
+
+    fn encoded_cmp(v1: &Value, v2: &Value) -> Ordering {
+        let mut bytes1 = Vec::new();
+        ciborium::ser::into_writer(v1, &mut bytes1).unwrap();
+        let mut bytes2 = Vec::new();
+        ciborium::ser::into_writer(v2, &mut bytes2).unwrap();
+        bytes1.len().cmp(&bytes2.len()).then(bytes1.cmp(&bytes2))
+    }
+
+    let values = vec![
+        val!(0),
+        val!(24),
+        val!(-25),
+        val!(1000),
+        val!(u64::MAX),
+        Value::from(u128::MAX),
+        Value::from(i128::MIN),
+        Value::from(u128::from(u64::MAX) + 1),
+        val!(1.5),
+        val!(1.0e300),
+        val!(f64::NAN),
+        val!(f64::INFINITY),
+        val!(0.0),
+        val!(-0.0),
+        val!(-1.5),
+        val!(65504.0),
+        val!(65505.0),
+        val!(0.1),
+        val!(1.0e10),
+        val!(-1.0e-300),
+        val!("a"),
+        val!("abc"),
+        Value::Bytes(vec![1]),
+        Value::Bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Value::Bytes(vec![2, 0, 0, 0, 0, 0, 0, 0, 0]),
+        val!(true),
+        val!(null),
+        cbor!([]).unwrap(),
+        cbor!([1, [2, 3]]).unwrap(),
+        cbor!([1, [2, 4]]).unwrap(),
+        cbor!([1, "x", [false]]).unwrap(),
+        cbor!({ 1 => 2 }).unwrap(),
+        cbor!({ 1 => [3] }).unwrap(),
+        cbor!({ "a" => null, "b" => [] }).unwrap(),
+        Value::Tag(0, Box::new(val!("2013-03-21T20:04:00Z"))),
+        Value::Tag(2, Box::new(Value::Bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]))),
+        Value::Tag(2, Box::new(Value::Bytes(vec![2, 0, 0, 0, 0, 0, 0, 0, 0]))),
+        Value::Tag(2, Box::new(Value::Bytes(vec![0xff; 16]))),
+        Value::from(u128::MAX >> 56),
+        Value::from(1u128 << 120),
+        Value::from(-(1i128 << 64) - 1),
+        Value::Tag(3, Box::new(val!(1))),
+    ];
+
+    // Pairs of the same kind have dedicated rules which need not follow the
+    // encoding, so only mixed kinds, tags and containers are checked here.
+    let kind = |v: &Value| match v {
+        Value::Integer(..) => 0,
+        Value::Text(..) => 1,
+        Value::Bool(..) => 2,
+        Value::Null => 3,
+        _ => 4,
+    };
+
+    for v1 in &values {
+        for v2 in &values {
+            if kind(v1) == kind(v2) && kind(v1) != 4 {
+                continue;
+            }
+
+            let c1 = CanonicalValue::from(v1.clone());
+            let c2 = CanonicalValue::from(v2.clone());
+            assert_eq!(c1.cmp(&c2), encoded_cmp(v1, v2), "{:?} vs {:?}", v1, v2);
+        }
+    }
+}
+
+#[rstest::rstest]
+#[case(KeyOrder::LengthFirst, cbor!([10, -1, false, 100, "z", [-1], "aa", [100]]).unwrap())]
+#[case(KeyOrder::Lexical, cbor!([10, 100, -1, "z", "aa", [100], [-1], false]).unwrap())]
+#[case(KeyOrder::LengthFirst, cbor!([10, "z", [-1], "aa", 1.5, 100000.0, 0.1]).unwrap())]
+#[case(KeyOrder::Lexical, cbor!([10, "z", "aa", [-1], 1.5, 100000.0, 0.1]).unwrap())]
+fn sort_keys(#[case] order: KeyOrder, #[case] golden: Value) {
+    let keys = golden.as_array().unwrap();
+    let entries = |keys: &[Value]| keys.iter().map(|k| (k.clone(), Value::Null)).collect();
+
+    let mut shuffled = keys.clone();
+    shuffled.shuffle(&mut rand::thread_rng());
+
+    let mut value = Value::Tag(24, Box::new(Value::Map(entries(&shuffled))));
+    let mut nested = Value::Array(vec![value.clone()]);
+
+    value.sort_keys(order, false);
+    assert_eq!(
+        value,
+        Value::Tag(24, Box::new(Value::Map(entries(&shuffled))))
+    );
+
+    nested.sort_keys(order, true);
+    let sorted = Value::Tag(24, Box::new(Value::Map(entries(keys))));
+    assert_eq!(nested, Value::Array(vec![sorted]));
+}
+
+#[rstest::rstest]
+#[case(Duplicates::First, Ok(cbor!({ 1 => "a", 2 => "b", { 3 => "c" } => "d" }).unwrap()))]
+#[case(Duplicates::Last, Ok(cbor!({ 2 => "b", 1 => "f", { 3 => "c" } => "g" }).unwrap()))]
+#[case(Duplicates::Reject, Err("duplicate map key 3"))]
+fn dedup_keys(#[case] policy: Duplicates, #[case] expected: Result<Value, &str>) {
+    let mut value = cbor!({
+        1 => "a",
+        2 => "b",
+        { 3 => "c", 3 => "c" } => "d",
+        1 => "e",
+        1 => "f",
+        { 3 => "c" } => "g",
+    })
+    .unwrap();
+
+    let result = value.dedup_keys(policy, true).map(|()| value);
+    match (result, expected) {
+        (Ok(value), Ok(expected)) => assert_eq!(value, expected),
+        (Err(ciborium::value::Error::Custom(msg)), Err(expected)) => assert_eq!(msg, expected),
+        (result, expected) => panic!("{:?} != {:?}", result, expected),
+    }
+}
+
+#[rstest::rstest]
+#[case(KeyOrder::LengthFirst)]
+#[case(KeyOrder::Lexical)]
+fn get_sorted(#[case] order: KeyOrder) {
+    let keys = cbor!([10, -1, false, 100, "z", [-1], "aa", [100]]).unwrap();
+    let entries = keys.as_array().unwrap().iter().enumerate();
+    let mut map = Value::Map(
+        entries
+            .map(|(i, k)| (k.clone(), (i as u64).into()))
+            .collect(),
+    );
+    map.sort_keys(order, false);
+
+    for (i, key) in keys.as_array().unwrap().iter().enumerate() {
+        assert_eq!(map.get_sorted(key, order), Some(&(i as u64).into()));
+    }
+
+    assert_eq!(map.get_sorted(&val!(1), order), None);
+    assert_eq!(map.get_sorted(&val!("zz"), order), None);
+    assert_eq!(val!([1]).get_sorted(&val!(0), order), None);
+
+    *map.get_sorted_mut(&val!("z"), order).unwrap() = val!(true);
+    assert_eq!(map.get_sorted(&val!("z"), order), Some(&val!(true)));
+}
+
+#[test]
+fn big_integer_keys() {
+    use std::collections::HashMap;
+
+    let unsigned: HashMap<u128, u8> = [0, 1 << 64, u128::MAX >> 56, 1 << 120, u128::MAX]
+        .into_iter()
+        .zip(0..)
+        .collect();
+    let signed: HashMap<i128, u8> = [0, -1, i128::MIN, -(1 << 64) - 1, i128::MAX]
+        .into_iter()
+        .zip(0..)
+        .collect();
+
+    let mut value = Value::serialized(&unsigned).unwrap();
+    value.sort_keys(KeyOrder::LengthFirst, true);
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<HashMap<u128, u8>, _>(&bytes[..]).unwrap(),
+        unsigned
+    );
+
+    // The keys are written in encoded order: the shortest bignums first.
+    let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+    let keys: Vec<u128> = value
+        .into_map()
+        .unwrap()
+        .into_iter()
+        .map(|(k, _)| k.deserialized().unwrap())
+        .collect();
+    assert_eq!(keys, [0, 1 << 64, u128::MAX >> 56, 1 << 120, u128::MAX]);
+
+    let mut value = Value::serialized(&signed).unwrap();
+    value.sort_keys(KeyOrder::LengthFirst, true);
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<HashMap<i128, u8>, _>(&bytes[..]).unwrap(),
+        signed
+    );
+}
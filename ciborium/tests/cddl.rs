@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use ciborium::cddl::{schema, Cddl, Schema};
+use ciborium::tag::{Accepted, Required, RequiredOneOf, TagSet};
+use ciborium::Value;
+use rstest::rstest;
+
+struct Dates;
+
+impl TagSet for Dates {
+    const TAGS: &'static [u64] = &[0, 1];
+}
+
+/// A recursive type with a named rule
+struct Tree;
+
+impl Cddl for Tree {
+    fn cddl(schema: &mut Schema) -> String {
+        schema.rule("Tree", Vec::<Tree>::cddl)
+    }
+}
+
+fn expr<T: Cddl + ?Sized>() -> String {
+    T::cddl(&mut Schema::new())
+}
+
+#[rstest]
+#[case(expr::<bool>(), "bool")]
+#[case(expr::<u16>(), "uint")]
+#[case(expr::<i64>(), "int")]
+#[case(expr::<u128>(), "uint / biguint")]
+#[case(expr::<f32>(), "float")]
+#[case(expr::<&str>(), "tstr")]
+#[case(expr::<Value>(), "any")]
+#[case(expr::<()>(), "null")]
+#[case(expr::<Option<String>>(), "tstr / null")]
+#[case(expr::<Vec<u8>>(), "[* uint]")]
+#[case(expr::<[i8; 4]>(), "[4*4 int]")]
+#[case(expr::<(u8, String)>(), "[uint, tstr]")]
+#[case(expr::<BTreeMap<String, Vec<bool>>>(), "{* tstr => [* bool]}")]
+#[case(expr::<Required<u64, 1>>(), "#6.1(uint)")]
+#[case(expr::<Accepted<String, 32>>(), "#6.32(tstr) / tstr")]
+#[case(expr::<RequiredOneOf<String, Dates>>(), "#6.0(tstr) / #6.1(tstr)")]
+fn types(#[case] cddl: String, #[case] expected: &str) {
+    assert_eq!(cddl, expected);
+}
+
+#[test]
+fn recursive() {
+    assert_eq!(schema::<Tree>(), "Tree = [* Tree]\n");
+    assert_eq!(
+        schema::<Option<Tree>>(),
+        "root = Tree / null\nTree = [* Tree]\n"
+    );
+}
@@ -1,5 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
+// The float cases are written with the `core::f32` and `core::f64` constants.
+#![allow(unknown_lints, clippy::legacy_numeric_constants)]
+
 extern crate std;
 
 use std::collections::{BTreeMap, HashMap};
@@ -439,3 +442,18 @@ fn handle_struct_field_names(input: &str, expected: Foo) {
     let read = from_reader(&buf[..]).unwrap();
     assert_eq!(expected, read);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn serde_json_names() {
+    let foo = Foo { bar: 42 };
+
+    let vec = ciborium::to_vec(&foo).unwrap();
+    let mut writer = Vec::new();
+    ciborium::to_writer(&foo, &mut writer).unwrap();
+    assert_eq!(vec, writer);
+    assert_eq!(vec, ciborium::into_vec(&foo).unwrap());
+
+    assert_eq!(ciborium::from_slice::<Foo>(&vec).unwrap(), foo);
+    assert_eq!(ciborium::from_reader::<Foo, _>(&vec[..]).unwrap(), foo);
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "compact")]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use ciborium::{cbor, compact, value::Integer, Value};
+use rstest::rstest;
+
+#[rstest]
+#[case(cbor!(null).unwrap())]
+#[case(cbor!(-1).unwrap())]
+#[case(cbor!(u64::MAX).unwrap())]
+#[case(Value::Integer(Integer::try_from(-(1i128 << 64)).unwrap()))]
+#[case(cbor!(1.5).unwrap())]
+#[case(cbor!("text").unwrap())]
+#[case(Value::Bytes(vec![1, 2, 3]))]
+#[case(Value::Tag(32, Box::new(cbor!("http://x").unwrap())))]
+#[case(cbor!([1, [2, "three"], { "a" => { 1 => [true, false] } }]).unwrap())]
+fn roundtrip(#[case] value: Value) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let decoded: compact::Value = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(Value::from(decoded.clone()), value);
+    assert_eq!(compact::Value::from(value), decoded);
+
+    let mut encoded = Vec::new();
+    ciborium::into_writer(&decoded, &mut encoded).unwrap();
+    assert_eq!(encoded, bytes);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn size() {
+    assert_eq!(core::mem::size_of::<Value>(), 32);
+    assert_eq!(core::mem::size_of::<compact::Value>(), 24);
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+#[case(-1)]
+#[case(u64::MAX.into())]
+#[case(-(1i128 << 64))]
+fn integer(#[case] x: i128) {
+    fn hash(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let full = Integer::try_from(x).unwrap();
+    let compact = compact::Integer::from(full);
+
+    assert_eq!(i128::from(compact), x);
+    assert_eq!(Integer::from(compact), full);
+    assert_eq!(hash(compact), hash(full));
+    assert_eq!(compact.to_string(), x.to_string());
+
+    let zero = compact::Integer::from(0u64);
+    assert_eq!(compact.cmp(&zero), x.cmp(&0));
+}
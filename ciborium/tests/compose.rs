@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{Deserializer, Options};
+use ciborium::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Outer {
+    inner: Vec<Inner>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    value: u8,
+}
+
+#[test]
+fn path_to_error() {
+    // {"inner": [{"value": 1}, {"value": "x"}]}
+    let bytes = hex::decode("a165696e6e657282a16576616c756501a16576616c75656178").unwrap();
+
+    let mut scratch = [0u8; 64];
+    let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    let error = serde_path_to_error::deserialize::<_, Outer>(&mut deserializer).unwrap_err();
+    assert_eq!(error.path().to_string(), "inner[1].value");
+}
+
+#[test]
+fn serialize() {
+    let outer = Outer {
+        inner: vec![Inner { value: 1 }],
+    };
+
+    let mut serializer = Serializer::new(Vec::new());
+    serde_path_to_error::serialize(&outer, &mut serializer).unwrap();
+    let bytes = serializer.into_inner();
+
+    let mut scratch = [0u8; 64];
+    let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    assert_eq!(Outer::deserialize(&mut deserializer).unwrap(), outer);
+}
+
+#[test]
+fn encoder_mut() {
+    use ciborium_io::Write;
+    use ciborium_ll::Header;
+
+    let mut inner = Vec::new();
+    ciborium::into_writer(&Inner { value: 1 }, &mut inner).unwrap();
+
+    // An array of a serialized item followed by a pre-encoded one.
+    let mut serializer = Serializer::new(Vec::new());
+    serializer
+        .encoder_mut()
+        .push(Header::Array(Some(2)))
+        .unwrap();
+    Inner { value: 2 }.serialize(&mut serializer).unwrap();
+    serializer.encoder_mut().write_all(&inner).unwrap();
+    let bytes = serializer.into_inner();
+
+    let mut scratch = [0u8; 64];
+    let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    let items = Vec::<Inner>::deserialize(&mut deserializer).unwrap();
+    assert_eq!(items, [Inner { value: 2 }, Inner { value: 1 }]);
+}
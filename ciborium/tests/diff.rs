@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::value::{diff, Value};
+use ciborium::{assert_cbor_eq, cbor};
+use rstest::rstest;
+
+fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    let error = std::panic::catch_unwind(f).unwrap_err();
+    match error.downcast::<String>() {
+        Ok(message) => *message,
+        Err(..) => panic!("unexpected panic payload"),
+    }
+}
+
+fn h(bytes: &[u8]) -> Value {
+    Value::Bytes(bytes.into())
+}
+
+#[rstest]
+#[case(cbor!(-1000).unwrap(), "-1000")]
+#[case(cbor!(u64::MAX).unwrap(), "18446744073709551615")]
+#[case(cbor!(1.0).unwrap(), "1.0")]
+#[case(Value::Float(f64::NAN), "NaN")]
+#[case(Value::Float(f64::NEG_INFINITY), "-Infinity")]
+#[case(h(&[]), "h''")]
+#[case(cbor!("a\"b\\c\n").unwrap(), r#""a\"b\\c\u000a""#)]
+#[case(cbor!([true, false, null]).unwrap(), "[true, false, null]")]
+#[case(cbor!({}).unwrap(), "{}")]
+#[case(Value::Tag(32, Box::new(cbor!("x").unwrap())), r#"32("x")"#)]
+fn diagnostic(#[case] value: Value, #[case] text: &str) {
+    assert_eq!(value.diagnostic().to_string(), text);
+}
+
+#[rstest]
+#[case(cbor!(1).unwrap(), cbor!("1").unwrap(), "$")]
+#[case(cbor!([1, 2]).unwrap(), cbor!([1, 2, 3]).unwrap(), "$[2]")]
+#[case(cbor!({ "a" => [{ 1 => 2 }] }).unwrap(), cbor!({ "a" => [{ 1 => 3 }] }).unwrap(), r#"$["a"][0][1]"#)]
+#[case(cbor!({ "a" => 1 }).unwrap(), cbor!({ "b" => 1 }).unwrap(), r#"$["a"]"#)]
+#[case(cbor!({ "a" => 1 }).unwrap(), cbor!({ "a" => 1, "b" => 2 }).unwrap(), r#"$["b"]"#)]
+#[case(Value::Float(0.0), Value::Float(-0.0), "$")]
+fn difference(#[case] expected: Value, #[case] actual: Value, #[case] path: &str) {
+    assert_eq!(diff(&expected, &actual).unwrap().path(), path);
+}
+
+#[test]
+fn equal() {
+    let nan = Value::Array(vec![Value::Float(f64::NAN)]);
+    assert!(diff(&nan, &nan).is_none());
+
+    let value = cbor!({ "a" => [1, { "b" => h(&[1, 2]) }] }).unwrap();
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    assert_cbor_eq!(value, bytes);
+    assert_cbor_eq!(bytes, value);
+    assert_cbor_eq!(&bytes[..], bytes.clone());
+}
+
+#[test]
+fn messages() {
+    let message = panic_message(|| {
+        assert_cbor_eq!(cbor!([1, "x"]).unwrap(), cbor!([1]).unwrap());
+    });
+    assert_eq!(
+        message,
+        "assertion failed: CBOR differs at $[1]\n  expected: \"x\"\n    actual: <missing>"
+    );
+
+    let message = panic_message(|| {
+        let expected = cbor!({ 1 => 2, 3 => 4 }).unwrap();
+        assert_cbor_eq!(expected, cbor!({ 3 => 4, 1 => 2 }).unwrap());
+    });
+    assert_eq!(
+        message,
+        "assertion failed: CBOR differs at $ (keys in a different order)\n  expected: {1: 2, 3: 4}\n    actual: {3: 4, 1: 2}"
+    );
+
+    // The integer 1, encoded in its shortest form and in two bytes.
+    let message = panic_message(|| assert_cbor_eq!([0x01], [0x18, 0x01]));
+    assert_eq!(
+        message,
+        "assertion failed: CBOR encodes the same value differently\n  expected: 01\n    actual: 1801"
+    );
+
+    let message = panic_message(|| assert_cbor_eq!([0x82, 0x01], cbor!(1).unwrap()));
+    assert!(message.starts_with("invalid CBOR"), "{}", message);
+}
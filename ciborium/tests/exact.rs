@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{from_slice_exact, Error};
+use ciborium::{cbor, Value};
+use rstest::rstest;
+
+#[rstest]
+#[case("01", Ok(1.into()))]
+#[case("8201820203", Ok(cbor!([1, [2, 3]]).unwrap()))]
+#[case("0102", Err(Some(1)))]
+#[case("820102ff", Err(Some(3)))]
+#[case("9f01ff00", Err(Some(3)))]
+#[case("", Err(None))]
+#[case("8201", Err(None))]
+fn exact(#[case] bytes: &str, #[case] expected: Result<Value, Option<u64>>) {
+    let bytes = hex::decode(bytes).unwrap();
+
+    let result = from_slice_exact::<Value>(&bytes);
+    match (&result, &expected) {
+        (Ok(value), Ok(expected)) => assert_eq!(value, expected),
+        (Err(Error::Semantic(offset, _)), Err(Some(expected))) => {
+            assert_eq!(offset, &Some(*expected))
+        }
+        (Err(Error::Io(..)), Err(None)) => (),
+        _ => panic!("{:?} != {:?}", result, expected),
+    }
+
+    #[cfg(feature = "std")]
+    match (
+        ciborium::de::from_reader_exact::<Value, _>(&bytes[..]),
+        expected,
+    ) {
+        (Ok(value), Ok(expected)) => assert_eq!(value, expected),
+        (Err(Error::Semantic(offset, _)), Err(Some(expected))) => {
+            assert_eq!(offset, Some(expected))
+        }
+        (Err(Error::Io(..)), Err(None)) => (),
+        (result, expected) => panic!("{:?} != {:?}", result, expected),
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "heapless")]
+
+use ciborium::de::{from_slice, from_slice_with_options, Error, Options};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Vecs {
+    #[serde(with = "ciborium::heapless::vec")]
+    items: heapless::Vec<u16, 3>,
+
+    #[serde(with = "ciborium::heapless::bytes")]
+    bytes: heapless::Vec<u8, 2>,
+
+    text: heapless::String<4>,
+}
+
+fn vecs(items: &[u16], bytes: &[u8], text: &str) -> Vecs {
+    Vecs {
+        items: heapless::Vec::from_slice(items).unwrap(),
+        bytes: heapless::Vec::from_slice(bytes).unwrap(),
+        text: text.try_into().unwrap(),
+    }
+}
+
+#[rstest]
+#[case(vecs(&[], &[], ""))]
+#[case(vecs(&[1, 2, 3], &[4, 5], "abcd"))]
+fn roundtrip(#[case] value: Vecs) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let options = Options::new().allocate(false);
+    let decoded: Vecs = from_slice_with_options(&bytes, options).unwrap();
+    assert_eq!(decoded, value);
+}
+
+// {"items": [...], "bytes": ..., "text": ...}
+#[rstest]
+#[case::items(
+    "a3 656974656d73 8401020304 656279746573 420405 6474657874 6461626364",
+    "invalid length 4, expected at most 3 elements"
+)]
+#[case::items_indefinite(
+    "a3 656974656d73 9f01020304ff 656279746573 420405 6474657874 6461626364",
+    "invalid length 4, expected at most 3 elements"
+)]
+#[case::bytes(
+    "a3 656974656d73 80 656279746573 43040506 6474657874 60",
+    "invalid length 3, expected at most 2 bytes"
+)]
+#[case::bytes_array(
+    "a3 656974656d73 80 656279746573 83040506 6474657874 60",
+    "invalid length 3, expected at most 2 bytes"
+)]
+#[case::text(
+    "a3 656974656d73 80 656279746573 40 6474657874 656162636465",
+    "invalid length 5, expected a string no more than 4 bytes long"
+)]
+fn overflow(#[case] bytes: &str, #[case] message: &str) {
+    let bytes = hex::decode(bytes.replace(' ', "")).unwrap();
+
+    match from_slice::<Vecs>(&bytes) {
+        Err(Error::Semantic(None, msg)) => assert_eq!(msg, message),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
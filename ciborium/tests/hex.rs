@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "hex")]
+
+use ciborium::de::Error;
+use ciborium::{cbor, from_hex_str, to_hex_string, Value};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Header {
+    alg: i8,
+    kid: String,
+}
+
+#[test]
+fn round_trip() {
+    let header = Header {
+        alg: -7,
+        kid: "k1".into(),
+    };
+
+    let hex = to_hex_string(&header).unwrap();
+    assert_eq!(hex, "a263616c6726636b6964626b31");
+    assert_eq!(from_hex_str::<Header>(&hex).unwrap(), header);
+    assert_eq!(from_hex_str::<Header>(&hex.to_uppercase()).unwrap(), header);
+
+    let value = Value::from_hex(&hex).unwrap();
+    assert_eq!(value, cbor!({ "alg" => -7, "kid" => "k1" }).unwrap());
+    assert_eq!(value.to_hex(), hex);
+}
+
+#[rstest]
+#[case("", 0)]
+#[case("8", 0)]
+#[case("820", 1)]
+#[case("82z102", 1)]
+#[case("82010z", 2)]
+fn invalid(#[case] hex: &str, #[case] offset: u64) {
+    match from_hex_str::<Vec<u8>>(hex) {
+        Err(Error::Syntax(x)) => assert_eq!(x, offset),
+        Err(Error::Io(..)) if hex.is_empty() => (),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn trailing() {
+    let result = from_hex_str::<u8>("0102");
+    assert!(matches!(result, Err(Error::Semantic(Some(1), _))));
+}
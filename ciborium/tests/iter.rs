@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::ser::{into_writer_map, into_writer_seq, serialize_sorted_map};
+use ciborium::value::KeyOrder;
+use ciborium::{cbor, Value};
+use serde::{Serialize, Serializer};
+
+#[test]
+fn seq() {
+    let mut bytes = Vec::new();
+    into_writer_seq(["a", "b"], &mut bytes).unwrap();
+    assert_eq!(hex::encode(&bytes), "9f61616162ff");
+
+    let mut empty = Vec::new();
+    into_writer_seq(core::iter::empty::<u8>(), &mut empty).unwrap();
+    assert_eq!(hex::encode(&empty), "9fff");
+
+    let mut records = Vec::new();
+    into_writer_seq((0..1000u32).map(|i| (i, i % 2 == 0)), &mut records).unwrap();
+    let value: Vec<(u32, bool)> = ciborium::from_reader(&records[..]).unwrap();
+    assert_eq!(value.len(), 1000);
+    assert_eq!(value[999], (999, false));
+}
+
+#[test]
+fn map() {
+    let mut bytes = Vec::new();
+    into_writer_map([("b", vec![1]), ("a", vec![])], &mut bytes).unwrap();
+    assert_eq!(hex::encode(&bytes), "bf61628101616180ff");
+
+    let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(value, cbor!({ "b" => [1], "a" => [] }).unwrap());
+}
+
+#[test]
+fn full() {
+    let mut buffer = [0u8; 3];
+    let result = into_writer_seq([1, 2, 3], &mut buffer[..]);
+    assert!(matches!(result, Err(ciborium::ser::Error::Io(..))));
+}
+
+#[rstest::rstest]
+#[case(KeyOrder::LengthFirst, "a4 0a00 2001 186402 616103")]
+#[case(KeyOrder::Lexical, "a4 0a00 186402 2001 616103")]
+fn sorted_map(#[case] order: KeyOrder, #[case] expected: &str) {
+    struct Sorted(Vec<(Value, u8)>, KeyOrder);
+
+    impl Serialize for Sorted {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let entries = self.0.iter().map(|(k, v)| (k, v));
+            serialize_sorted_map(entries, self.1, serializer)
+        }
+    }
+
+    let entries = vec![
+        ("a".into(), 3),
+        (100.into(), 2),
+        ((-1).into(), 1),
+        (10.into(), 0),
+    ];
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&Sorted(entries.clone(), order), &mut bytes).unwrap();
+    assert_eq!(hex::encode(&bytes), expected.replace(' ', ""));
+
+    let mut duplicates = entries;
+    duplicates.push((10.into(), 4));
+    let result = ciborium::into_writer(&Sorted(duplicates, order), &mut Vec::new());
+    assert!(matches!(result, Err(ciborium::ser::Error::Value(..))));
+}
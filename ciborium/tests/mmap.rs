@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "mmap")]
+
+use std::path::PathBuf;
+
+use ciborium::de::Error;
+use ciborium::mmap::Mapped;
+use ciborium::{cbor, Value};
+use serde::{Deserialize, Serialize};
+
+/// A file in the temporary directory which is removed on drop
+struct Temp(PathBuf);
+
+impl Temp {
+    fn new(name: &str, bytes: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("ciborium-{}-{}", std::process::id(), name));
+        std::fs::write(&path, bytes).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for Temp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn decode() {
+    let value = cbor!({ "id" => 7, "tags" => ["a", "b"] }).unwrap();
+    let temp = Temp::new("decode", &ciborium::into_vec(&value).unwrap());
+
+    let mapped = unsafe { Mapped::open(&temp.0) }.unwrap();
+    assert!(mapped.is_mapped());
+    assert_eq!(mapped.value().unwrap(), value);
+    assert_eq!(mapped.extract::<u8>("/id").unwrap(), Some(7));
+    assert_eq!(
+        mapped.extract::<String>("/tags/1").unwrap().as_deref(),
+        Some("b")
+    );
+
+    let read = Mapped::read(&temp.0).unwrap();
+    assert!(!read.is_mapped());
+    assert_eq!(&read[..], &mapped[..]);
+    assert_eq!(read.value().unwrap(), value);
+}
+
+#[test]
+fn empty() {
+    let temp = Temp::new("empty", &[]);
+
+    let mapped = unsafe { Mapped::open(&temp.0) }.unwrap();
+    assert!(!mapped.is_mapped());
+    assert!(mapped.is_empty());
+    assert_eq!(mapped.iter::<Value>().count(), 0);
+    mapped.value().unwrap_err();
+}
+
+#[test]
+fn sequence() {
+    // 1, "two", [3], followed by a truncated item
+    let temp = Temp::new("sequence", &hex::decode("016374776f810318").unwrap());
+    let mapped = unsafe { Mapped::open(&temp.0) }.unwrap();
+
+    let mut items = mapped.iter::<Value>();
+    assert_eq!(items.next().unwrap().unwrap(), Value::from(1));
+    assert_eq!(items.next().unwrap().unwrap(), Value::from("two"));
+    assert_eq!(items.next().unwrap().unwrap(), cbor!([3]).unwrap());
+    items.next().unwrap().unwrap_err();
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn recover() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        id: u32,
+        name: String,
+    }
+
+    let first = ciborium::into_vec(&Record {
+        id: 1,
+        name: "a".into(),
+    })
+    .unwrap();
+    let second = ciborium::into_vec(&Record {
+        id: 2,
+        name: "b".into(),
+    })
+    .unwrap();
+
+    // A record, two bytes of damage, a record and a truncated record
+    let bytes = [&first[..], &[0x1c, 0xff], &second, &second[..4]].concat();
+    let temp = Temp::new("recover", &bytes);
+    let mapped = Mapped::read(&temp.0).unwrap();
+
+    let damaged = first.len() as u64;
+    let truncated = bytes.len() as u64 - 4;
+
+    let mut items = mapped.recover::<Record>().probe(64);
+    assert_eq!(items.next().unwrap().unwrap().id, 1);
+
+    let skipped = items.next().unwrap().unwrap_err();
+    assert_eq!(skipped.range, damaged..damaged + 2);
+    assert!(matches!(skipped.error, Error::Syntax(x) if x == damaged));
+
+    assert_eq!(items.next().unwrap().unwrap().id, 2);
+    assert_eq!(
+        items.next().unwrap().unwrap_err().range,
+        truncated..bytes.len() as u64
+    );
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn recover_tagged() {
+    // 55799(1), then 55799({1: 55799(2) ...}) cut short, then 55799(3)
+    let temp = Temp::new(
+        "recover_tagged",
+        &hex::decode("d9d9f701d9d9f7a201d9d9f702d9d9f703").unwrap(),
+    );
+    let mapped = Mapped::read(&temp.0).unwrap();
+
+    let tagged = |x: u8| Value::Tag(55799, Box::new(x.into()));
+
+    // Any byte deserializes as a value, so only the tags are trusted.
+    let items: Vec<_> = mapped.recover::<Value>().collect();
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[0].as_ref().unwrap(), &tagged(1));
+    assert_eq!(items[1].as_ref().unwrap_err().range, 4..9);
+    assert_eq!(items[2].as_ref().unwrap(), &tagged(2));
+    assert_eq!(items[3].as_ref().unwrap(), &tagged(3));
+}
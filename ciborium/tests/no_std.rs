@@ -1,13 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-#![cfg(all(feature = "serde", not(feature = "std")))]
+#![cfg(not(feature = "std"))]
 #![no_std]
 
 extern crate alloc;
 
 use alloc::vec::Vec;
 
-use ciborium::{de::from_reader, ser::into_writer};
+use ciborium::{de::from_reader, ser::into_writer, ser::to_slice};
 
 #[test]
 fn decode() {
@@ -33,7 +33,25 @@ fn encode_vec() {
     assert_eq!(buffer[0], 3);
 }
 
+#[test]
+fn encode_prefix() {
+    let mut buffer = [0xffu8; 8];
+    let bytes = to_slice(&(1u8, 500u16), &mut buffer).unwrap();
+    assert_eq!(bytes, [0x82, 0x01, 0x19, 0x01, 0xf4]);
+    assert_eq!(buffer[5..], [0xff; 3]);
+}
+
 #[test]
 fn oos() {
     into_writer(&3u8, &mut [][..]).unwrap_err();
+    to_slice(&500u16, &mut [0u8; 2]).unwrap_err();
+}
+
+#[cfg(feature = "core-error")]
+#[test]
+fn core_error() {
+    fn is_error<T, E: core::error::Error>(_: &Result<T, E>) {}
+
+    is_error(&from_reader::<u8, &[u8]>(&[]));
+    is_error(&into_writer(&3u8, &mut [][..]));
 }
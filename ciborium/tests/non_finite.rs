@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::{de, ser, Value};
+use rstest::rstest;
+
+#[rstest]
+#[case(f64::NAN)]
+#[case(f64::INFINITY)]
+#[case(f64::NEG_INFINITY)]
+fn rejected(#[case] float: f64) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&float, &mut bytes).unwrap();
+
+    let options = ser::Options::new().non_finite_floats(false);
+    let result = ser::into_writer_with_options(&float, &mut Vec::new(), options);
+    assert!(matches!(result, Err(ser::Error::Value(..))));
+
+    let options = de::Options::new().non_finite_floats(false);
+    let result = de::from_reader_with_options::<f64, _>(&bytes[..], options);
+    assert!(matches!(result, Err(de::Error::Semantic(Some(0), ..))));
+
+    // Non-finite floats are also rejected when nested inside a `Value`.
+    let array = [&[0x81][..], &bytes].concat();
+    let result = de::from_reader_with_options::<Value, _>(&array[..], options);
+    assert!(matches!(result, Err(de::Error::Semantic(Some(1), ..))));
+}
+
+#[rstest]
+#[case(0.0)]
+#[case(-1.5)]
+#[case(f64::MAX)]
+#[case(f64::MIN_POSITIVE)]
+fn accepted(#[case] float: f64) {
+    let mut bytes = Vec::new();
+    let options = ser::Options::new().non_finite_floats(false);
+    ser::into_writer_with_options(&float, &mut bytes, options).unwrap();
+
+    let options = de::Options::new().non_finite_floats(false);
+    let decoded: f64 = de::from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(decoded, float);
+}
@@ -0,0 +1,423 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{
+    from_reader, from_reader_with_buffer_and_options, from_reader_with_options, Error, Options,
+};
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+/// A reader which does not expose its buffer
+struct Unbuffered<'a>(&'a [u8]);
+
+impl ciborium_io::Read for Unbuffered<'_> {
+    type Error = ();
+
+    fn read_exact(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        if data.len() > self.0.len() {
+            return Err(());
+        }
+
+        let (prefix, suffix) = self.0.split_at(data.len());
+        data.copy_from_slice(prefix);
+        self.0 = suffix;
+        Ok(())
+    }
+}
+
+/// Records whether a string was visited borrowed or owned
+#[derive(Debug, PartialEq)]
+enum Visited {
+    Str,
+    String,
+}
+
+impl<'de> Deserialize<'de> for Visited {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+
+        impl<'de> Visitor<'de> for V {
+            type Value = Visited;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, _: &str) -> Result<Visited, E> {
+                Ok(Visited::Str)
+            }
+
+            fn visit_string<E: serde::de::Error>(self, _: String) -> Result<Visited, E> {
+                Ok(Visited::String)
+            }
+        }
+
+        deserializer.deserialize_any(V)
+    }
+}
+
+/// Records the size hint reported for a collection
+#[derive(Debug, PartialEq)]
+struct Hint(Option<usize>);
+
+impl<'de> Deserialize<'de> for Hint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+
+        impl<'de> Visitor<'de> for V {
+            type Value = Hint;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a collection")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, acc: A) -> Result<Hint, A::Error> {
+                Ok(Hint(acc.size_hint()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, acc: A) -> Result<Hint, A::Error> {
+                Ok(Hint(acc.size_hint()))
+            }
+        }
+
+        deserializer.deserialize_any(V)
+    }
+}
+
+#[test]
+fn size_hint_clamped() {
+    // An array and a map claiming 2^60 items with no content.
+    let array = hex::decode("9b1000000000000000").unwrap();
+    let map = hex::decode("bb1000000000000000").unwrap();
+
+    assert_eq!(
+        from_reader::<Hint, _>(&array[..]).unwrap(),
+        Hint(Some(1024))
+    );
+    assert_eq!(from_reader::<Hint, _>(&map[..]).unwrap(), Hint(Some(1024)));
+
+    let options = Options::new().max_size_hint(8);
+    let hint: Hint = from_reader_with_options(&array[..], options).unwrap();
+    assert_eq!(hint, Hint(Some(8)));
+}
+
+#[test]
+fn size_hint_exact() {
+    let bytes = hex::decode("83010203").unwrap();
+    assert_eq!(from_reader::<Hint, _>(&bytes[..]).unwrap(), Hint(Some(3)));
+
+    let bytes = hex::decode("9f010203ff").unwrap();
+    assert_eq!(from_reader::<Hint, _>(&bytes[..]).unwrap(), Hint(None));
+}
+
+#[test]
+fn huge_header_does_not_preallocate() {
+    let bytes = hex::decode("9b1000000000000000").unwrap();
+    match from_reader::<Vec<u64>, _>(&bytes[..]).unwrap_err() {
+        Error::Io(..) => (),
+        e => panic!("incorrect error: {:?}", e),
+    }
+}
+
+#[test]
+fn recursion_limit() {
+    let bytes = [0x81, 0x81, 0x81, 0x00];
+
+    let options = Options::new().recursion_limit(2);
+    match from_reader_with_options::<IgnoredAny, _>(&bytes[..], options).unwrap_err() {
+        Error::RecursionLimitExceeded => (),
+        e => panic!("incorrect error: {:?}", e),
+    }
+
+    let options = Options::new().recursion_limit(3);
+    from_reader_with_options::<IgnoredAny, _>(&bytes[..], options).unwrap();
+}
+
+#[test]
+fn scratch_size() {
+    let mut bytes = hex::decode("7820").unwrap();
+    bytes.extend_from_slice(&[b'x'; 32]);
+
+    let visited: Visited = from_reader(Unbuffered(&bytes)).unwrap();
+    assert_eq!(visited, Visited::Str);
+
+    let options = Options::new().scratch_size(16);
+    let visited: Visited = from_reader_with_options(Unbuffered(&bytes), options).unwrap();
+    assert_eq!(visited, Visited::String);
+
+    let options = Options::new().scratch_size(32);
+    let visited: Visited = from_reader_with_options(Unbuffered(&bytes), options).unwrap();
+    assert_eq!(visited, Visited::Str);
+}
+
+#[test]
+fn caller_buffer() {
+    let mut bytes = hex::decode("7820").unwrap();
+    bytes.extend_from_slice(&[b'x'; 32]);
+
+    let mut scratch = [0u8; 8];
+    let options = Options::new().scratch_size(4096);
+    let visited: Visited =
+        from_reader_with_buffer_and_options(Unbuffered(&bytes), &mut scratch, options).unwrap();
+    assert_eq!(visited, Visited::String);
+}
+
+#[test]
+fn integer_only() {
+    let options = Options::new().floats(false);
+
+    // [1, 1.5] with the float as half, single and double precision.
+    for input in ["8201f93e00", "8201fa3fc00000", "8201fb3ff8000000000000"] {
+        let bytes = hex::decode(input).unwrap();
+
+        let result = from_reader_with_options::<ciborium::Value, _>(&bytes[..], options);
+        assert!(matches!(result, Err(Error::Semantic(..))));
+
+        let result = from_reader_with_options::<(u8, f64), _>(&bytes[..], options);
+        assert!(matches!(result, Err(Error::Semantic(Some(2), ..))));
+
+        let (_, float): (u8, f64) = from_reader(&bytes[..]).unwrap();
+        assert_eq!(float, 1.5);
+    }
+
+    let bytes = hex::decode("820102").unwrap();
+    let value: (u8, u8) = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, (1, 2));
+}
+
+#[test]
+fn tag_limit() {
+    // Three tags (each 0xc1) on the integer 0, within an array.
+    let bytes = hex::decode("81c1c1c100").unwrap();
+
+    let options = Options::new().tag_limit(2);
+    let result = from_reader_with_options::<Vec<u8>, _>(&bytes[..], options);
+    assert!(matches!(result, Err(Error::Semantic(Some(3), ..))));
+
+    let result = from_reader_with_options::<IgnoredAny, _>(&bytes[..], options);
+    assert!(matches!(result, Err(Error::Semantic(Some(3), ..))));
+
+    let options = Options::new().tag_limit(3);
+    let value: Vec<u8> = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, [0]);
+    from_reader_with_options::<IgnoredAny, _>(&bytes[..], options).unwrap();
+
+    // The limit applies per item, not to the whole input.
+    let bytes = hex::decode("82c1c100c1c100").unwrap();
+    let options = Options::new().tag_limit(2);
+    let value: Vec<u8> = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, [0, 0]);
+}
+
+#[test]
+fn strict_tags() {
+    use ciborium::{tag::Captured, Value};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Untagged {
+        Int(u8),
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Struct {
+        a: u8,
+    }
+
+    let options = Options::new().skip_tags(false);
+
+    // 1(0)
+    let bytes = hex::decode("c100").unwrap();
+    match from_reader_with_options::<u8, _>(&bytes[..], options).unwrap_err() {
+        Error::Semantic(Some(0), msg) => assert_eq!(msg, "unexpected tag 1"),
+        e => panic!("incorrect error: {:?}", e),
+    }
+    let result = from_reader_with_options::<Untagged, _>(&bytes[..], options.untagged_enums(true));
+    assert!(matches!(result, Err(Error::Semantic(Some(0), ..))));
+    let untagged = Options::new().untagged_enums(true);
+    let value: Untagged = from_reader_with_options(&bytes[..], untagged).unwrap();
+    assert_eq!(value, Untagged::Int(0));
+
+    // Tags which are captured are still accepted.
+    let value: Value = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, Value::Tag(1, Box::new(0.into())));
+    let captured: Captured<u8> = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(captured, Captured(Some(1), 0));
+
+    // [32("a")]
+    let bytes = hex::decode("81d8206161").unwrap();
+    let result = from_reader_with_options::<Vec<String>, _>(&bytes[..], options);
+    assert!(matches!(result, Err(Error::Semantic(Some(1), ..))));
+
+    // Bignums are accepted as integers: 2(h'0100')
+    let bytes = hex::decode("c2420100").unwrap();
+    let value: u64 = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, 256);
+
+    // Skipped fields are not inspected: {"a": 1, "b": 1(0)}
+    let bytes = hex::decode("a26161016162c100").unwrap();
+    let value: Struct = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value.a, 1);
+}
+
+#[test]
+fn unknown_simple() {
+    use ciborium::{cbor, Value};
+
+    // [simple(16), simple(255), false]
+    let bytes = hex::decode("83f0f8fff4").unwrap();
+
+    match from_reader::<Value, _>(&bytes[..]).unwrap_err() {
+        Error::Semantic(_, msg) => assert!(msg.contains("expected known simple value")),
+        e => panic!("incorrect error: {:?}", e),
+    }
+
+    let options = Options::new().unknown_simple_as_null(true);
+    let value: Value = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, cbor!([null, null, false]).unwrap());
+
+    let value: (Option<u8>, (), bool) = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, (None, (), false));
+}
+
+#[test]
+fn lossy_utf8() {
+    use ciborium::{de::from_slice_with_options, Value};
+
+    // "a\xffb", then the same split into two segments
+    for hex in ["6361ff62", "7f6261ff6162ff"] {
+        let bytes = hex::decode(hex).unwrap();
+
+        let result = from_reader::<String, _>(&bytes[..]);
+        assert!(matches!(result, Err(Error::Syntax(..))));
+
+        let options = Options::new().lossy_utf8(true);
+        let text: String = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(text, "a\u{fffd}b");
+        let text: String = from_slice_with_options(&bytes, options).unwrap();
+        assert_eq!(text, "a\u{fffd}b");
+        let text: Value = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(text, Value::Text("a\u{fffd}b".into()));
+
+        let buffered = Unbuffered(&bytes);
+        let text: String = from_reader_with_options(buffered, options).unwrap();
+        assert_eq!(text, "a\u{fffd}b");
+
+        let options = options.allocate(false);
+        let result = from_slice_with_options::<&str>(&bytes, options);
+        assert!(matches!(result, Err(Error::Semantic(..))));
+    }
+
+    // Valid text is still borrowed.
+    let options = Options::new().lossy_utf8(true);
+    let text: &str = from_slice_with_options(&[0x62, b'h', b'i'], options).unwrap();
+    assert_eq!(text, "hi");
+}
+
+#[test]
+fn coerce_strings() {
+    use ciborium::{de::from_slice_with_options, Value};
+    use serde_bytes::ByteBuf;
+
+    // "hi", h'6869', and both split into segments
+    let text = ["626869", "7f61686169ff"].map(|x| hex::decode(x).unwrap());
+    let bytes = ["426869", "5f41684169ff"].map(|x| hex::decode(x).unwrap());
+
+    for bytes in &text {
+        let result = from_reader::<ByteBuf, _>(&bytes[..]);
+        assert!(matches!(result, Err(Error::Semantic(..))));
+
+        let options = Options::new().coerce_strings(true);
+        let value: ByteBuf = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, b"hi"[..]);
+        let value: Vec<u8> = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, b"hi");
+        let value: Value = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, Value::Text("hi".into()));
+    }
+
+    for bytes in &bytes {
+        let result = from_reader::<String, _>(&bytes[..]);
+        assert!(matches!(result, Err(Error::Semantic(..))));
+
+        let options = Options::new().coerce_strings(true);
+        let value: String = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, "hi");
+        let value: Value = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, Value::Bytes(b"hi".to_vec()));
+    }
+
+    let options = Options::new().coerce_strings(true);
+    let value: &str = from_slice_with_options(&bytes[0], options).unwrap();
+    assert_eq!(value, "hi");
+    let value: &[u8] = from_slice_with_options(&text[0], options).unwrap();
+    assert_eq!(value, b"hi");
+
+    // Byte strings must still hold valid UTF-8 to be read as text.
+    let invalid = hex::decode("42c328").unwrap();
+    let result = from_reader_with_options::<String, _>(&invalid[..], options);
+    assert!(matches!(result, Err(Error::Syntax(0))));
+}
+
+#[test]
+fn coerce_numbers() {
+    use ciborium::Value;
+
+    let options = Options::new().coerce_numbers(true);
+    let decode = |x: &str| hex::decode(x).unwrap();
+
+    // Integers where floats are expected
+    for (bytes, float) in [
+        ("01", 1.0),
+        ("20", -1.0),
+        ("1b0020000000000000", 9007199254740992.0),
+        ("3bffffffffffffffff", -18446744073709551616.0),
+    ] {
+        let bytes = decode(bytes);
+        assert!(from_reader::<f64, _>(&bytes[..]).is_err());
+        let value: f64 = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, float);
+    }
+
+    let value: f32 = from_reader_with_options(&[0x18, 0x64][..], options).unwrap();
+    assert_eq!(value, 100.0);
+
+    for bytes in ["1b0020000000000001", "1bffffffffffffffff"] {
+        let bytes = decode(bytes);
+        let result = from_reader_with_options::<f64, _>(&bytes[..], options);
+        assert!(matches!(result, Err(Error::Semantic(Some(0), ..))));
+    }
+
+    // Integral floats where integers are expected
+    for (bytes, integer) in [("f93c00", 1), ("f9c000", -2), ("f98000", 0)] {
+        let bytes = decode(bytes);
+        assert!(from_reader::<i32, _>(&bytes[..]).is_err());
+        let value: i32 = from_reader_with_options(&bytes[..], options).unwrap();
+        assert_eq!(value, integer);
+    }
+
+    let bytes = decode("fb4415af1d78b58c40");
+    let value: u128 = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, 100000000000000000000);
+
+    let bytes = decode("f93e00"); // 1.5
+    let result = from_reader_with_options::<i32, _>(&bytes[..], options);
+    assert!(matches!(result, Err(Error::Semantic(Some(0), ..))));
+
+    let bytes = decode("f95c00"); // 256.0
+    assert!(from_reader_with_options::<u8, _>(&bytes[..], options).is_err());
+
+    let bytes = decode("f9bc00"); // -1.0
+    assert!(from_reader_with_options::<u64, _>(&bytes[..], options).is_err());
+
+    let bytes = decode("f97c00"); // Infinity
+    assert!(from_reader_with_options::<i64, _>(&bytes[..], options).is_err());
+
+    // Floats stay rejected when they are not accepted at all.
+    let bytes = decode("f93c00");
+    let strict = options.floats(false);
+    assert!(from_reader_with_options::<i32, _>(&bytes[..], strict).is_err());
+
+    // Dynamic values keep the type on the wire.
+    let value: Value = from_reader_with_options(&bytes[..], options).unwrap();
+    assert_eq!(value, Value::Float(1.0));
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(all(feature = "yoke", feature = "std"))]
+
+use std::borrow::Cow;
+
+use ciborium::de::Error;
+use ciborium::owned::Owned;
+use ciborium::{cbor, Value};
+use serde::Deserialize;
+use yoke::Yokeable;
+
+#[derive(Debug, PartialEq, Deserialize, Yokeable)]
+struct Record<'a> {
+    id: u32,
+    name: &'a str,
+    #[serde(borrow)]
+    note: Cow<'a, str>,
+}
+
+fn encode(value: &Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn borrows() {
+    let bytes = encode(&cbor!({ "id" => 7, "name" => "seven", "note" => "prime" }).unwrap());
+    let owned = Owned::<Record<'static>>::from_bytes(bytes.clone()).unwrap();
+
+    let record = owned.get();
+    assert_eq!(record.id, 7);
+    assert_eq!(record.name, "seven");
+    assert!(matches!(record.note, Cow::Borrowed("prime")));
+
+    // The strings point into the owned input.
+    let range = owned.bytes().as_ptr_range();
+    assert!(range.contains(&record.name.as_ptr()));
+    assert_eq!(owned.bytes(), bytes);
+}
+
+#[test]
+fn cache() {
+    let cache: Vec<Owned<Record<'static>>> = (0..3)
+        .map(|id| {
+            let value = cbor!({ "id" => id, "name" => "x", "note" => "y" }).unwrap();
+            Owned::from_reader(&encode(&value)[..]).unwrap()
+        })
+        .collect();
+
+    let ids: Vec<u32> = std::thread::spawn(move || cache.iter().map(|x| x.get().id).collect())
+        .join()
+        .unwrap();
+    assert_eq!(ids, [0, 1, 2]);
+}
+
+#[test]
+fn invalid() {
+    let bytes = encode(&cbor!({ "id" => "seven", "name" => "x", "note" => "y" }).unwrap());
+    let result = Owned::<Record<'static>>::from_bytes(bytes);
+    assert!(matches!(result, Err(Error::Semantic(..))));
+
+    let result = Owned::<Record<'static>>::from_bytes(vec![0xa1]);
+    assert!(matches!(result, Err(Error::Io(..))));
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "rayon")]
+
+use ciborium::de::Error;
+use ciborium::{cbor, parallel, Value};
+
+#[test]
+fn decode() {
+    let mut bytes = Vec::new();
+    let mut expected = Vec::new();
+    for i in 0..1000 {
+        let value =
+            cbor!({ "id" => i, "name" => format!("item {}", i), "tags" => [i, "x"] }).unwrap();
+        ciborium::into_writer(&value, &mut bytes).unwrap();
+        expected.push(value);
+    }
+
+    assert_eq!(parallel::split(&bytes).unwrap().len(), 1000);
+
+    let values: Vec<Value> = parallel::from_slice(&bytes).unwrap();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn empty() {
+    assert!(parallel::split(&[]).unwrap().is_empty());
+    assert!(parallel::from_slice::<Value>(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn truncated() {
+    // 1, then an array missing its second element
+    let bytes = hex::decode("018201").unwrap();
+    assert!(matches!(parallel::split(&bytes), Err(Error::Io(..))));
+}
+
+#[test]
+fn error_offset() {
+    // 1, "a", then an invalid UTF-8 string at offset 3
+    let bytes = hex::decode("0161616280ff").unwrap();
+
+    match parallel::from_slice::<Value>(&bytes) {
+        Err(Error::Syntax(offset)) => assert_eq!(offset, 3),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn first_error() {
+    // Invalid UTF-8 strings on either side of the middle of the sequence.
+    // The items before the first are slow to decode, so the second one is
+    // usually reached first.
+    let mut bytes = Vec::new();
+    let mut bad = Vec::new();
+    for i in 0..1000 {
+        match i {
+            0..=498 => ciborium::into_writer(&"x".repeat(4096), &mut bytes).unwrap(),
+            499 | 500 => {
+                bad.push(bytes.len() as u64);
+                bytes.extend_from_slice(&[0x62, 0x80, 0xff]);
+            }
+            _ => bytes.push(0x01),
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+
+    for _ in 0..16 {
+        match pool.install(|| parallel::from_slice::<Value>(&bytes)) {
+            Err(Error::Syntax(offset)) => assert_eq!(offset, bad[0]),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
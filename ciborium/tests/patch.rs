@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::cbor;
+use ciborium::patch::{apply, diff, Operation, Patch};
+use ciborium::value::{Path, Value};
+use rstest::rstest;
+
+fn path(segments: &[Value]) -> Path {
+    Path::from(segments.to_vec())
+}
+
+fn text(s: &str) -> Value {
+    Value::Text(s.into())
+}
+
+#[rstest]
+#[case(cbor!(1).unwrap(), cbor!(1).unwrap(), 0)]
+#[case(cbor!(1).unwrap(), cbor!("x").unwrap(), 1)]
+#[case(cbor!([1, 2, 3]).unwrap(), cbor!([1, 5, 3]).unwrap(), 1)]
+#[case(cbor!([1, 2, 3]).unwrap(), cbor!([1]).unwrap(), 2)]
+#[case(cbor!([1]).unwrap(), cbor!([1, [2], { 3 => 4 }]).unwrap(), 2)]
+#[case(cbor!({ "a" => 1, "b" => 2 }).unwrap(), cbor!({ "a" => 1, "c" => 3 }).unwrap(), 2)]
+#[case(cbor!({ "a" => { "b" => [1, 2] } }).unwrap(), cbor!({ "a" => { "b" => [1, 3] } }).unwrap(), 1)]
+#[case(cbor!({ 1 => 2, 3 => 4 }).unwrap(), cbor!({ 3 => 4, 1 => 2 }).unwrap(), 1)]
+#[case(cbor!({ 1 => 2, 3 => 4 }).unwrap(), cbor!({ 3 => 5 }).unwrap(), 2)]
+#[case(
+    Value::Tag(1, Box::new(cbor!([1, 2]).unwrap())),
+    Value::Tag(1, Box::new(cbor!([1, 3]).unwrap())),
+    1
+)]
+#[case(Value::Tag(1, Box::new(cbor!(1).unwrap())), Value::Tag(4, Box::new(cbor!(1).unwrap())), 1)]
+#[case(Value::Float(f64::NAN), Value::Float(f64::NAN), 0)]
+fn roundtrip(#[case] from: Value, #[case] to: Value, #[case] operations: usize) {
+    let patch = diff(&from, &to);
+    assert_eq!(patch.operations().len(), operations, "{:?}", patch);
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&patch, &mut bytes).unwrap();
+    let patch: Patch = ciborium::from_reader(&bytes[..]).unwrap();
+
+    let mut value = from.clone();
+    apply(&mut value, &patch).unwrap();
+    assert!(ciborium::value::diff(&value, &to).is_none());
+}
+
+#[test]
+fn operations() {
+    let from = cbor!({ "a" => [1, 2], "b" => true }).unwrap();
+    let to = cbor!({ "a" => [1], "c" => null }).unwrap();
+
+    let patch = diff(&from, &to);
+    assert_eq!(
+        patch.operations(),
+        [
+            Operation::Remove {
+                path: path(&[text("a"), 1.into()])
+            },
+            Operation::Remove {
+                path: path(&[text("b")])
+            },
+            Operation::Add {
+                path: path(&[text("c")]),
+                value: Value::Null,
+            },
+        ]
+    );
+}
+
+#[rstest]
+#[case::insert(Operation::Add { path: path(&[text("a"), 0.into()]), value: 0.into() }, Ok(cbor!({ "a" => [0, 1, 2] }).unwrap()))]
+#[case::append(Operation::Add { path: path(&[text("a"), 2.into()]), value: 3.into() }, Ok(cbor!({ "a" => [1, 2, 3] }).unwrap()))]
+#[case::past_end(Operation::Add { path: path(&[text("a"), 3.into()]), value: 3.into() }, Err("no item at $[\"a\"][3]"))]
+#[case::existing_key(Operation::Add { path: path(&[text("a")]), value: 0.into() }, Ok(cbor!({ "a" => 0 }).unwrap()))]
+#[case::root(Operation::Add { path: Path::new(), value: 0.into() }, Ok(cbor!(0).unwrap()))]
+#[case::remove_missing(Operation::Remove { path: path(&[text("b")]) }, Err("no item at $[\"b\"]"))]
+#[case::remove_root(Operation::Remove { path: Path::new() }, Err("cannot remove the root"))]
+#[case::replace_missing(Operation::Replace { path: path(&[text("b")]), value: 0.into() }, Err("no item at $[\"b\"]"))]
+#[case::not_container(Operation::Add { path: path(&[text("a"), 0.into(), 0.into()]), value: 0.into() }, Err("no item at $[\"a\"][0][0]"))]
+fn apply_operation(#[case] operation: Operation, #[case] result: Result<Value, &str>) {
+    let mut value = cbor!({ "a" => [1, 2] }).unwrap();
+
+    match (apply(&mut value, &vec![operation].into()), result) {
+        (Ok(()), Ok(expected)) => assert_eq!(value, expected),
+        (Err(ciborium::value::Error::Custom(msg)), Err(expected)) => assert_eq!(msg, expected),
+        (result, expected) => panic!("{:?} != {:?}", result, expected),
+    }
+}
+
+#[test]
+fn pointers() {
+    let from = cbor!({ "a/b" => [1, 2], 3 => { "c" => true } }).unwrap();
+    let to = cbor!({ "a/b" => [1, 5], 3 => { "c" => false } }).unwrap();
+
+    let pointers: Vec<_> = diff(&from, &to)
+        .operations()
+        .iter()
+        .map(|op| op.path().to_pointer().unwrap())
+        .collect();
+    assert_eq!(pointers, ["/a~1b/1", "/3/c"]);
+
+    for pointer in &pointers {
+        let path = from.pointer_path(pointer).unwrap();
+        assert_eq!(path.to_pointer().as_ref(), Some(pointer));
+    }
+
+    assert_eq!(from.pointer_path("/3/d"), None);
+    assert_eq!(path(&[cbor!([1]).unwrap()]).to_pointer(), None);
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::cbor;
+use ciborium::value::{ByteFormat, Value};
+use rstest::rstest;
+
+#[rstest]
+#[case(cbor!(1).unwrap(), "1")]
+#[case(cbor!([]).unwrap(), "[]")]
+#[case(cbor!({}).unwrap(), "{}")]
+#[case(cbor!([1, [2]]).unwrap(), "[\n  1,\n  [\n    2\n  ]\n]")]
+#[case(cbor!({ [1] => "x" }).unwrap(), "{\n  [\n    1\n  ]: \"x\"\n}")]
+#[case(Value::Tag(24, Box::new(cbor!([1]).unwrap())), "24([\n  1\n])")]
+fn tree(#[case] value: Value, #[case] text: &str) {
+    assert_eq!(value.pretty().to_string(), text);
+    assert_eq!(format!("{:?}", value.pretty()), text);
+    assert_eq!(format!("{:#}", value.diagnostic()), text);
+}
+
+#[rstest]
+#[case(ByteFormat::Hex, "h'6869ff'")]
+#[case(ByteFormat::Text, r"'hi\xff'")]
+#[case(ByteFormat::Length, "<3 bytes>")]
+fn bytes(#[case] format: ByteFormat, #[case] text: &str) {
+    let value = Value::Bytes(b"hi\xff".to_vec());
+    assert_eq!(value.pretty().bytes(format).to_string(), text);
+}
+
+#[rstest]
+#[case(0, "[<1 item>]")]
+#[case(1, "[\n  {<1 pair>}\n]")]
+#[case(2, "[\n  {\n    \"a\": [<2 items>]\n  }\n]")]
+fn depth(#[case] limit: usize, #[case] text: &str) {
+    let value = cbor!([{ "a" => [1, 2] }]).unwrap();
+    assert_eq!(value.pretty().depth(limit).to_string(), text);
+}
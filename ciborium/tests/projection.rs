@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::cbor;
+use ciborium::de::{from_reader, Deserializer, Error, Options};
+use ciborium::value::Value;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Header {
+    alg: i32,
+    kid: String,
+}
+
+#[test]
+fn projection() {
+    let record = hex::decode(concat!(
+        "a4",
+        "63616c6726",             // "alg": -7
+        "677061796c6f6164",       // "payload":
+        "5f440102030443050607ff", //     (_ h'01020304', h'050607')
+        "656578747261",           // "extra":
+        "bf6161c1f800606261ffff", //     {_ "a": 1(simple(0)), "": "a\xff"}
+        "636b6964",               // "kid":
+        "636b2d31",               //     "k-1"
+    ))
+    .unwrap();
+
+    // The skipped items contain values which could not be visited at all.
+    from_reader::<Value, _>(&record[..]).unwrap_err();
+
+    let header: Header = from_reader(&record[..]).unwrap();
+    assert_eq!(
+        header,
+        Header {
+            alg: -7,
+            kid: "k-1".into()
+        }
+    );
+}
+
+#[test]
+fn skip_item() {
+    let bytes = hex::decode("9f8201a1020380ff7f61616162ff05").unwrap();
+
+    let mut scratch = [0u8; 0];
+    let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    deserializer.skip_item().unwrap();
+    deserializer.skip_item().unwrap();
+    assert_eq!(u8::deserialize(&mut deserializer).unwrap(), 5);
+}
+
+#[test]
+fn skip_errors() {
+    for (bytes, offset) in [("ff", 0), ("5f6161ff", 1), ("82015f4101", 5)] {
+        let bytes = hex::decode(bytes).unwrap();
+
+        let mut scratch = [0u8; 16];
+        let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+        match deserializer.skip_item().unwrap_err() {
+            Error::Syntax(x) if x == offset => (),
+            Error::Io(..) if offset == 5 => (),
+            e => panic!("incorrect error: {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn extract() {
+    let long = "k".repeat(100);
+    let value = cbor!({
+        "header" => { "alg" => -7, "a/b~c" => "escaped" },
+        "payload" => [10, 20, { 1 => "one", -2 => "minus two" }],
+        long.as_str() => "long",
+    })
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let get = |pointer: &str| ciborium::extract::<Value, _>(&bytes[..], pointer).unwrap();
+    assert_eq!(get(""), Some(value.clone()));
+    assert_eq!(get("/header/alg"), Some(Value::from(-7)));
+    assert_eq!(get("/header/a~1b~0c"), Some(Value::from("escaped")));
+    assert_eq!(get("/payload/1"), Some(Value::from(20)));
+    assert_eq!(get("/payload/2/1"), Some(Value::from("one")));
+    assert_eq!(get("/payload/2/-2"), Some(Value::from("minus two")));
+    assert_eq!(get(&format!("/{}", long)), Some(Value::from("long")));
+
+    assert_eq!(get("/header/kid"), None);
+    assert_eq!(get("/payload/3"), None);
+    assert_eq!(get("/payload/x"), None);
+    assert_eq!(get("/payload/01"), None);
+    assert_eq!(get("/payload/+1"), None);
+    assert_eq!(get("/payload/2/01"), None);
+    assert_eq!(get("/payload/2/+1"), None);
+    assert_eq!(get("/payload/2/-02"), None);
+    assert_eq!(get("/header/alg/0"), None);
+    assert_eq!(get(&format!("/{}k", long)), None);
+
+    match ciborium::extract::<Value, _>(&bytes[..], "header").unwrap_err() {
+        Error::Semantic(None, ..) => (),
+        e => panic!("incorrect error: {:?}", e),
+    }
+}
+
+#[test]
+fn extract_indefinite() {
+    // {_ (_ "he", "ad"): 6([_ 1, {_ "x": 2}])}
+    let bytes = hex::decode("bf7f626865626164ffc69f01bf617802ffffff").unwrap();
+
+    let x: Option<u8> = ciborium::extract(&bytes[..], "/head/1/x").unwrap();
+    assert_eq!(x, Some(2));
+
+    let y: Option<u8> = ciborium::extract(&bytes[..], "/head/2").unwrap();
+    assert_eq!(y, None);
+
+    let z: Option<u8> = ciborium::extract(&bytes[..], "/hea").unwrap();
+    assert_eq!(z, None);
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::cbor;
+use ciborium::value::{Query, Value};
+use rstest::rstest;
+
+fn document() -> Value {
+    cbor!({
+        "store" => {
+            "books" => [
+                { "title" => "A", "price" => 8, "tags" => ["x"] },
+                { "title" => "B", "price" => 12.5 },
+                { "title" => "C", "price" => 20, "isbn" => "123" },
+            ],
+            "bike" => { "price" => 100, "color" => "red" },
+        },
+        1 => Value::Tag(42, Box::new(cbor!([true, false]).unwrap())),
+    })
+    .unwrap()
+}
+
+#[rstest]
+#[case("$", &["$"])]
+#[case("$.store.bike.color", &[r#"$["store"]["bike"]["color"]"#])]
+#[case(r#"$["store"]['bike']"#, &[r#"$["store"]["bike"]"#])]
+#[case("$.store.books[0].title", &[r#"$["store"]["books"][0]["title"]"#])]
+#[case("$.store.books[-1].title", &[r#"$["store"]["books"][2]["title"]"#])]
+#[case("$.store.books[3]", &[])]
+#[case("$[1][1]", &["$[1][1]"])]
+#[case("$.store.*", &[r#"$["store"]["books"]"#, r#"$["store"]["bike"]"#])]
+#[case(
+    "$.store.books[*].title",
+    &[
+        r#"$["store"]["books"][0]["title"]"#,
+        r#"$["store"]["books"][1]["title"]"#,
+        r#"$["store"]["books"][2]["title"]"#,
+    ]
+)]
+#[case(
+    "$..price",
+    &[
+        r#"$["store"]["books"][0]["price"]"#,
+        r#"$["store"]["books"][1]["price"]"#,
+        r#"$["store"]["books"][2]["price"]"#,
+        r#"$["store"]["bike"]["price"]"#,
+    ]
+)]
+#[case("$..[0]", &[r#"$["store"]["books"][0]"#, r#"$["store"]["books"][0]["tags"][0]"#, "$[1][0]"])]
+#[case("$.store.books[?(@.isbn)].title", &[r#"$["store"]["books"][2]["title"]"#])]
+#[case(
+    "$.store.books[?(@.price > 10)].title",
+    &[r#"$["store"]["books"][1]["title"]"#, r#"$["store"]["books"][2]["title"]"#]
+)]
+#[case("$.store.books[?(@.price <= 8)]", &[r#"$["store"]["books"][0]"#])]
+#[case("$.store.books[?( @.title == 'B' )]", &[r#"$["store"]["books"][1]"#])]
+#[case(
+    "$.store.books[?(@.title != \"B\")]",
+    &[r#"$["store"]["books"][0]"#, r#"$["store"]["books"][2]"#]
+)]
+#[case("$.store.books[?(@.tags[0] == \"x\")]", &[r#"$["store"]["books"][0]"#])]
+#[case("$..[?(@.color >= 'red')]", &[r#"$["store"]["bike"]"#])]
+#[case("$..*[?(@ == true)]", &["$[1][0]"])]
+fn select(#[case] query: &str, #[case] expected: &[&str]) {
+    let document = document();
+    let selected = document.query(query).unwrap();
+    let paths: Vec<String> = selected.iter().map(|(p, _)| p.to_string()).collect();
+    assert_eq!(paths, expected);
+
+    for (path, value) in selected {
+        assert_eq!(document.get_path(&path), Some(value));
+    }
+}
+
+#[rstest]
+#[case("")]
+#[case("store")]
+#[case("$.")]
+#[case("$[")]
+#[case("$[true]")]
+#[case("$['a")]
+#[case("$[?(@.a > )]")]
+#[case("$[?(@.a")]
+#[case("$ x")]
+fn invalid(#[case] query: &str) {
+    assert!(query.parse::<Query>().is_err());
+}
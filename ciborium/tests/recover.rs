@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "std")]
+
+use std::io;
+
+use ciborium::de::{Error, Recover};
+use ciborium::Value;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u32,
+    name: String,
+}
+
+fn record(id: u32) -> Vec<u8> {
+    let name = "x".repeat(id as usize);
+    ciborium::into_vec(&Record { id, name }).unwrap()
+}
+
+/// A reader which hands out one byte at a time, then fails after `fail`
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    fail: Option<usize>,
+}
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.fail {
+            Some(0) => return Err(io::ErrorKind::BrokenPipe.into()),
+            Some(ref mut x) => *x -= 1,
+            None => (),
+        }
+
+        match self.bytes.split_first() {
+            Some((byte, rest)) => {
+                buf[0] = *byte;
+                self.bytes = rest;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn reader() {
+    let bytes = [&record(1)[..], &[0x1c, 0xff], &record(2)].concat();
+    let damaged = record(1).len() as u64;
+
+    let reader = Trickle {
+        bytes: &bytes,
+        fail: None,
+    };
+
+    let items: Vec<_> = Recover::<_, Record>::new(reader).probe(64).collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].as_ref().unwrap().id, 1);
+    assert_eq!(items[1].as_ref().unwrap_err().range, damaged..damaged + 2);
+    assert_eq!(items[2].as_ref().unwrap().id, 2);
+}
+
+#[test]
+fn window() {
+    // The second record does not fit in the window, so it is skipped.
+    let bytes = [&record(1)[..], &[0x1c], &record(40), &record(3)].concat();
+    let damaged = record(1).len() as u64;
+    let resumed = damaged + 1 + record(40).len() as u64;
+
+    let items: Vec<_> = Recover::<_, Record>::new(&bytes[..]).probe(32).collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1].as_ref().unwrap_err().range, damaged..resumed);
+    assert_eq!(items[2].as_ref().unwrap().id, 3);
+}
+
+#[test]
+fn long_damage() {
+    // 55799(1), 10000 bytes of damage, then 55799(2)
+    let bytes = [
+        &[0xd9, 0xd9, 0xf7, 0x01][..],
+        &[0x1c; 10000],
+        &[0xd9, 0xd9, 0xf7, 0x02],
+    ]
+    .concat();
+
+    let items: Vec<_> = Recover::<_, Value>::new(&bytes[..]).collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1].as_ref().unwrap_err().range, 4..10004);
+    assert_eq!(
+        items[2].as_ref().unwrap(),
+        &Value::Tag(55799, Box::new(2.into()))
+    );
+}
+
+#[test]
+fn reader_error() {
+    let bytes = [&record(1)[..], &record(2)].concat();
+    let reader = Trickle {
+        bytes: &bytes,
+        fail: Some(record(1).len() + 3),
+    };
+
+    let mut items = Recover::<_, Record>::new(reader).probe(64);
+    assert_eq!(items.next().unwrap().unwrap().id, 1);
+
+    let skipped = items.next().unwrap().unwrap_err();
+    assert!(matches!(skipped.error, Error::Io(..)));
+    assert!(items.next().is_none());
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "std")]
+
+use ciborium::de::{Deserializer, Error, Options, Replay};
+use ciborium::{cbor, Value};
+use ciborium_io::Read;
+use serde::Deserialize;
+
+/// A reader which cannot seek and returns one byte at a time
+struct Trickle<'a>(&'a [u8]);
+
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((&byte, rest)), Some(slot)) => {
+                *slot = byte;
+                self.0 = rest;
+                Ok(1)
+            }
+
+            _ => Ok(0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Point(Point),
+    Path(Vec<Point>),
+    Other(Value),
+}
+
+fn shape<R: Read>(de: &mut Deserializer<'_, Replay<R>>) -> Result<Shape, Error<R::Error>>
+where
+    R::Error: std::fmt::Debug,
+{
+    de.speculate(|de| Point::deserialize(de).map(Shape::Point))
+        .or_else(|_| de.speculate(|de| Vec::deserialize(de).map(Shape::Path)))
+        .or_else(|_| Value::deserialize(de).map(Shape::Other))
+}
+
+#[test]
+fn fallback() {
+    let items = [
+        cbor!({ "x" => 1, "y" => 2 }).unwrap(),
+        cbor!([{ "x" => 3, "y" => 4 }, { "x" => 5, "y" => 6 }]).unwrap(),
+        cbor!([{ "x" => 7, "z" => 8 }]).unwrap(),
+        cbor!("done").unwrap(),
+    ];
+
+    let mut bytes = Vec::new();
+    for item in &items {
+        ciborium::into_writer(item, &mut bytes).unwrap();
+    }
+
+    let mut scratch = [0u8; 16];
+    let reader = Replay::new(Trickle(&bytes));
+    let mut de = Deserializer::new(reader, &mut scratch, Options::new());
+
+    assert_eq!(shape(&mut de).unwrap(), Shape::Point(Point { x: 1, y: 2 }));
+    assert_eq!(
+        shape(&mut de).unwrap(),
+        Shape::Path(vec![Point { x: 3, y: 4 }, Point { x: 5, y: 6 }])
+    );
+    assert_eq!(shape(&mut de).unwrap(), Shape::Other(items[2].clone()));
+    assert_eq!(shape(&mut de).unwrap(), Shape::Other(items[3].clone()));
+}
+
+#[test]
+fn nested() {
+    // [1, "two"] followed by 3
+    let bytes = [0x82, 0x01, 0x63, b't', b'w', b'o', 0x03];
+
+    let mut scratch = [0u8; 16];
+    let mut de = Deserializer::new(Replay::new(&bytes[..]), &mut scratch, Options::new());
+
+    let pair = de.speculate(|de| {
+        let inner = de.speculate(|de| <(u8, u8)>::deserialize(de));
+        assert!(inner.is_err());
+        <(u8, String)>::deserialize(de)
+    });
+
+    assert_eq!(pair.unwrap(), (1, "two".into()));
+    assert_eq!(u8::deserialize(&mut de).unwrap(), 3);
+}
+
+#[test]
+fn offsets() {
+    // [1, 2] followed by a truncated item
+    let bytes = [0x82, 0x01, 0x02, 0x1c];
+
+    let mut scratch = [0u8; 16];
+    let mut de = Deserializer::new(Replay::new(&bytes[..]), &mut scratch, Options::new());
+
+    assert!(de.speculate(|de| String::deserialize(de)).is_err());
+    assert_eq!(<[u8; 2]>::deserialize(&mut de).unwrap(), [1, 2]);
+    assert!(matches!(u8::deserialize(&mut de), Err(Error::Syntax(3))));
+}
+
+#[test]
+fn reader() {
+    let mut replay = Replay::new(&[1u8, 2, 3, 4][..]);
+    let mut byte = [0u8; 1];
+    let mut pair = [0u8; 2];
+
+    replay.checkpoint();
+    replay.read_exact(&mut pair).unwrap();
+    replay.checkpoint();
+    replay.read_exact(&mut byte).unwrap();
+    assert_eq!(byte, [3]);
+
+    replay.rollback();
+    replay.read_exact(&mut byte).unwrap();
+    assert_eq!(byte, [3]);
+
+    replay.rollback();
+    replay.read_exact(&mut pair).unwrap();
+    assert_eq!(pair, [1, 2]);
+    replay.read_exact(&mut pair).unwrap();
+    assert_eq!(pair, [3, 4]);
+}
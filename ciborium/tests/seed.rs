@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{from_reader_seed, from_slice_seed};
+use serde::de::{DeserializeSeed, Deserializer, SeqAccess, Visitor};
+
+/// Sums an array of integers, adding an initial offset
+struct Sum(u64);
+
+impl<'de> DeserializeSeed<'de> for Sum {
+    type Value = u64;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Sum {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "an array of integers")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut acc: A) -> Result<u64, A::Error> {
+        let mut sum = self.0;
+        while let Some(x) = acc.next_element::<u64>()? {
+            sum += x;
+        }
+        Ok(sum)
+    }
+}
+
+#[test]
+fn reader() {
+    let bytes = hex::decode("83010203").unwrap();
+    assert_eq!(from_reader_seed(&bytes[..], Sum(10)).unwrap(), 16);
+}
+
+#[test]
+fn slice() {
+    let bytes = hex::decode("9f0102ff").unwrap();
+    assert_eq!(from_slice_seed(&bytes, Sum(0)).unwrap(), 3);
+}
+
+#[test]
+fn slice_error() {
+    let bytes = hex::decode("a0").unwrap();
+    from_slice_seed(&bytes, Sum(0)).unwrap_err();
+}
+
+#[test]
+fn interned_keys() {
+    use ciborium::de::Interner;
+    use std::rc::Rc;
+
+    // [{"a": 1, "b": 2}, {"b": 3, "a": 4}]
+    let bytes = hex::decode("82a2616101616202a2616203616104").unwrap();
+
+    let mut interner = Interner::new();
+    let records = from_slice_seed(&bytes, interner.records::<u8>()).unwrap();
+    assert_eq!(interner.len(), 2);
+
+    let keys: Vec<_> = records
+        .iter()
+        .map(|r| r.keys().collect::<Vec<_>>())
+        .collect();
+    assert!(Rc::ptr_eq(keys[0][0], keys[1][0]));
+    assert!(Rc::ptr_eq(keys[0][1], keys[1][1]));
+    assert_eq!(records[1]["a"], 4);
+
+    // Non-text keys are rejected.
+    let bytes = hex::decode("81a10101").unwrap();
+    from_slice_seed(&bytes, interner.records::<u8>()).unwrap_err();
+}
+
+#[test]
+fn value() {
+    use ciborium::Value;
+
+    let value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
+    assert_eq!(value.deserialized_seed(Sum(10)).unwrap(), 16);
+    Value::from(1).deserialized_seed(Sum(0)).unwrap_err();
+}
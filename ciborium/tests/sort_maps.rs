@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use ciborium::de::{self, from_reader_with_options, from_slice_with_options, Error};
+use ciborium::ser::{into_writer_with_options, Options};
+use ciborium::value::{KeyOrder, Value};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct Record {
+    zone: &'static str,
+    id: u32,
+    labels: HashMap<String, i64>,
+    #[serde(flatten)]
+    extra: BTreeMap<i64, bool>,
+}
+
+#[derive(Serialize)]
+enum Message {
+    Update { value: u8, key: u8 },
+}
+
+fn encode<T: Serialize>(value: &T, options: Options) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    into_writer_with_options(value, &mut bytes, options).unwrap();
+    bytes
+}
+
+/// Encodes the value with all of its maps sorted through `Value`
+fn sorted<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut value = Value::serialized(value).unwrap();
+    value.sort_keys(KeyOrder::Lexical, true);
+    encode(&value, Options::new())
+}
+
+#[test]
+fn sort_maps() {
+    let record = Record {
+        zone: "eu",
+        id: 7,
+        labels: (0..32).map(|i| (format!("label{}", i), -i)).collect(),
+        extra: [(-1, true), (1000, false), (2, true)].into(),
+    };
+
+    let options = Options::new().sort_maps(true);
+    let bytes = encode(&record, options);
+    assert_eq!(bytes, sorted(&record));
+    assert_eq!(bytes[0], 0xa6); // Flattened maps are no longer indefinite
+
+    let message = Message::Update { value: 1, key: 2 };
+    let bytes = encode(&message, options);
+    assert_eq!(
+        hex::encode(&bytes),
+        "a166557064617465a2636b6579026576616c756501"
+    );
+
+    // Structs written as arrays keep their field order.
+    let bytes = encode(&message, options.struct_as_array(true));
+    assert_eq!(hex::encode(&bytes), "a166557064617465820102");
+}
+
+#[rstest]
+#[case("a0", None, None)]
+#[case("a26161011903e802", None, Some(4))]
+#[case("a21903e802616101", Some(5), None)]
+#[case("a2616101616102", Some(4), Some(4))]
+#[case("bf616201616102ff", Some(4), Some(4))]
+#[case("a16161a2616201616102", Some(7), Some(7))]
+#[case("a27f6161ff01616202", Some(6), Some(6))]
+fn key_order(#[case] bytes: &str, #[case] length_first: Option<u64>, #[case] lexical: Option<u64>) {
+    let bytes = hex::decode(bytes).unwrap();
+
+    for (order, expected) in [
+        (KeyOrder::LengthFirst, length_first),
+        (KeyOrder::Lexical, lexical),
+    ] {
+        let options = de::Options::new().key_order(order);
+
+        let result = from_slice_with_options::<Value>(&bytes, options);
+        match (result, expected) {
+            (Ok(value), None) => assert_eq!(
+                value,
+                ciborium::from_reader::<Value, _>(&bytes[..]).unwrap()
+            ),
+            (Err(Error::Semantic(offset, _)), Some(expected)) => assert_eq!(offset, Some(expected)),
+            (result, _) => panic!("unexpected result under {:?}: {:?}", order, result),
+        }
+
+        let result = from_reader_with_options::<Value, _>(&bytes[..], options);
+        assert_eq!(result.is_ok(), expected.is_none());
+    }
+}
+
+#[test]
+fn key_order_round_trip() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entry {
+        name: String,
+        id: u32,
+        labels: BTreeMap<String, i64>,
+    }
+
+    let entry = Entry {
+        name: "eu".into(),
+        id: 7,
+        labels: (0..32).map(|i| (format!("label{}", i), -i)).collect(),
+    };
+
+    let bytes = encode(&entry, Options::new().sort_maps(true));
+    let options = de::Options::new().key_order(KeyOrder::Lexical);
+    let decoded: Entry = from_slice_with_options(&bytes, options).unwrap();
+    assert_eq!(decoded, entry);
+
+    // Struct fields are written in declaration order, which is not sorted.
+    let bytes = encode(&entry, Options::new());
+    assert!(from_slice_with_options::<Entry>(&bytes, options).is_err());
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{Deserializer, Options, Replay, Stats};
+use ciborium::{cbor, Value};
+use serde::Deserialize;
+
+/// Deserializes a `T` from `bytes`, along with the stats collected
+fn stats<'de, T: Deserialize<'de>>(bytes: &[u8]) -> (T, Stats) {
+    let mut scratch = [0u8; 16];
+    let mut deserializer = Deserializer::new(bytes, &mut scratch, Options::new());
+    deserializer.collect_stats();
+    let value = T::deserialize(&mut deserializer).unwrap();
+    (value, deserializer.stats().unwrap())
+}
+
+#[test]
+fn shape() {
+    let value = cbor!({
+        "id" => 7,
+        "deltas" => [-1, -2, 3.5, null],
+        "payload" => Value::Tag(24, Box::new(Value::Bytes(vec![0; 40]))),
+        "meta" => { "ok" => true },
+    })
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    let (_, stats) = stats::<Value>(&bytes);
+
+    assert_eq!(stats.unsigned, 1);
+    assert_eq!(stats.negative, 2);
+    assert_eq!(stats.bytes, 1);
+    assert_eq!(stats.text, 5);
+    assert_eq!(stats.arrays, 1);
+    assert_eq!(stats.maps, 2);
+    assert_eq!(stats.tags, 1);
+    assert_eq!(stats.simple, 3);
+    assert_eq!(stats.items(), 16);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.string_bytes, 2 + 6 + 7 + 4 + 2 + 40);
+    assert_eq!(stats.largest_collection, 4);
+}
+
+#[test]
+fn typed() {
+    #[derive(Deserialize)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    // The skipped field is counted along with those deserialized.
+    let value = cbor!({ "x" => 1, "y" => 2, "note" => [true, "far"] }).unwrap();
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let (point, stats) = stats::<Point>(&bytes);
+    assert_eq!((point.x, point.y), (1, 2));
+    assert_eq!((stats.maps, stats.arrays, stats.unsigned), (1, 1, 2));
+    assert_eq!((stats.text, stats.simple), (4, 1));
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.largest_collection, 3);
+}
+
+#[test]
+fn indefinite() {
+    // [_ "ab" (_ "c" "de"), h'00'] followed by 1
+    let bytes = [
+        0x9f, 0x62, b'a', b'b', 0x7f, 0x61, b'c', 0x62, b'd', b'e', 0xff, 0x41, 0x00, 0xff, 0x01,
+    ];
+
+    let mut scratch = [0u8; 16];
+    let mut deserializer = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    assert_eq!(deserializer.stats(), None);
+
+    deserializer.collect_stats();
+    Value::deserialize(&mut deserializer).unwrap();
+    let stats = deserializer.stats().unwrap();
+
+    // Only definite lengths are known from the headers.
+    assert_eq!((stats.arrays, stats.text, stats.bytes), (1, 2, 1));
+    assert_eq!(stats.string_bytes, 3);
+    assert_eq!(stats.largest_collection, 0);
+    assert_eq!(stats.max_depth, 2);
+
+    // Collection carries on across items until it is started over.
+    assert_eq!(u8::deserialize(&mut deserializer).unwrap(), 1);
+    assert_eq!(deserializer.stats().unwrap().unsigned, 1);
+
+    deserializer.collect_stats();
+    assert_eq!(deserializer.stats(), Some(Stats::default()));
+}
+
+#[test]
+fn speculate() {
+    // [2, "hello"]
+    let bytes = [0x82, 0x02, 0x65, b'h', b'e', b'l', b'l', b'o'];
+
+    let mut scratch = [0u8; 16];
+    let reader = Replay::new(&bytes[..]);
+    let mut deserializer = Deserializer::new(reader, &mut scratch, Options::new());
+    deserializer.collect_stats();
+
+    // A failed attempt is not counted.
+    assert!(deserializer
+        .speculate(|de| <(u8, u8)>::deserialize(de))
+        .is_err());
+    deserializer
+        .speculate(|de| <(u8, String)>::deserialize(de))
+        .unwrap();
+
+    let stats = deserializer.stats().unwrap();
+    assert_eq!((stats.arrays, stats.unsigned, stats.text), (1, 1, 1));
+    assert_eq!(stats.items(), 3);
+}
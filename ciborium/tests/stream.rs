@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "std")]
+
+use ciborium::de::{Deserializer, Error, Options};
+use ciborium::stream::{BytesSink, BytesSource};
+use ciborium::{cbor, Value};
+
+use rstest::rstest;
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+
+/// Records each write separately
+#[derive(Default)]
+struct Writes(Vec<Vec<u8>>);
+
+impl std::io::Write for Writes {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn stream(bytes: &str, scratch: usize) -> Result<Writes, Error<std::io::Error>> {
+    let bytes = hex::decode(bytes).unwrap();
+    let mut scratch = vec![0; scratch];
+    let mut de = Deserializer::new(&bytes[..], &mut scratch, Options::new());
+    BytesSink(Writes::default()).deserialize(&mut de)
+}
+
+#[rstest]
+#[case("40", 4, &[])]
+#[case("43010203", 4, &["010203"])]
+#[case("43010203", 2, &["0102", "03"])]
+#[case("5f410142020340ff", 4, &["01", "0203"])]
+#[case("5f43010203ff", 1, &["01", "02", "03"])]
+#[case("c24101", 4, &["01"])]
+#[case("6161", 4, &["61"])]
+#[case("63e282ac", 4, &["e282ac"])]
+#[case("6461e282ac", 3, &["61", "e282ac"])]
+#[case("7f6161626263ff", 4, &["61", "6263"])]
+fn chunks(#[case] bytes: &str, #[case] scratch: usize, #[case] writes: &[&str]) {
+    let writes: Vec<_> = writes.iter().map(|w| hex::decode(w).unwrap()).collect();
+    assert_eq!(stream(bytes, scratch).unwrap().0, writes);
+}
+
+#[rstest]
+#[case("01", 4)]
+#[case("4201", 4)]
+#[case("5f6161ff", 4)]
+#[case("7f4161ff", 4)]
+#[case("62ff61", 4)]
+#[case("7f62e28261acff", 4)]
+#[case("63e282ac", 1)]
+#[case("4101", 0)]
+fn invalid(#[case] bytes: &str, #[case] scratch: usize) {
+    assert!(stream(bytes, scratch).is_err());
+}
+
+#[test]
+fn field() {
+    #[derive(Deserialize)]
+    struct Upload {
+        name: String,
+        body: BytesSink<Vec<u8>>,
+        size: usize,
+    }
+
+    let body: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+    let value = cbor!({
+        "name" => "a.bin",
+        "body" => Value::Bytes(body.clone()),
+        "size" => body.len(),
+    })
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+
+    let upload: Upload = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(upload.name, "a.bin");
+    assert_eq!(upload.body.0, body);
+    assert_eq!(upload.size, body.len());
+
+    let upload: Upload = value.deserialized().unwrap();
+    assert_eq!(upload.body.0, body);
+}
+
+#[rstest]
+#[case(Value::Bytes(vec![1, 2, 3]), &[1, 2, 3])]
+#[case(cbor!([1, 2, 3]).unwrap(), &[1, 2, 3])]
+fn from_value(#[case] value: Value, #[case] bytes: &[u8]) {
+    let sink: BytesSink<Vec<u8>> = value.deserialized().unwrap();
+    assert_eq!(sink.0, bytes);
+}
+
+#[rstest]
+#[case("[1, 2, 3]", &[1, 2, 3])]
+#[case("\"abc\"", b"abc")]
+fn from_json(#[case] json: &str, #[case] bytes: &[u8]) {
+    let sink: BytesSink<Vec<u8>> = serde_json::from_str(json).unwrap();
+    assert_eq!(sink.0, bytes);
+
+    assert!(serde_json::from_str::<BytesSink<Vec<u8>>>("[256]").is_err());
+}
+
+/// Returns at most one byte per read
+struct Trickle<'a>(&'a [u8]);
+
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(first)) => {
+                *first = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[rstest]
+#[case(BytesSource::new(Trickle(&[])), "5fff")]
+#[case(BytesSource::new(Trickle(&[1, 2, 3])), "5f410141024103ff")]
+#[case(BytesSource::with_len(Trickle(&[]), 0), "40")]
+#[case(BytesSource::with_len(Trickle(&[1, 2, 3]), 3), "43010203")]
+#[case(BytesSource::with_len(Trickle(&[1, 2, 3]), 2), "420102")]
+fn encode(#[case] source: BytesSource<Trickle<'static>>, #[case] bytes: &str) {
+    let mut encoded = Vec::new();
+    ciborium::into_writer(&source, &mut encoded).unwrap();
+    assert_eq!(hex::encode(encoded), bytes);
+}
+
+#[test]
+fn encode_short() {
+    let source = BytesSource::with_len(Trickle(&[1, 2]), 3);
+    assert!(ciborium::into_writer(&source, Vec::new()).is_err());
+}
+
+#[test]
+fn round_trip() {
+    #[derive(Serialize)]
+    struct Sent<'a> {
+        body: BytesSource<&'a [u8]>,
+        size: usize,
+    }
+
+    #[derive(Deserialize)]
+    struct Received {
+        body: BytesSink<Vec<u8>>,
+        size: usize,
+    }
+
+    let body: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+
+    for sent in [
+        Sent {
+            body: BytesSource::new(&body[..]),
+            size: body.len(),
+        },
+        Sent {
+            body: BytesSource::with_len(&body[..], body.len()),
+            size: body.len(),
+        },
+    ] {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&sent, &mut bytes).unwrap();
+
+        let received: Received = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_eq!(received.body.0, body);
+        assert_eq!(received.size, body.len());
+    }
+
+    let value = Value::serialized(&BytesSource::new(Trickle(&[1, 2]))).unwrap();
+    assert_eq!(
+        value,
+        cbor!([Value::Bytes(vec![1]), Value::Bytes(vec![2])]).unwrap()
+    );
+
+    let sink: BytesSink<Vec<u8>> = value.deserialized().unwrap();
+    assert_eq!(sink.0, [1, 2]);
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::ser::{into_writer_with_options, Options};
+use ciborium::{cbor, de::from_reader, into_writer, Value};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: u8,
+    value: i16,
+    inner: Inner,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    ok: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Message {
+    Reading { sensor: u8, value: i16 },
+}
+
+fn encode<T: Serialize>(value: &T, options: Options) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    into_writer_with_options(value, &mut bytes, options).unwrap();
+    bytes
+}
+
+#[test]
+fn struct_as_array() {
+    let reading = Reading {
+        sensor: 3,
+        value: -2,
+        inner: Inner { ok: true },
+    };
+
+    let bytes = encode(&reading, Options::new().struct_as_array(true));
+    assert_eq!(hex::encode(&bytes), "83032181f5");
+    assert_eq!(from_reader::<Reading, _>(&bytes[..]).unwrap(), reading);
+
+    let bytes = encode(&reading, Options::new());
+    let mut expected = Vec::new();
+    into_writer(&reading, &mut expected).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(from_reader::<Reading, _>(&bytes[..]).unwrap(), reading);
+}
+
+#[test]
+fn struct_variant_as_array() {
+    let message = Message::Reading {
+        sensor: 1,
+        value: 2,
+    };
+
+    let bytes = encode(&message, Options::new().struct_as_array(true));
+    let value: Value = from_reader(&bytes[..]).unwrap();
+    assert_eq!(value, cbor!({ "Reading" => [1, 2] }).unwrap());
+    assert_eq!(from_reader::<Message, _>(&bytes[..]).unwrap(), message);
+}
+
+#[rstest]
+#[case("820321")] // too short
+#[case("8303f481f5")] // wrong type
+fn struct_as_array_invalid(#[case] bytes: &str) {
+    let bytes = hex::decode(bytes).unwrap();
+    from_reader::<Reading, _>(&bytes[..]).unwrap_err();
+}
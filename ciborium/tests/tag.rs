@@ -19,6 +19,13 @@ use core::fmt::Debug;
     case(Accepted::<_, 6>(true), "c6f5", Value::Tag(6, Value::Bool(true).into()), true, true),
     case(Accepted::<_, 6>(true), "c7f5", Value::Tag(7, Value::Bool(true).into()), false, false),
     case(Accepted::<_, 6>(true), "f5", Value::Bool(true), false, true),
+
+    case(Defaulted::<_, 6>(true), "c6f5", Value::Tag(6, Value::Bool(true).into()), true, true),
+    case(Defaulted::<_, 6>(true), "c7f5", Value::Tag(7, Value::Bool(true).into()), false, false),
+    case(Defaulted::<_, 6>(true), "f5", Value::Bool(true), false, false),
+    case(Defaulted::<_, 6>(false), "f6", Value::Null, true, true),
+    case(Defaulted::<_, 6>(false), "f7", Value::Null, false, true),
+    case(Defaulted::<_, 6>(false), "c6f4", Value::Tag(6, Value::Bool(false).into()), false, true),
 )]
 fn test<T: Serialize + DeserializeOwned + Debug + Eq>(
     item: T,
@@ -55,3 +62,98 @@ fn test<T: Serialize + DeserializeOwned + Debug + Eq>(
         Err(..) => (),
     }
 }
+
+#[rstest(item, json,
+    case(Captured(Some(6), true), r#"{"tag":6,"value":true}"#),
+    case(Captured(None, true), r#"{"tag":null,"value":true}"#),
+    case(Required::<_, 6>(true), r#"{"tag":6,"value":true}"#),
+    case(Accepted::<_, 6>(true), r#"{"tag":6,"value":true}"#),
+    case(Defaulted::<_, 6>(true), r#"{"tag":6,"value":true}"#),
+    case(Defaulted::<_, 6>(false), "null"),
+)]
+fn human_readable<T: Serialize + DeserializeOwned + Debug + Eq>(item: T, json: &str) {
+    assert_eq!(serde_json::to_string(&item).unwrap(), json);
+    assert_eq!(serde_json::from_str::<T>(json).unwrap(), item);
+}
+
+#[test]
+fn human_readable_tag_checks() {
+    let wrong = r#"{"tag":7,"value":true}"#;
+    serde_json::from_str::<Required<bool, 6>>(wrong).unwrap_err();
+    serde_json::from_str::<Accepted<bool, 6>>(wrong).unwrap_err();
+
+    let untagged = r#"{"value":true}"#;
+    serde_json::from_str::<Required<bool, 6>>(untagged).unwrap_err();
+    assert_eq!(
+        serde_json::from_str::<Accepted<bool, 6>>(untagged).unwrap(),
+        Accepted(true)
+    );
+    assert_eq!(
+        serde_json::from_str::<Captured<bool>>(untagged).unwrap(),
+        Captured(None, true)
+    );
+}
+
+struct Dates;
+
+impl TagSet for Dates {
+    const TAGS: &'static [u64] = &[0, 1];
+}
+
+#[rstest(
+    bytes,
+    tag,
+    case("c0f5", Some(0)),
+    case("c1f5", Some(1)),
+    case("c2f5", None),
+    case("f5", None)
+)]
+fn required_one_of(bytes: &str, tag: Option<u64>) {
+    let bytes = hex::decode(bytes).unwrap();
+    let result = from_reader::<RequiredOneOf<bool, Dates>, _>(&bytes[..]);
+
+    match tag {
+        Some(tag) => {
+            let item = result.unwrap();
+            assert_eq!(item.tag(), tag);
+            assert!(*item.value());
+
+            let mut encoded = Vec::new();
+            into_writer(&item, &mut encoded).unwrap();
+            assert_eq!(encoded, bytes);
+        }
+
+        None => {
+            result.unwrap_err();
+        }
+    }
+}
+
+#[rstest(
+    bytes,
+    tag,
+    encoded,
+    case("c0f5", Some(0), "c0f5"),
+    case("c1f5", Some(1), "c1f5"),
+    case("f5", None, "c0f5")
+)]
+fn accepted_one_of(bytes: &str, tag: Option<u64>, encoded: &str) {
+    let bytes = hex::decode(bytes).unwrap();
+    let item: AcceptedOneOf<bool, Dates> = from_reader(&bytes[..]).unwrap();
+    assert_eq!(item.tag(), tag);
+    assert!(item.into_value());
+
+    let item = AcceptedOneOf::<bool, Dates>::new(tag, true).unwrap();
+    let mut output = Vec::new();
+    into_writer(&item, &mut output).unwrap();
+    assert_eq!(hex::encode(output), encoded);
+}
+
+#[test]
+fn one_of_rejects() {
+    let bytes = hex::decode("c2f5").unwrap();
+    from_reader::<AcceptedOneOf<bool, Dates>, _>(&bytes[..]).unwrap_err();
+
+    assert!(RequiredOneOf::<bool, Dates>::new(2, true).is_none());
+    assert!(AcceptedOneOf::<bool, Dates>::new(Some(2), true).is_none());
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::de::{from_reader_with_options, Options};
+use ciborium::{cbor, Value};
+use rstest::rstest;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Record {
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+    },
+    Integer(i64),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+enum Internal {
+    Record {
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        n: i64,
+    },
+    Any {
+        value: Value,
+    },
+}
+
+const OPTIONS: Options = Options::new().untagged_enums(true);
+
+#[rstest]
+#[case("a16464617461420102", Untagged::Record { data: vec![1, 2] })]
+#[case("c105", Untagged::Integer(5))]
+#[case("c2420100", Untagged::Integer(256))]
+#[case("c3420100", Untagged::Integer(-257))]
+#[case("d82068687474703a2f2f78", Untagged::Text("http://x".into()))]
+#[case("d9d9f7c16474657874", Untagged::Text("text".into()))]
+fn untagged(#[case] bytes: &str, #[case] expected: Untagged) {
+    let bytes = hex::decode(bytes).unwrap();
+    let value: Untagged = from_reader_with_options(&bytes[..], OPTIONS).unwrap();
+    assert_eq!(value, expected);
+}
+
+#[rstest]
+#[case(cbor!({ "type" => "Record", "data" => Value::Bytes(vec![1]), "n" => -3 }).unwrap())]
+#[case(Value::Tag(9, Box::new(cbor!({ "data" => Value::Bytes(vec![1]), "type" => "Record", "n" => -3 }).unwrap())))]
+fn internally_tagged(#[case] input: Value) {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&input, &mut bytes).unwrap();
+    let value: Internal = from_reader_with_options(&bytes[..], OPTIONS).unwrap();
+    assert_eq!(
+        value,
+        Internal::Record {
+            data: vec![1],
+            n: -3
+        }
+    );
+}
+
+#[test]
+fn internally_tagged_value() {
+    // Tags inside buffered content are dropped; integer keys survive.
+    let input = cbor!({
+        "type" => "Any",
+        "value" => { 1 => Value::Tag(7, Box::new(Value::Bytes(vec![1]))) },
+    })
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&input, &mut bytes).unwrap();
+    let value: Internal = from_reader_with_options(&bytes[..], OPTIONS).unwrap();
+    assert_eq!(
+        value,
+        Internal::Any {
+            value: cbor!({ 1 => Value::Bytes(vec![1]) }).unwrap()
+        }
+    );
+}
+
+#[test]
+fn untagged_enums_disabled() {
+    // Without the option, tags and bignums reach serde's buffer and fail.
+    for bytes in ["c105", "c2420100"] {
+        let bytes = hex::decode(bytes).unwrap();
+        assert!(ciborium::from_reader::<Untagged, _>(&bytes[..]).is_err());
+    }
+
+    let bytes = hex::decode("a16464617461420102").unwrap();
+    let value: Untagged = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(value, Untagged::Record { data: vec![1, 2] });
+}
+
+#[test]
+fn tags_preserved_outside_buffering() {
+    let bytes = hex::decode("c105").unwrap();
+    let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(value, Value::Tag(1, Box::new(5.into())));
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::validate::Schema;
+use ciborium::{cbor, Value};
+use rstest::rstest;
+
+fn header() -> Schema {
+    Schema::map()
+        .required(1, Schema::one_of([Schema::integer(), Schema::text()]))
+        .key(4, Schema::bytes())
+        .key("crit", Schema::array(Schema::text()))
+        .key("x5t", Schema::tagged(24, Schema::bytes()))
+        .key("v", Schema::exactly(1))
+}
+
+fn messages(schema: &Schema, value: &Value) -> Vec<String> {
+    let violations = schema.validate(value);
+    violations.iter().map(ToString::to_string).collect()
+}
+
+#[rstest]
+#[case(cbor!({ 1 => -7 }), &[])]
+#[case(cbor!({ 1 => "ES256", 4 => Value::Bytes(vec![1]), "other" => null }), &[])]
+#[case(cbor!({ 1 => Value::from(u128::MAX) }), &[])]
+#[case(cbor!({ 4 => Value::Bytes(vec![]) }), &["$: missing required key 1"])]
+#[case(cbor!({ 1 => 1.5 }), &["$[1]: matches none of the alternatives"])]
+#[case(cbor!({ 1 => 1, 4 => "kid" }), &["$[4]: expected bytes, found text"])]
+#[case(
+    cbor!({ 1 => 1, "crit" => ["a", [], "b", false] }),
+    &[r#"$["crit"][1]: expected text, found array"#, r#"$["crit"][3]: expected text, found bool"#]
+)]
+#[case(
+    cbor!({ 1 => 1, "x5t" => Value::Bytes(vec![]) }),
+    &[r#"$["x5t"]: expected tag 24, found bytes"#]
+)]
+#[case(
+    cbor!({ 1 => 1, "x5t" => Value::Tag(24, Box::new(Value::Null)) }),
+    &[r#"$["x5t"]: expected bytes, found null"#]
+)]
+#[case(cbor!({ 1 => 1, "v" => 2 }), &[r#"$["v"]: expected 1"#])]
+#[case(cbor!([1]), &["$: expected map, found array"])]
+fn validate(#[case] value: Result<Value, ciborium::value::Error>, #[case] expected: &[&str]) {
+    let value = value.unwrap();
+    assert_eq!(messages(&header(), &value), expected);
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    let violations = header().validate_reader(&bytes[..]).unwrap();
+    assert_eq!(violations, header().validate(&value));
+}
+
+#[test]
+fn closed() {
+    let value = cbor!({ 1 => 1, "extra" => 2 }).unwrap();
+    assert!(header().validate(&value).is_empty());
+
+    let violations = header().closed().validate(&value);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path().segments(), [Value::from("extra")]);
+    assert_eq!(violations[0].message(), "unexpected key");
+}
+
+#[test]
+fn invalid_input() {
+    assert!(Schema::any().validate_reader(&[0x82, 0x01][..]).is_err());
+}
+
+#[test]
+#[should_panic]
+fn key_on_non_map() {
+    let _ = Schema::array(Schema::any()).key("a", Schema::any());
+}
@@ -0,0 +1,475 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::{cbor, value::Value};
+
+#[test]
+fn eq_primitives() {
+    let value = cbor!({
+        "name" => "alice",
+        "admin" => true,
+        "age" => 42,
+        "delta" => -7,
+        "score" => 9.5,
+    })
+    .unwrap();
+
+    let get = |key: &str| {
+        value
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .unwrap()
+    };
+
+    assert_eq!(*get("name"), "alice");
+    assert_eq!("alice", *get("name"));
+    assert_eq!(*get("name"), String::from("alice"));
+    assert_ne!(*get("name"), "bob");
+
+    assert_eq!(*get("admin"), true);
+    assert_eq!(true, *get("admin"));
+
+    assert_eq!(*get("age"), 42u64);
+    assert_eq!(*get("age"), 42i64);
+    assert_eq!(42u64, *get("age"));
+    assert_eq!(*get("delta"), -7i64);
+    assert_ne!(*get("delta"), 7u64);
+
+    assert_eq!(*get("score"), 9.5);
+    assert_eq!(9.5, *get("score"));
+
+    // No conversions between kinds
+    assert_ne!(Value::Float(42.0), 42u64);
+    assert_ne!(Value::Integer(42.into()), 42.0);
+    assert_ne!(Value::Text("true".into()), true);
+}
+
+#[test]
+fn from_btreemap() {
+    let mut map = std::collections::BTreeMap::new();
+    map.insert("b", 2u8);
+    map.insert("a", 1u8);
+
+    let value = Value::from(map);
+    assert_eq!(value, cbor!({ "a" => 1, "b" => 2 }).unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_hashmap() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(1u8, vec![0u8]);
+
+    let value = Value::from(map);
+    assert_eq!(value, Value::Map(vec![(1.into(), Value::Bytes(vec![0]))]));
+}
+
+#[test]
+fn from_iter() {
+    let value: Value = vec![1u8, 2, 3].into_iter().collect();
+    assert_eq!(value, cbor!([1, 2, 3]).unwrap());
+
+    let value: Value = std::iter::empty::<bool>().collect();
+    assert_eq!(value, Value::Array(vec![]));
+}
+
+#[test]
+fn from_option() {
+    assert_eq!(Value::from(Some("x")), Value::Text("x".into()));
+    assert_eq!(Value::from(None::<u8>), Value::Null);
+    assert_eq!(Value::from(Some(Some(1u8))), Value::Integer(1.into()));
+
+    let nickname: Option<&str> = None;
+    let value = cbor!({ "nickname" => nickname }).unwrap();
+    assert_eq!(value, Value::Map(vec![("nickname".into(), Value::Null)]));
+}
+
+#[test]
+fn integer_repr() {
+    use ciborium::value::Integer;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut integers: Vec<Integer> = [0i128, -1, 1, u64::MAX.into(), -1 - i128::from(u64::MAX)]
+        .into_iter()
+        .map(|x| Integer::try_from(x).unwrap())
+        .collect();
+    integers.sort();
+
+    let sorted: Vec<i128> = integers.iter().map(|x| i128::from(*x)).collect();
+    assert_eq!(
+        sorted,
+        [-1 - i128::from(u64::MAX), -1, 0, 1, u64::MAX.into()]
+    );
+
+    assert_eq!(format!("{:?}", Integer::from(-5)), "Integer(-5)");
+
+    // Integers hash as the i128 they hold.
+    let hash = |x: &dyn Fn(&mut DefaultHasher)| {
+        let mut hasher = DefaultHasher::new();
+        x(&mut hasher);
+        hasher.finish()
+    };
+    for x in [0i128, -5, u64::MAX.into(), -1 - i128::from(u64::MAX)] {
+        let integer = Integer::try_from(x).unwrap();
+        assert_eq!(hash(&|h| integer.hash(h)), hash(&|h| x.hash(h)));
+    }
+
+    assert!(Integer::try_from(i128::from(u64::MAX) + 1).is_err());
+    assert!(Integer::try_from(-2 - i128::from(u64::MAX)).is_err());
+}
+
+// The examples of RFC 7396, Appendix A
+#[rstest::rstest]
+#[case(cbor!({"a" => "b"}), cbor!({"a" => "c"}), cbor!({"a" => "c"}))]
+#[case(cbor!({"a" => "b"}), cbor!({"b" => "c"}), cbor!({"a" => "b", "b" => "c"}))]
+#[case(cbor!({"a" => "b"}), cbor!({"a" => null}), cbor!({}))]
+#[case(cbor!({"a" => "b", "b" => "c"}), cbor!({"a" => null}), cbor!({"b" => "c"}))]
+#[case(cbor!({"a" => ["b"]}), cbor!({"a" => "c"}), cbor!({"a" => "c"}))]
+#[case(cbor!({"a" => "c"}), cbor!({"a" => ["b"]}), cbor!({"a" => ["b"]}))]
+#[case(
+    cbor!({"a" => {"b" => "c"}}),
+    cbor!({"a" => {"b" => "d", "c" => null}}),
+    cbor!({"a" => {"b" => "d"}})
+)]
+#[case(cbor!({"a" => [{"b" => "c"}]}), cbor!({"a" => [1]}), cbor!({"a" => [1]}))]
+#[case(cbor!(["a", "b"]), cbor!(["c", "d"]), cbor!(["c", "d"]))]
+#[case(cbor!({"a" => "b"}), cbor!(["c"]), cbor!(["c"]))]
+#[case(cbor!({"a" => "foo"}), cbor!(null), cbor!(null))]
+#[case(cbor!({"a" => "foo"}), cbor!("bar"), cbor!("bar"))]
+#[case(cbor!({"e" => null}), cbor!({"a" => 1}), cbor!({"e" => null, "a" => 1}))]
+#[case(cbor!([1, 2]), cbor!({"a" => "b", "c" => null}), cbor!({"a" => "b"}))]
+#[case(cbor!({}), cbor!({"a" => {"bb" => {"ccc" => null}}}), cbor!({"a" => {"bb" => {}}}))]
+// Keys need not be text.
+#[case(cbor!({1 => "a", 2 => "b"}), cbor!({2 => null, 3 => "c"}), cbor!({1 => "a", 3 => "c"}))]
+fn merge_patch(
+    #[case] target: Result<Value, ciborium::value::Error>,
+    #[case] patch: Result<Value, ciborium::value::Error>,
+    #[case] result: Result<Value, ciborium::value::Error>,
+) {
+    let mut target = target.unwrap();
+    target.apply_merge_patch(&patch.unwrap());
+    assert_eq!(target, result.unwrap());
+}
+
+#[test]
+fn path() {
+    use ciborium::value::Path;
+
+    let mut value = cbor!({ "a" => [0, { 1 => "x" }], 2 => null }).unwrap();
+    let path = Path::from_iter([cbor!("a").unwrap(), 1.into(), 1.into()]);
+
+    assert_eq!(path.to_string(), r#"$["a"][1][1]"#);
+    assert_eq!(value.get_path(&path), Some(&cbor!("x").unwrap()));
+    assert_eq!(value.get_path(&Path::new()), Some(&value));
+    assert_eq!(value.get_path(&Path::from_iter([2])), Some(&Value::Null));
+    assert_eq!(
+        value.get_path(&Path::from_iter([cbor!("a").unwrap(), 2.into()])),
+        None
+    );
+    assert_eq!(value.get_path(&Path::from_iter(["a", "0"])), None);
+
+    *value.get_path_mut(&path).unwrap() = cbor!("y").unwrap();
+    assert_eq!(
+        value,
+        cbor!({ "a" => [0, { 1 => "y" }], 2 => null }).unwrap()
+    );
+
+    // Tags are transparent.
+    let value = Value::Tag(
+        1,
+        Box::new(cbor!([Value::Tag(2, Box::new(5.into()))]).unwrap()),
+    );
+    assert_eq!(
+        value.get_path(&Path::from_iter([0])),
+        Some(&Value::Tag(2, Box::new(5.into())))
+    );
+}
+
+#[test]
+fn walk() {
+    let value = cbor!({ "a" => [1, Value::Tag(7, Box::new("x".into()))], 2 => {} }).unwrap();
+
+    let mut visited = Vec::new();
+    value.walk(&mut |path, item| visited.push((path.to_string(), item.diagnostic().to_string())));
+
+    assert_eq!(
+        visited,
+        [
+            ("$", r#"{"a": [1, 7("x")], 2: {}}"#),
+            (r#"$["a"]"#, r#"[1, 7("x")]"#),
+            (r#"$["a"][0]"#, "1"),
+            (r#"$["a"][1]"#, r#"7("x")"#),
+            (r#"$["a"][1]"#, r#""x""#),
+            ("$[2]", "{}"),
+        ]
+        .map(|(p, v)| (p.to_string(), v.to_string()))
+    );
+}
+
+#[test]
+fn map_values() {
+    let value = cbor!({ "a" => [1, 2], "b" => Value::Tag(7, Box::new(3.into())) }).unwrap();
+
+    // Children are transformed first, so containers see the new children.
+    let mut order = Vec::new();
+    let value = value.map_values(&mut |path, item| {
+        order.push(path.to_string());
+        match item {
+            Value::Integer(x) => Value::from(i128::from(x) * 10),
+            Value::Array(x) => Value::from(x.len() as u64),
+            item => item,
+        }
+    });
+
+    assert_eq!(
+        value,
+        cbor!({ "a" => 2, "b" => Value::Tag(7, Box::new(30.into())) }).unwrap()
+    );
+    assert_eq!(
+        order,
+        [
+            r#"$["a"][0]"#,
+            r#"$["a"][1]"#,
+            r#"$["a"]"#,
+            r#"$["b"]"#,
+            r#"$["b"]"#,
+            "$"
+        ]
+    );
+}
+
+#[test]
+fn pointer() {
+    let mut value =
+        cbor!({ "a/b" => [1, Value::Tag(7, Box::new(cbor!({ -1 => "x" }).unwrap()))] }).unwrap();
+
+    assert_eq!(value.pointer(""), Some(&value.clone()));
+    assert_eq!(value.pointer("/a~1b/1/-1"), Some(&cbor!("x").unwrap()));
+    assert_eq!(value.pointer("/a~1b/2"), None);
+    assert_eq!(value.pointer("/a~1b/01"), None);
+    assert_eq!(value.pointer("/a~1b/+1"), None);
+    assert_eq!(value.pointer("a"), None);
+
+    *value.pointer_mut("/a~1b/0").unwrap() = cbor!(2).unwrap();
+    assert_eq!(value.pointer("/a~1b/0"), Some(&cbor!(2).unwrap()));
+}
+
+#[rstest::rstest]
+#[case("/1", true)]
+#[case("/-1", true)]
+#[case("/0", true)]
+#[case("/01", false)]
+#[case("/+1", false)]
+#[case("/-01", false)]
+#[case("/-0", false)]
+#[case("/1.0", false)]
+fn pointer_integer_key(#[case] pointer: &str, #[case] found: bool) {
+    let value = cbor!({ 0 => "zero", 1 => "one", -1 => "minus one" }).unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    let extracted = ciborium::extract::<Value, _>(&bytes[..], pointer).unwrap();
+
+    assert_eq!(value.pointer(pointer).is_some(), found);
+    assert_eq!(value.pointer(pointer), extracted.as_ref());
+
+    let path = value.pointer_path(pointer);
+    assert_eq!(path.is_some(), found);
+    if let Some(path) = path {
+        assert_eq!(path.to_pointer().unwrap(), pointer);
+    }
+}
+
+#[rstest::rstest]
+#[case::replace("/a/0", false, Ok(Some(cbor!(1).unwrap())), cbor!({ "a" => [0, 2], "b" => null }))]
+#[case::replace_root("", false, Ok(Some(cbor!({ "a" => [1, 2], "b" => null }).unwrap())), cbor!(0))]
+#[case::append("/a/-", false, Ok(None), cbor!({ "a" => [1, 2, 0], "b" => null }))]
+#[case::append_index("/a/2", false, Ok(None), cbor!({ "a" => [1, 2, 0], "b" => null }))]
+#[case::add_key("/c", false, Ok(None), cbor!({ "a" => [1, 2], "b" => null, "c" => 0 }))]
+#[case::past_end("/a/3", false, Err(r#"cannot add "3" to "/a""#), cbor!({ "a" => [1, 2], "b" => null }))]
+#[case::missing("/c/d", false, Err(r#""/c" does not exist"#), cbor!({ "a" => [1, 2], "b" => null }))]
+#[case::not_container("/a/0/x", true, Err(r#"cannot add "x" to "/a/0""#), cbor!({ "a" => [1, 2], "b" => null }))]
+#[case::create_map("/c/d/e", true, Ok(None), cbor!({ "a" => [1, 2], "b" => null, "c" => { "d" => { "e" => 0 } } }))]
+#[case::create_array("/c/-/0", true, Ok(None), cbor!({ "a" => [1, 2], "b" => null, "c" => [[0]] }))]
+#[case::replace_null("/b/x", true, Ok(None), cbor!({ "a" => [1, 2], "b" => { "x" => 0 } }))]
+#[case::null_not_created("/b/x", false, Err(r#"cannot add "x" to "/b""#), cbor!({ "a" => [1, 2], "b" => null }))]
+fn pointer_set(
+    #[case] pointer: &str,
+    #[case] create: bool,
+    #[case] result: Result<Option<Value>, &str>,
+    #[case] expected: Result<Value, ciborium::value::Error>,
+) {
+    let mut value = cbor!({ "a" => [1, 2], "b" => null }).unwrap();
+
+    match (value.pointer_set(pointer, 0.into(), create), result) {
+        (Ok(old), Ok(result)) => assert_eq!(old, result),
+        (Err(ciborium::value::Error::Custom(msg)), Err(result)) => assert_eq!(msg, result),
+        (old, result) => panic!("{:?} != {:?}", old, result),
+    }
+
+    assert_eq!(value, expected.unwrap());
+}
+
+#[rstest::rstest]
+#[case("0", Some(0))]
+#[case("-1", Some(-1))]
+#[case("+7", Some(7))]
+#[case("18446744073709551615", Some(u64::MAX.into()))]
+#[case("-18446744073709551616", Some(-(1 << 64)))]
+#[case("18446744073709551616", None)]
+#[case("-18446744073709551617", None)]
+#[case("1.0", None)]
+#[case("", None)]
+fn integer_text(#[case] text: &str, #[case] expected: Option<i128>) {
+    use ciborium::value::Integer;
+
+    let parsed = text.parse::<Integer>().ok();
+    assert_eq!(parsed.map(i128::from), expected);
+    assert_eq!(Integer::try_from(text).ok(), parsed);
+
+    if let Some(integer) = parsed {
+        assert_eq!(integer.to_string(), expected.unwrap().to_string());
+    }
+}
+
+#[test]
+fn integer_arithmetic() {
+    use ciborium::value::Integer;
+
+    let int = |x: i128| Integer::try_from(x).unwrap();
+    let max = u64::MAX as i128;
+
+    assert_eq!(i128::from(Integer::MAX), max);
+    assert_eq!(i128::from(Integer::MIN), -max - 1);
+
+    assert_eq!(int(max - 1).checked_add(int(1)), Some(Integer::MAX));
+    assert_eq!(Integer::MAX.checked_add(int(1)), None);
+    assert_eq!(Integer::MIN.checked_add(Integer::MAX), Some(int(-1)));
+
+    assert_eq!(int(-max).checked_sub(int(1)), Some(Integer::MIN));
+    assert_eq!(Integer::MIN.checked_sub(int(1)), None);
+    assert_eq!(int(0).checked_sub(Integer::MAX), Some(int(-max)));
+
+    assert_eq!(int(-3).checked_mul(int(4)), Some(int(-12)));
+    assert_eq!(Integer::MAX.checked_mul(int(2)), None);
+    assert_eq!(Integer::MIN.checked_mul(Integer::MIN), None);
+
+    assert_eq!(Integer::MAX.checked_neg(), Some(int(-max)));
+    assert_eq!(Integer::MIN.checked_neg(), None);
+    assert_eq!(int(-5).checked_abs(), Some(int(5)));
+    assert_eq!(Integer::MIN.checked_abs(), None);
+    assert_eq!(Integer::MIN.unsigned_abs(), 1 << 64);
+
+    assert!(int(-1).is_negative() && !int(-1).is_positive());
+    assert!(!int(0).is_negative() && !int(0).is_positive());
+    assert!(!int(1).is_negative() && int(1).is_positive());
+}
+
+#[rstest::rstest]
+#[case("c2510100000000000000000000000000000000", 2)]
+#[case("c3510100000000000000000000000000000000", 3)]
+#[case("c25f48010000000000000049000000000000000000ff", 2)]
+fn oversized_bignum(#[case] hex: &str, #[case] tag: u64) {
+    let bytes = hex::decode(hex).unwrap();
+
+    let value: Value = ciborium::from_reader(&bytes[..]).unwrap();
+    match &value {
+        Value::Tag(t, x) => {
+            assert_eq!(*t, tag);
+            assert_eq!(x.as_bytes().map(Vec::len), Some(17));
+        }
+        value => panic!("unexpected value: {:?}", value),
+    }
+
+    // The bignum passes through both the encoder and the value deserializer.
+    assert_eq!(value.deserialized::<Value>().unwrap(), value);
+    let mut encoded = Vec::new();
+    ciborium::into_writer(&value, &mut encoded).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<Value, _>(&encoded[..]).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn into_deserializer() {
+    use ciborium::value::Error;
+    use serde::de::{value::MapDeserializer, IntoDeserializer};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Config {
+        name: String,
+        port: u16,
+        tags: Vec<String>,
+    }
+
+    let value = cbor!({ "name" => "db", "port" => 5432, "tags" => ["a", "b"] }).unwrap();
+    let expected = Config {
+        name: "db".into(),
+        port: 5432,
+        tags: vec!["a".into(), "b".into()],
+    };
+
+    let borrowed = Config::deserialize(IntoDeserializer::<Error>::into_deserializer(&value));
+    assert_eq!(borrowed.unwrap(), expected);
+
+    let owned = Config::deserialize(IntoDeserializer::<Error>::into_deserializer(value));
+    assert_eq!(owned.unwrap(), expected);
+
+    // Values can stand in for the entries of serde's own deserializers.
+    let entries = [
+        ("name", cbor!("db").unwrap()),
+        ("port", cbor!(5432).unwrap()),
+        ("tags", cbor!(["a", "b"]).unwrap()),
+    ];
+
+    let layered = Config::deserialize(MapDeserializer::<_, Error>::new(entries.into_iter()));
+    assert_eq!(layered.unwrap(), expected);
+}
+
+#[test]
+fn iterators() {
+    let mut array = cbor!([1, "a", null]).unwrap();
+    assert_eq!(array.iter().len(), 3);
+    assert_eq!(array.iter().next_back(), Some(&Value::Null));
+
+    for item in &mut array {
+        *item = Value::Bool(item.is_null());
+    }
+    assert_eq!(array, cbor!([false, false, true]).unwrap());
+
+    let items: Vec<Value> = Value::Tag(258, Box::new(array)).into_iter().collect();
+    assert_eq!(
+        items,
+        [Value::Bool(false), Value::Bool(false), Value::Bool(true)]
+    );
+
+    let mut map = cbor!({ "a" => 1, "b" => 2 }).unwrap();
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        [&cbor!("a").unwrap(), &cbor!("b").unwrap()]
+    );
+    assert_eq!(
+        map.values().collect::<Vec<_>>(),
+        [&cbor!(1).unwrap(), &cbor!(2).unwrap()]
+    );
+    assert_eq!(
+        map.entries().nth(1),
+        Some((&cbor!("b").unwrap(), &cbor!(2).unwrap()))
+    );
+
+    map.values_mut().for_each(|v| *v = Value::Null);
+    for (key, value) in map.entries_mut() {
+        *value = key.clone();
+    }
+    assert_eq!(map, cbor!({ "a" => "a", "b" => "b" }).unwrap());
+
+    // Values of other kinds have no items or entries.
+    assert_eq!(map.iter().count(), 0);
+    assert_eq!(cbor!([1]).unwrap().keys().count(), 0);
+    assert_eq!(Value::Null.into_iter().count(), 0);
+}
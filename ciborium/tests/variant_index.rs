@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::ser::{into_writer_with_options, Options};
+use ciborium::{cbor, de::from_reader, Value};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Command {
+    Reset,
+    Set(u8),
+    Move(i8, i8),
+    Configure { rate: u16 },
+}
+
+#[rstest]
+#[case(Command::Reset, "00", cbor!(0))]
+#[case(Command::Set(5), "a10105", cbor!({ 1 => 5 }))]
+#[case(Command::Move(1, -1), "a102820120", cbor!({ 2 => [1, -1] }))]
+#[case(Command::Configure { rate: 9 }, "a103a1647261746509", cbor!({ 3 => { "rate" => 9 } }))]
+fn variant_index(
+    #[case] command: Command,
+    #[case] encoded: &str,
+    #[case] value: Result<Value, ciborium::value::Error>,
+) {
+    let value = value.unwrap();
+
+    let mut bytes = Vec::new();
+    let options = Options::new().variant_index(true);
+    into_writer_with_options(&command, &mut bytes, options).unwrap();
+    assert_eq!(hex::encode(&bytes), encoded);
+
+    assert_eq!(from_reader::<Value, _>(&bytes[..]).unwrap(), value);
+    assert_eq!(from_reader::<Command, _>(&bytes[..]).unwrap(), command);
+    assert_eq!(value.deserialized::<Command>().unwrap(), command);
+
+    // Names are still accepted alongside indices.
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&command, &mut bytes).unwrap();
+    assert_eq!(from_reader::<Command, _>(&bytes[..]).unwrap(), command);
+}
+
+#[test]
+fn unknown_index() {
+    let result = from_reader::<Command, _>(&[0xa1, 0x04, 0x00][..]);
+    assert!(result.is_err());
+
+    let value = cbor!({ 4 => 0 }).unwrap();
+    assert!(value.deserialized::<Command>().is_err());
+}
+
+#[test]
+fn field_index() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Pair {
+        a: u8,
+        b: u8,
+    }
+
+    // {0: 1, 1: 2}
+    let bytes = [0xa2, 0x00, 0x01, 0x01, 0x02];
+    assert!(from_reader::<Pair, _>(&bytes[..]).is_err());
+
+    let value = cbor!({ 0 => 1, 1 => 2 }).unwrap();
+    assert!(value.deserialized::<Pair>().is_err());
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use ciborium::{cbor, Value};
+use js_sys::{Array, Map, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn roundtrip() {
+    let values = [
+        cbor!(null).unwrap(),
+        cbor!(true).unwrap(),
+        cbor!(-7).unwrap(),
+        cbor!(u64::MAX).unwrap(),
+        cbor!(1.5).unwrap(),
+        cbor!("text").unwrap(),
+        Value::Bytes(vec![1, 2, 3]),
+        cbor!([1, [2, 3], { "a" => 4 }]).unwrap(),
+        cbor!({ 1 => "x", "y" => [] }).unwrap(),
+    ];
+
+    for value in values {
+        let js = JsValue::from(&value);
+        assert_eq!(Value::try_from(&js).unwrap(), value);
+    }
+}
+
+#[wasm_bindgen_test]
+fn types() {
+    assert!(JsValue::from(cbor!(1).unwrap()).as_f64().is_some());
+    assert!(JsValue::from(cbor!(u64::MAX).unwrap()).is_bigint());
+    assert!(JsValue::from(Value::Bytes(vec![1])).is_instance_of::<Uint8Array>());
+    assert!(JsValue::from(cbor!([1]).unwrap()).is_instance_of::<Array>());
+    assert!(JsValue::from(cbor!({ 1 => 2 }).unwrap()).is_instance_of::<Map>());
+
+    // Tags are dropped.
+    let tagged = Value::Tag(1, Box::new(cbor!(0).unwrap()));
+    assert_eq!(JsValue::from(&tagged).as_f64(), Some(0.0));
+}
+
+#[wasm_bindgen_test]
+fn from_js() {
+    assert_eq!(Value::try_from(JsValue::UNDEFINED).unwrap(), Value::Null);
+    assert_eq!(
+        Value::try_from(JsValue::from_f64(2.0)).unwrap(),
+        cbor!(2).unwrap()
+    );
+
+    let object = js_sys::JSON::parse(r#"{"a": [1, 0.5]}"#).unwrap();
+    let value = Value::try_from(object).unwrap();
+    assert_eq!(value, cbor!({ "a" => [1, 0.5] }).unwrap());
+
+    // Cyclic arrays are rejected rather than overflowing the stack.
+    let array = Array::new();
+    array.push(&array);
+    assert!(Value::try_from(JsValue::from(array)).is_err());
+}